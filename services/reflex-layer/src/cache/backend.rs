@@ -0,0 +1,41 @@
+//! Low-level cache storage backend
+//!
+//! [`Cache`](crate::cache::Cache) is the rich, TTL-enum, crypto-aware API the
+//! rest of the service talks to. `CacheBackend` is the thinner primitive
+//! layer underneath it: raw get/set/incr/expire/delete on plain strings,
+//! with no notion of TTL presets or at-rest encryption. `RedisCache`
+//! implements it directly against its Redis connection, and
+//! [`InMemoryCache`](crate::cache::InMemoryCache) implements it against a
+//! `DashMap`, so a failed Redis health check at startup can degrade the
+//! service to a real in-memory cache instead of `main()`'s
+//! "Continuing without Redis" log line papering over a cache that silently
+//! errors on every request.
+
+use async_trait::async_trait;
+
+use crate::cache::types::CacheError;
+
+/// Raw cache storage primitives, implemented by a live Redis connection or
+/// an in-memory fallback
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Fetch the raw value stored at `key`, or `None` on a miss
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError>;
+
+    /// Store `value` at `key`, expiring after `ttl_secs` seconds
+    /// (`None` means no expiration)
+    async fn set(&self, key: &str, value: &str, ttl_secs: Option<u64>) -> Result<(), CacheError>;
+
+    /// Atomically add `delta` to the integer counter stored at `key`
+    /// (treating a missing or unparseable value as 0) and return the new
+    /// total
+    async fn incr(&self, key: &str, delta: i64) -> Result<i64, CacheError>;
+
+    /// Set (or refresh) the expiration on an existing key
+    ///
+    /// Returns `false` if `key` doesn't exist.
+    async fn expire(&self, key: &str, ttl_secs: u64) -> Result<bool, CacheError>;
+
+    /// Remove `key`
+    async fn delete(&self, key: &str) -> Result<(), CacheError>;
+}
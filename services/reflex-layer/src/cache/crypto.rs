@@ -0,0 +1,154 @@
+//! Transparent at-rest encryption for cached values
+//!
+//! Cache values (serialized detection verdicts, which may embed flagged PII
+//! snippets via `matched_text`) can be encrypted before they leave the process
+//! and are only ever decrypted in memory. The cache key itself is bound as AEAD
+//! associated data, so a ciphertext cannot be replayed verbatim under a
+//! different cache key.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
+
+use crate::cache::types::CacheError;
+
+/// Length of the ChaCha20-Poly1305 key, in bytes
+pub const CACHE_CRYPTO_KEY_LEN: usize = 32;
+
+/// Length of the random nonce prepended to each ciphertext, in bytes
+const NONCE_LEN: usize = 12;
+
+/// AEAD encryption/decryption for cache values at rest
+///
+/// Each call to [`CacheCrypto::encrypt`] draws a fresh random 96-bit nonce and
+/// prepends it to the ciphertext before base64-encoding the result, so the
+/// output is safe to store as a plain cache value. The cache key that the
+/// value will be stored under is bound as associated data, which prevents a
+/// captured ciphertext from being replayed under a different key.
+#[derive(Clone)]
+pub struct CacheCrypto {
+    cipher: ChaCha20Poly1305,
+}
+
+impl CacheCrypto {
+    /// Create a new `CacheCrypto` from a 32-byte key
+    pub fn new(key: &[u8; CACHE_CRYPTO_KEY_LEN]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Encrypt a plaintext cache value
+    ///
+    /// `cache_key` is bound as AEAD associated data and must be passed
+    /// unchanged to [`CacheCrypto::decrypt`]. Returns a base64-encoded
+    /// `nonce || ciphertext` string suitable for storing as the cache value.
+    pub fn encrypt(&self, cache_key: &str, plaintext: &str) -> Result<String, CacheError> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: cache_key.as_bytes(),
+                },
+            )
+            .map_err(|e| CacheError::Crypto(format!("Cache value encryption failed: {}", e)))?;
+
+        let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+
+        Ok(STANDARD.encode(out))
+    }
+
+    /// Decrypt a cache value produced by [`CacheCrypto::encrypt`]
+    ///
+    /// `cache_key` must match the value passed to `encrypt`. Returns `None`
+    /// on any decoding, tag-verification, or UTF-8 failure, so that a stale
+    /// value (e.g. written under a key that has since been rotated) is
+    /// treated as a cache miss rather than a hard error.
+    pub fn decrypt(&self, cache_key: &str, encoded: &str) -> Option<String> {
+        let raw = STANDARD.decode(encoded).ok()?;
+        if raw.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce_bytes, ciphertext) = raw.split_at(NONCE_LEN);
+        let nonce = Nonce::from_slice(nonce_bytes);
+
+        let plaintext = self
+            .cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: ciphertext,
+                    aad: cache_key.as_bytes(),
+                },
+            )
+            .ok()?;
+
+        String::from_utf8(plaintext).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_crypto() -> CacheCrypto {
+        CacheCrypto::new(&[7u8; CACHE_CRYPTO_KEY_LEN])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let crypto = test_crypto();
+        let encrypted = crypto.encrypt("reflex:cache:abc123", "sensitive verdict").unwrap();
+        let decrypted = crypto.decrypt("reflex:cache:abc123", &encrypted);
+        assert_eq!(decrypted, Some("sensitive verdict".to_string()));
+    }
+
+    #[test]
+    fn test_encrypt_output_is_not_plaintext() {
+        let crypto = test_crypto();
+        let encrypted = crypto.encrypt("reflex:cache:abc123", "sensitive verdict").unwrap();
+        assert!(!encrypted.contains("sensitive verdict"));
+    }
+
+    #[test]
+    fn test_encrypt_is_nondeterministic() {
+        let crypto = test_crypto();
+        let a = crypto.encrypt("reflex:cache:abc123", "value").unwrap();
+        let b = crypto.encrypt("reflex:cache:abc123", "value").unwrap();
+        assert_ne!(a, b, "fresh random nonce should produce different ciphertexts");
+    }
+
+    #[test]
+    fn test_decrypt_wrong_cache_key_fails_as_miss() {
+        let crypto = test_crypto();
+        let encrypted = crypto.encrypt("reflex:cache:abc123", "sensitive verdict").unwrap();
+        assert_eq!(crypto.decrypt("reflex:cache:other", &encrypted), None);
+    }
+
+    #[test]
+    fn test_decrypt_wrong_key_fails_as_miss() {
+        let crypto_a = CacheCrypto::new(&[1u8; CACHE_CRYPTO_KEY_LEN]);
+        let crypto_b = CacheCrypto::new(&[2u8; CACHE_CRYPTO_KEY_LEN]);
+
+        let encrypted = crypto_a.encrypt("reflex:cache:abc123", "value").unwrap();
+        assert_eq!(crypto_b.decrypt("reflex:cache:abc123", &encrypted), None);
+    }
+
+    #[test]
+    fn test_decrypt_garbage_input_fails_as_miss() {
+        let crypto = test_crypto();
+        assert_eq!(crypto.decrypt("reflex:cache:abc123", "not-valid-base64!!"), None);
+        assert_eq!(crypto.decrypt("reflex:cache:abc123", "dG9vIHNob3J0"), None);
+    }
+}
@@ -0,0 +1,225 @@
+//! HyperLogLog cardinality estimation
+//!
+//! A fixed-memory estimator for "how many distinct items have I seen?",
+//! used by [`CacheStats`](crate::cache::CacheStats) to answer "how many
+//! distinct cache keys / requesters are we seeing?" without tracking a
+//! growing set of every key ever touched.
+
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use std::collections::hash_map::DefaultHasher;
+
+/// Number of register-index bits; `2^PRECISION` registers are kept
+/// (`p=14` -> 16,384 one-byte registers -> 16KB per instance)
+const PRECISION: u32 = 14;
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// Fixed-memory, lock-free cardinality estimator
+///
+/// Each `record` hashes its input to 64 bits, uses the top `PRECISION`
+/// bits to pick one of `2^PRECISION` registers, and stores
+/// `max(register, 1 + leading_zeros(remaining bits))`, updated with a
+/// relaxed CAS-max loop so recording stays lock-free like
+/// [`CacheStats`](crate::cache::CacheStats)'s other counters. Cardinality
+/// is then estimated from the harmonic mean of the registers, with small-
+/// and large-range corrections per the standard HyperLogLog algorithm.
+pub struct HyperLogLog {
+    registers: Vec<AtomicU8>,
+}
+
+impl HyperLogLog {
+    /// Create an estimator with all registers at zero (cardinality 0)
+    pub fn new() -> Self {
+        Self {
+            registers: (0..NUM_REGISTERS).map(|_| AtomicU8::new(0)).collect(),
+        }
+    }
+
+    /// Record an occurrence of `item`
+    pub fn record(&self, item: &str) {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        self.record_hash(hasher.finish());
+    }
+
+    fn record_hash(&self, hash: u64) {
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // The remaining bits, with a guard bit set so leading_zeros() can't
+        // exceed the bits actually available to inspect.
+        let remaining = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = 1 + remaining.leading_zeros() as u8;
+
+        let register = &self.registers[index];
+        let mut current = register.load(Ordering::Relaxed);
+        while rank > current {
+            match register.compare_exchange_weak(
+                current,
+                rank,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(observed) => current = observed,
+            }
+        }
+    }
+
+    /// Reset every register back to zero (cardinality 0)
+    pub fn reset(&self) {
+        for register in &self.registers {
+            register.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Merge `other`'s registers into `self` (register-wise max), so
+    /// per-shard estimators can be combined into a global estimate
+    /// without either side losing what it's already seen
+    pub fn merge(&self, other: &HyperLogLog) {
+        for (mine, theirs) in self.registers.iter().zip(other.registers.iter()) {
+            let theirs = theirs.load(Ordering::Relaxed);
+            let mut current = mine.load(Ordering::Relaxed);
+            while theirs > current {
+                match mine.compare_exchange_weak(
+                    current,
+                    theirs,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(observed) => current = observed,
+                }
+            }
+        }
+    }
+
+    /// Estimate the number of distinct items recorded
+    pub fn estimate(&self) -> u64 {
+        let m = NUM_REGISTERS as f64;
+        let registers: Vec<u8> = self
+            .registers
+            .iter()
+            .map(|r| r.load(Ordering::Relaxed))
+            .collect();
+
+        let alpha_m = match NUM_REGISTERS {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+
+        let sum_inverse: f64 = registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw_estimate = alpha_m * m * m / sum_inverse;
+
+        let zero_registers = registers.iter().filter(|&&r| r == 0).count();
+
+        if raw_estimate <= 2.5 * m && zero_registers > 0 {
+            // Small-range correction: linear counting over empty registers
+            (m * (m / zero_registers as f64).ln()).round() as u64
+        } else {
+            raw_estimate.round() as u64
+        }
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for HyperLogLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HyperLogLog")
+            .field("estimate", &self.estimate())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_estimate_is_zero() {
+        let hll = HyperLogLog::new();
+        assert_eq!(hll.estimate(), 0);
+    }
+
+    #[test]
+    fn test_estimate_is_close_for_known_cardinality() {
+        let hll = HyperLogLog::new();
+        for i in 0..10_000 {
+            hll.record(&format!("key-{i}"));
+        }
+
+        let estimate = hll.estimate() as f64;
+        // Standard error for p=14 is ~0.8%; allow a generous 5% band.
+        assert!(
+            (estimate - 10_000.0).abs() / 10_000.0 < 0.05,
+            "estimate {} too far from 10000",
+            estimate
+        );
+    }
+
+    #[test]
+    fn test_repeated_items_dont_inflate_estimate() {
+        let hll = HyperLogLog::new();
+        for _ in 0..1000 {
+            hll.record("same-key");
+        }
+
+        assert_eq!(hll.estimate(), 1);
+    }
+
+    #[test]
+    fn test_merge_combines_two_disjoint_estimators() {
+        let a = HyperLogLog::new();
+        let b = HyperLogLog::new();
+
+        for i in 0..5_000 {
+            a.record(&format!("a-{i}"));
+        }
+        for i in 0..5_000 {
+            b.record(&format!("b-{i}"));
+        }
+
+        a.merge(&b);
+        let estimate = a.estimate() as f64;
+        assert!(
+            (estimate - 10_000.0).abs() / 10_000.0 < 0.05,
+            "merged estimate {} too far from 10000",
+            estimate
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_the_estimate() {
+        let hll = HyperLogLog::new();
+        for i in 0..100 {
+            hll.record(&format!("key-{i}"));
+        }
+        assert!(hll.estimate() > 0);
+
+        hll.reset();
+        assert_eq!(hll.estimate(), 0);
+    }
+
+    #[test]
+    fn test_merge_is_idempotent_for_same_data() {
+        let a = HyperLogLog::new();
+        for i in 0..1_000 {
+            a.record(&format!("key-{i}"));
+        }
+        let before = a.estimate();
+
+        let b = HyperLogLog::new();
+        for i in 0..1_000 {
+            b.record(&format!("key-{i}"));
+        }
+        a.merge(&b);
+
+        assert_eq!(a.estimate(), before);
+    }
+}
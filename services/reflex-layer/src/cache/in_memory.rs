@@ -0,0 +1,331 @@
+//! In-memory `CacheBackend`/`Cache` fallback
+//!
+//! Backs the service when Redis is unreachable at startup (see `main.rs`'s
+//! "Continuing without Redis" path) and doubles as a dependency-free mock
+//! for tests. Entries live in a `DashMap` with a lazily-checked expiry, plus
+//! a background sweeper (started via [`spawn_sweeper`](InMemoryCache::spawn_sweeper))
+//! that periodically purges expired entries so the map doesn't grow
+//! unbounded between accesses.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use tokio::task::JoinHandle;
+
+use crate::cache::backend::CacheBackend;
+use crate::cache::types::{Cache, CacheError, CacheStats, CacheTTL};
+
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| Instant::now() >= at)
+    }
+}
+
+/// `DashMap`-backed in-memory cache, implementing both [`CacheBackend`] and
+/// the higher-level [`Cache`] trait so it can stand in for `RedisCache`/
+/// `TwoTierCache` wherever `AppState` needs a cache
+pub struct InMemoryCache {
+    entries: DashMap<String, Entry>,
+    stats: CacheStats,
+}
+
+impl InMemoryCache {
+    /// Create an empty in-memory cache
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            stats: CacheStats::new(),
+        }
+    }
+
+    /// Remove all expired entries; returns the number removed
+    pub fn sweep_expired(&self) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| !entry.is_expired());
+        before - self.entries.len()
+    }
+
+    /// Spawn a background task that sweeps expired entries every `interval`
+    pub fn spawn_sweeper(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let cache = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let removed = cache.sweep_expired();
+                if removed > 0 {
+                    tracing::debug!("In-memory cache sweep removed {} expired entries", removed);
+                }
+            }
+        })
+    }
+
+    /// Number of entries currently stored, including not-yet-swept expired ones
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for InMemoryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        let Some(entry) = self.entries.get(key) else {
+            return Ok(None);
+        };
+
+        if entry.is_expired() {
+            drop(entry);
+            self.entries.remove(key);
+            return Ok(None);
+        }
+
+        // A garbled or non-UTF8 stored value is treated as a miss rather
+        // than erroring or panicking.
+        Ok(String::from_utf8(entry.value.clone()).ok())
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl_secs: Option<u64>) -> Result<(), CacheError> {
+        let expires_at = ttl_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                value: value.as_bytes().to_vec(),
+                expires_at,
+            },
+        );
+        Ok(())
+    }
+
+    async fn incr(&self, key: &str, delta: i64) -> Result<i64, CacheError> {
+        let mut entry = self.entries.entry(key.to_string()).or_insert_with(|| Entry {
+            value: b"0".to_vec(),
+            expires_at: None,
+        });
+
+        if entry.is_expired() {
+            entry.value = b"0".to_vec();
+            entry.expires_at = None;
+        }
+
+        // A malformed counter (non-UTF8 or non-numeric) restarts from 0
+        // rather than failing the request.
+        let current: i64 = std::str::from_utf8(&entry.value)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let new_value = current + delta;
+        entry.value = new_value.to_string().into_bytes();
+        Ok(new_value)
+    }
+
+    async fn expire(&self, key: &str, ttl_secs: u64) -> Result<bool, CacheError> {
+        match self.entries.get_mut(key) {
+            Some(mut entry) if !entry.is_expired() => {
+                entry.expires_at = Some(Instant::now() + Duration::from_secs(ttl_secs));
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.entries.remove(key);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Cache for InMemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        self.stats.record_key(key);
+        match CacheBackend::get(self, key).await? {
+            Some(value) => {
+                self.stats.record_hit();
+                Ok(Some(value))
+            }
+            None => {
+                self.stats.record_miss();
+                Ok(None)
+            }
+        }
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: CacheTTL) -> Result<(), CacheError> {
+        CacheBackend::set(self, key, value, ttl.as_seconds()).await?;
+        self.stats.record_set();
+        self.stats.record_key(key);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        CacheBackend::delete(self, key).await?;
+        self.stats.record_delete();
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        Ok(CacheBackend::get(self, key).await?.is_some())
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> Result<u64, CacheError> {
+        use crate::cache::key::validate_cache_pattern;
+
+        validate_cache_pattern(pattern)?;
+
+        // No SCAN/KEYS glob here, just the `prefix:*` shape every call site
+        // in this codebase actually uses.
+        let prefix = pattern.trim_end_matches('*');
+        let matching: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key().starts_with(prefix))
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        for key in &matching {
+            self.entries.remove(key);
+        }
+
+        Ok(matching.len() as u64)
+    }
+
+    fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backend_get_set_round_trip() {
+        let cache = InMemoryCache::new();
+        cache.set("key1", "value1", None).await.unwrap();
+        assert_eq!(cache.get("key1").await.unwrap(), Some("value1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_backend_get_miss() {
+        let cache = InMemoryCache::new();
+        assert_eq!(cache.get("missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_backend_ttl_expiry() {
+        let cache = InMemoryCache::new();
+        cache.set("key1", "value1", Some(0)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(cache.get("key1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_backend_incr_from_missing_key() {
+        let cache = InMemoryCache::new();
+        assert_eq!(cache.incr("counter", 5).await.unwrap(), 5);
+        assert_eq!(cache.incr("counter", 3).await.unwrap(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_backend_incr_tolerates_garbled_value() {
+        let cache = InMemoryCache::new();
+        cache.set("counter", "not-a-number", None).await.unwrap();
+        assert_eq!(cache.incr("counter", 1).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backend_get_tolerates_non_utf8_value() {
+        let cache = InMemoryCache::new();
+        cache.entries.insert(
+            "binary".to_string(),
+            Entry {
+                value: vec![0xff, 0xfe, 0xfd],
+                expires_at: None,
+            },
+        );
+        assert_eq!(cache.get("binary").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_backend_expire_missing_key_returns_false() {
+        let cache = InMemoryCache::new();
+        assert!(!cache.expire("missing", 60).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_backend_expire_existing_key() {
+        let cache = InMemoryCache::new();
+        cache.set("key1", "value1", None).await.unwrap();
+        assert!(cache.expire("key1", 0).await.unwrap());
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(cache.get("key1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_backend_sweep_expired() {
+        let cache = InMemoryCache::new();
+        cache.set("expires", "value", Some(0)).await.unwrap();
+        cache.set("stays", "value", None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert_eq!(cache.sweep_expired(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_trait_hit_and_miss() {
+        let cache = InMemoryCache::new();
+        Cache::set(&cache, "key1", "value1", CacheTTL::Short)
+            .await
+            .unwrap();
+        assert_eq!(
+            Cache::get(&cache, "key1").await.unwrap(),
+            Some("value1".to_string())
+        );
+        Cache::get(&cache, "missing").await.unwrap();
+
+        let stats = cache.stats().snapshot();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.sets, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_trait_invalidate_pattern() {
+        let cache = InMemoryCache::new();
+        Cache::set(&cache, "reflex:pattern:a", "1", CacheTTL::Short)
+            .await
+            .unwrap();
+        Cache::set(&cache, "reflex:pattern:b", "2", CacheTTL::Short)
+            .await
+            .unwrap();
+        Cache::set(&cache, "reflex:other:c", "3", CacheTTL::Short)
+            .await
+            .unwrap();
+
+        let deleted = Cache::invalidate_pattern(&cache, "reflex:pattern:*")
+            .await
+            .unwrap();
+        assert_eq!(deleted, 2);
+        assert!(Cache::get(&cache, "reflex:other:c").await.unwrap().is_some());
+    }
+}
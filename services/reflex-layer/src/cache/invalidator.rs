@@ -0,0 +1,246 @@
+//! Cross-instance cache invalidation over Redis Pub/Sub
+//!
+//! A write or purge on one replica (e.g. [`RedisCache::invalidate_pattern`])
+//! only drops that replica's own entries; peers holding the same data in an
+//! in-process layer (e.g. [`TwoTierCache`](crate::cache::TwoTierCache)'s L1)
+//! never find out. [`CacheInvalidator`] closes that gap: publishers announce
+//! the affected pattern on [`INVALIDATION_CHANNEL`], and every instance runs
+//! a background listener (see [`CacheInvalidator::spawn_listener`]) that
+//! invokes registered callbacks for patterns other instances announced.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::cache::types::CacheError;
+use crate::redis_client::RedisClient;
+
+/// Redis Pub/Sub channel invalidation announcements are published on
+pub const INVALIDATION_CHANNEL: &str = "reflex:invalidate";
+
+/// A registered invalidation callback: invoked with the invalidated pattern
+pub type InvalidationCallback = Box<dyn Fn(&str) + Send + Sync>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InvalidationMessage {
+    instance_id: String,
+    sequence: u64,
+    pattern: String,
+}
+
+/// Publishes and listens for cross-instance cache invalidation events
+///
+/// Every instance gets its own randomly-generated `instance_id`; that id
+/// (plus a per-publisher sequence number) rides along on each message so a
+/// publisher's own listener ignores events it published itself.
+pub struct CacheInvalidator {
+    redis: Arc<RedisClient>,
+    instance_id: String,
+    sequence: AtomicU64,
+    pub(crate) listeners: DashMap<u64, InvalidationCallback>,
+    next_listener_id: AtomicU64,
+}
+
+impl CacheInvalidator {
+    /// Create a new invalidator with a fresh random instance id
+    pub fn new(redis: Arc<RedisClient>) -> Self {
+        Self {
+            redis,
+            instance_id: Uuid::new_v4().to_string(),
+            sequence: AtomicU64::new(0),
+            listeners: DashMap::new(),
+            next_listener_id: AtomicU64::new(0),
+        }
+    }
+
+    /// This instance's id, as it appears in published messages
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Publish an invalidation announcement for `pattern`
+    ///
+    /// Callers invoke this after a write or purge that other instances'
+    /// in-process caches need to react to (e.g. alongside
+    /// [`RedisCache::invalidate_pattern`](crate::cache::RedisCache::invalidate_pattern)).
+    pub async fn publish(&self, pattern: &str) -> Result<(), CacheError> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let message = InvalidationMessage {
+            instance_id: self.instance_id.clone(),
+            sequence,
+            pattern: pattern.to_string(),
+        };
+        let payload =
+            serde_json::to_string(&message).map_err(|e| CacheError::Serialization(e.to_string()))?;
+
+        let mut conn = self.redis.get_connection(INVALIDATION_CHANNEL).await?;
+        conn.publish::<_, _, ()>(INVALIDATION_CHANNEL, payload)
+            .await
+            .map_err(CacheError::Redis)?;
+
+        debug!(
+            "Published invalidation for pattern {} (seq {})",
+            pattern, sequence
+        );
+        Ok(())
+    }
+
+    /// Register a callback invoked with the pattern of every invalidation
+    /// this instance receives from a peer
+    ///
+    /// Returns a subscription id to pass to [`CacheInvalidator::unsubscribe`].
+    pub fn subscribe(&self, callback: impl Fn(&str) + Send + Sync + 'static) -> u64 {
+        let id = self.next_listener_id.fetch_add(1, Ordering::Relaxed);
+        self.listeners.insert(id, Box::new(callback));
+        id
+    }
+
+    /// Remove a previously-registered callback; a no-op if `id` is unknown
+    pub fn unsubscribe(&self, id: u64) {
+        self.listeners.remove(&id);
+    }
+
+    /// Spawn a background task holding a dedicated Pub/Sub connection that
+    /// listens on [`INVALIDATION_CHANNEL`] and fans out every peer-published
+    /// invalidation to the registered callbacks
+    ///
+    /// Pub/Sub needs a connection dedicated to it (the pool's connections
+    /// are shared and get recycled), so this opens its own outside the
+    /// pool, reconnecting with a short backoff if it drops. The connection
+    /// targets the client's configured `url`, which is only meaningful for
+    /// `Standalone` deployments; fanning this out across every Cluster
+    /// shard or the Sentinel-resolved master is out of scope for this
+    /// change.
+    pub fn spawn_listener(self: &Arc<Self>) -> JoinHandle<()> {
+        let invalidator = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = invalidator.run_listener().await {
+                    error!(
+                        "Cache invalidation listener error, reconnecting in 1s: {}",
+                        e
+                    );
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        })
+    }
+
+    async fn run_listener(&self) -> Result<(), CacheError> {
+        let client = redis::Client::open(self.redis.config().url.clone())
+            .map_err(CacheError::Redis)?;
+        let conn = client
+            .get_async_connection()
+            .await
+            .map_err(CacheError::Redis)?;
+        let mut pubsub = conn.into_pubsub();
+        pubsub
+            .subscribe(INVALIDATION_CHANNEL)
+            .await
+            .map_err(CacheError::Redis)?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = match msg.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!("Failed to decode invalidation message payload: {}", e);
+                    continue;
+                }
+            };
+
+            let message: InvalidationMessage = match serde_json::from_str(&payload) {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!("Failed to parse invalidation message: {}", e);
+                    continue;
+                }
+            };
+
+            if message.instance_id == self.instance_id {
+                continue; // ignore our own announcements
+            }
+
+            debug!(
+                "Received invalidation for pattern {} from {} (seq {})",
+                message.pattern, message.instance_id, message.sequence
+            );
+            for listener in self.listeners.iter() {
+                (listener.value())(&message.pattern);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedisConfig;
+    use std::sync::Mutex;
+
+    fn test_redis_config() -> RedisConfig {
+        RedisConfig {
+            url: "redis://localhost:6379".to_string(),
+            pool_size: 10,
+            connection_timeout_ms: 5000,
+            command_timeout_ms: 3000,
+            cache_ttl_secs: 300,
+            ..Default::default()
+        }
+    }
+
+    fn make_invalidator() -> CacheInvalidator {
+        let redis = RedisClient::new(test_redis_config()).unwrap();
+        CacheInvalidator::new(Arc::new(redis))
+    }
+
+    #[test]
+    fn test_instance_ids_are_unique() {
+        let a = make_invalidator();
+        let b = make_invalidator();
+        assert_ne!(a.instance_id(), b.instance_id());
+    }
+
+    #[test]
+    fn test_subscribe_and_unsubscribe() {
+        let invalidator = make_invalidator();
+        let received: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let received_clone = Arc::clone(&received);
+        let id = invalidator.subscribe(move |pattern| {
+            received_clone.lock().unwrap().push(pattern.to_string());
+        });
+
+        for listener in invalidator.listeners.iter() {
+            (listener.value())("reflex:pii:*");
+        }
+        assert_eq!(received.lock().unwrap().as_slice(), ["reflex:pii:*"]);
+
+        invalidator.unsubscribe(id);
+        assert!(invalidator.listeners.is_empty());
+    }
+
+    #[test]
+    fn test_invalidation_message_roundtrips_through_json() {
+        let message = InvalidationMessage {
+            instance_id: "instance-a".to_string(),
+            sequence: 42,
+            pattern: "reflex:injection:*".to_string(),
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        let parsed: InvalidationMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.instance_id, "instance-a");
+        assert_eq!(parsed.sequence, 42);
+        assert_eq!(parsed.pattern, "reflex:injection:*");
+    }
+}
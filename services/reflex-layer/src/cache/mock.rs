@@ -0,0 +1,274 @@
+//! In-process mock `Cache`, gated behind the `mocks` feature
+//!
+//! [`InMemoryCache`](crate::cache::InMemoryCache) already stands in for
+//! Redis as a production fallback, but its `invalidate_pattern` only
+//! understands the literal `prefix:*` shape this codebase's own call sites
+//! use. `MockCache` exists purely so the `cache::redis_cache` test suite
+//! (and server integration tests) can exercise real Redis glob semantics
+//! (`*` and `?`) without a live Redis, the way fred.rs's `mocks` feature
+//! lets its key-command tests run against an in-process transport. It is
+//! not meant to replace `InMemoryCache` as a runtime fallback.
+#![cfg(feature = "mocks")]
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::cache::types::{Cache, CacheError, CacheStats, CacheTTL};
+
+/// `HashMap`-behind-`RwLock` mock of [`Cache`] with real Redis glob
+/// (`*`/`?`) pattern matching, for deterministic tests that would otherwise
+/// need a live Redis
+pub struct MockCache {
+    entries: RwLock<HashMap<String, (String, Option<Instant>)>>,
+    stats: CacheStats,
+}
+
+impl MockCache {
+    /// Create an empty mock cache
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            stats: CacheStats::new(),
+        }
+    }
+
+    /// Number of entries currently stored, including not-yet-expired ones
+    /// that haven't been read since expiring
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Whether the cache holds no entries
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+impl Default for MockCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Translate a Redis-style glob (`*` matches any run of characters, `?`
+/// matches exactly one) into a match against `key`
+fn glob_match(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+
+    // Standard greedy glob matcher with backtracking on `*`: `p`/`k` walk
+    // forward in lockstep, and `star`/`star_k` remember the most recent `*`
+    // so we can rewind `k` and retry when a later literal fails to match.
+    let (mut p, mut k) = (0, 0);
+    let (mut star, mut star_k) = (None, 0);
+
+    while k < key.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == key[k]) {
+            p += 1;
+            k += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_k = k;
+            p += 1;
+        } else if let Some(sp) = star {
+            p = sp + 1;
+            star_k += 1;
+            k = star_k;
+        } else {
+            return false;
+        }
+    }
+
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+
+    p == pattern.len()
+}
+
+#[async_trait]
+impl Cache for MockCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        self.stats.record_key(key);
+
+        let hit = {
+            let entries = self.entries.read().await;
+            match entries.get(key) {
+                Some((value, expires_at)) => {
+                    if expires_at.is_some_and(|at| Instant::now() >= at) {
+                        None
+                    } else {
+                        Some(value.clone())
+                    }
+                }
+                None => None,
+            }
+        };
+
+        if hit.is_none() {
+            // Lazily drop an expired entry so it doesn't linger forever.
+            self.entries.write().await.remove(key);
+            self.stats.record_miss();
+        } else {
+            self.stats.record_hit();
+        }
+
+        Ok(hit)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: CacheTTL) -> Result<(), CacheError> {
+        let expires_at = ttl.as_seconds().map(|secs| Instant::now() + Duration::from_secs(secs));
+        self.entries
+            .write()
+            .await
+            .insert(key.to_string(), (value.to_string(), expires_at));
+        self.stats.record_set();
+        self.stats.record_key(key);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.entries.write().await.remove(key);
+        self.stats.record_delete();
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        Ok(self.get(key).await?.is_some())
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> Result<u64, CacheError> {
+        use crate::cache::key::validate_cache_pattern;
+
+        validate_cache_pattern(pattern)?;
+
+        let mut entries = self.entries.write().await;
+        let matching: Vec<String> = entries
+            .keys()
+            .filter(|key| glob_match(pattern, key))
+            .cloned()
+            .collect();
+
+        for key in &matching {
+            entries.remove(key);
+        }
+
+        Ok(matching.len() as u64)
+    }
+
+    fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_set_round_trip() {
+        let cache = MockCache::new();
+        cache.set("key1", "value1", CacheTTL::Short).await.unwrap();
+        assert_eq!(cache.get("key1").await.unwrap(), Some("value1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_miss() {
+        let cache = MockCache::new();
+        assert_eq!(cache.get("missing").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry() {
+        let cache = MockCache::new();
+        cache.set("key1", "value1", CacheTTL::Custom(0)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(cache.get("key1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_ttl_never_expires() {
+        let cache = MockCache::new();
+        cache.set("key1", "value1", CacheTTL::Persistent).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(cache.get("key1").await.unwrap(), Some("value1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let cache = MockCache::new();
+        cache.set("key1", "value1", CacheTTL::Short).await.unwrap();
+        cache.delete("key1").await.unwrap();
+        assert_eq!(cache.get("key1").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_exists() {
+        let cache = MockCache::new();
+        assert!(!cache.exists("key1").await.unwrap());
+        cache.set("key1", "value1", CacheTTL::Short).await.unwrap();
+        assert!(cache.exists("key1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_pattern_prefix_star() {
+        let cache = MockCache::new();
+        cache.set("reflex:pattern:a", "1", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:pattern:b", "2", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:other:c", "3", CacheTTL::Short).await.unwrap();
+
+        let deleted = cache.invalidate_pattern("reflex:pattern:*").await.unwrap();
+        assert_eq!(deleted, 2);
+        assert!(cache.exists("reflex:other:c").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_pattern_glob_question_mark() {
+        let cache = MockCache::new();
+        cache.set("reflex:item:1", "a", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:item:2", "b", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:item:10", "c", CacheTTL::Short).await.unwrap();
+
+        let deleted = cache.invalidate_pattern("reflex:item:?").await.unwrap();
+        assert_eq!(deleted, 2);
+        assert!(cache.exists("reflex:item:10").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_pattern_glob_mid_string_star() {
+        let cache = MockCache::new();
+        cache.set("reflex:user:42:profile", "a", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:user:42:session", "b", CacheTTL::Short).await.unwrap();
+        cache.set("reflex:user:7:profile", "c", CacheTTL::Short).await.unwrap();
+
+        let deleted = cache
+            .invalidate_pattern("reflex:user:*:profile")
+            .await
+            .unwrap();
+        assert_eq!(deleted, 2);
+        assert!(cache.exists("reflex:user:42:session").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_pattern_rejects_invalid_pattern() {
+        let cache = MockCache::new();
+        assert!(cache.invalidate_pattern("*").await.is_err());
+        assert!(cache.invalidate_pattern("no-namespace").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stats_track_hits_and_misses() {
+        let cache = MockCache::new();
+        cache.set("key1", "value1", CacheTTL::Short).await.unwrap();
+        cache.get("key1").await.unwrap();
+        cache.get("missing").await.unwrap();
+
+        let stats = cache.stats().snapshot();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.sets, 1);
+    }
+}
@@ -3,14 +3,32 @@
 //! Provides Redis-backed caching with TTL management, pattern invalidation,
 //! and comprehensive statistics tracking for performance optimization.
 
+pub mod backend;
+pub mod crypto;
+pub mod hll;
+pub mod in_memory;
+pub mod invalidator;
 pub mod key;
+#[cfg(feature = "mocks")]
+pub mod mock;
 pub mod redis_cache;
+pub mod sharded;
+pub mod two_tier;
 pub mod types;
 
 // Re-export commonly used items
+pub use backend::CacheBackend;
+pub use crypto::{CacheCrypto, CACHE_CRYPTO_KEY_LEN};
+pub use hll::HyperLogLog;
+pub use invalidator::{CacheInvalidator, InvalidationCallback, INVALIDATION_CHANNEL};
+pub use in_memory::InMemoryCache;
 pub use key::{
     generate_cache_key, generate_cache_key_fast, generate_custom_cache_key, validate_cache_pattern,
     DEFAULT_NAMESPACE,
 };
+#[cfg(feature = "mocks")]
+pub use mock::MockCache;
 pub use redis_cache::RedisCache;
+pub use sharded::ShardedLruCache;
+pub use two_tier::TwoTierCache;
 pub use types::{Cache, CacheError, CacheStats, CacheStatsSnapshot, CacheTTL};
@@ -3,12 +3,71 @@
 //! Provides a production-ready Redis cache with connection pooling, retry logic,
 //! TTL management, and pattern-based invalidation.
 
+use crate::cache::backend::CacheBackend;
+use crate::cache::crypto::CacheCrypto;
+use crate::cache::invalidator::CacheInvalidator;
 use crate::cache::types::{Cache, CacheError, CacheStats, CacheTTL};
-use crate::redis_client::RedisClient;
+use crate::redis_client::{retry_redis_command, RedisClient};
 use async_trait::async_trait;
 use redis::AsyncCommands;
 use std::sync::Arc;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
+
+/// Atomic "get-or-lock": returns the cached value if present, otherwise
+/// acquires a short-lived `NX` lock (on a separate `<key>:lock` key, so the
+/// lock sentinel never shows up as a cached value) so exactly one caller
+/// computes the value while others see [`GetOrLock::Locked`] and back off.
+///
+/// KEYS[1] = cache key, KEYS[2] = lock key
+/// ARGV[1] = lock sentinel value, ARGV[2] = lock TTL in milliseconds
+///
+/// Returns `{1, value}` on a cache hit, `{0, false}` if this caller
+/// acquired the lock, or `{2, false}` if another caller already holds it.
+const GET_OR_LOCK_SCRIPT: &str = r#"
+local value = redis.call('GET', KEYS[1])
+if value then
+    return {1, value}
+end
+local acquired = redis.call('SET', KEYS[2], ARGV[1], 'NX', 'PX', ARGV[2])
+if acquired then
+    return {0, false}
+else
+    return {2, false}
+end
+"#;
+
+/// Sliding-TTL refresh: bumps a key's expiry only when its remaining TTL has
+/// dropped below a threshold, so a hot key's expiry keeps sliding forward
+/// without paying an `EXPIRE` round-trip on every access.
+///
+/// KEYS[1] = key, ARGV[1] = refresh threshold in milliseconds, ARGV[2] = new
+/// TTL in milliseconds. Returns `1` if the TTL was refreshed, `0` otherwise
+/// (key missing, persistent, or still above the threshold).
+const REFRESH_TTL_SCRIPT: &str = r#"
+local ttl = redis.call('PTTL', KEYS[1])
+if ttl < 0 then
+    return 0
+end
+if ttl < tonumber(ARGV[1]) then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+return 0
+"#;
+
+/// Outcome of [`RedisCache::get_or_lock`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GetOrLock {
+    /// The value was already cached
+    Hit(String),
+    /// No value was cached and this caller acquired the compute lock; it
+    /// should compute the value, call `set` to publish it, and the lock
+    /// will then either be overtaken by the real key or expire on its own
+    Acquired,
+    /// No value was cached but another caller already holds the compute
+    /// lock; this caller should back off and retry
+    Locked,
+}
 
 /// Redis-backed cache implementation
 pub struct RedisCache {
@@ -16,6 +75,12 @@ pub struct RedisCache {
     redis: Arc<RedisClient>,
     /// Cache statistics tracker
     stats: Arc<CacheStats>,
+    /// Optional at-rest encryption for cache values
+    crypto: Option<CacheCrypto>,
+    /// Optional cross-instance invalidation announcer; when set,
+    /// `invalidate_pattern` publishes the purged pattern so peer instances
+    /// can evict their own in-process copies
+    invalidator: Option<Arc<CacheInvalidator>>,
 }
 
 impl RedisCache {
@@ -31,13 +96,98 @@ impl RedisCache {
         Self {
             redis,
             stats: Arc::new(CacheStats::new()),
+            crypto: None,
+            invalidator: None,
         }
     }
 
+    /// Create a new Redis cache with transparent at-rest value encryption
+    ///
+    /// # Arguments
+    /// * `redis` - Arc to configured RedisClient
+    /// * `crypto` - AEAD encryption for cache values
+    ///
+    /// # Returns
+    /// * `Self` - New RedisCache instance that encrypts values before writing
+    ///   them to Redis and decrypts them transparently on read
+    pub fn with_crypto(redis: Arc<RedisClient>, crypto: CacheCrypto) -> Self {
+        debug!("Creating RedisCache with at-rest encryption enabled");
+        Self {
+            redis,
+            stats: Arc::new(CacheStats::new()),
+            crypto: Some(crypto),
+            invalidator: None,
+        }
+    }
+
+    /// Attach a [`CacheInvalidator`] so `invalidate_pattern` announces
+    /// purges to other instances over Redis Pub/Sub
+    pub fn with_invalidator(mut self, invalidator: Arc<CacheInvalidator>) -> Self {
+        self.invalidator = Some(invalidator);
+        self
+    }
+
     /// Get statistics reference for this cache
     pub fn stats_ref(&self) -> Arc<CacheStats> {
         Arc::clone(&self.stats)
     }
+
+    /// Atomically check `key` for a cached value or claim the right to
+    /// compute it
+    ///
+    /// Collapses the classic GET-compute-SET race (every concurrent miss
+    /// redoing the same upstream work) into a single round trip: the first
+    /// caller to miss gets [`GetOrLock::Acquired`] and should compute the
+    /// value and `set` it; everyone else gets [`GetOrLock::Locked`] and
+    /// should back off and retry.
+    pub async fn get_or_lock(&self, key: &str, lock_ttl_ms: u64) -> Result<GetOrLock, CacheError> {
+        let lock_key = format!("{}:lock", key);
+        let lock_ttl_ms = lock_ttl_ms.to_string();
+        let result: (u8, Option<String>) = self
+            .redis
+            .eval_cached(
+                GET_OR_LOCK_SCRIPT,
+                &[key, &lock_key],
+                &["1", &lock_ttl_ms],
+            )
+            .await?;
+
+        match result {
+            (1, Some(stored)) => match &self.crypto {
+                Some(crypto) => match crypto.decrypt(key, &stored) {
+                    Some(value) => Ok(GetOrLock::Hit(value)),
+                    None => {
+                        warn!("Cache value for {} failed decryption, treating as miss", key);
+                        Ok(GetOrLock::Acquired)
+                    }
+                },
+                None => Ok(GetOrLock::Hit(stored)),
+            },
+            (0, _) => Ok(GetOrLock::Acquired),
+            _ => Ok(GetOrLock::Locked),
+        }
+    }
+
+    /// Bump `key`'s expiry to `new_ttl_secs` only if its remaining TTL has
+    /// dropped below `threshold_secs`
+    ///
+    /// Lets a hot key's expiry keep sliding forward without paying an
+    /// `EXPIRE` round-trip on every access. Returns `true` if the TTL was
+    /// refreshed.
+    pub async fn refresh_ttl_if_low(
+        &self,
+        key: &str,
+        threshold_secs: u64,
+        new_ttl_secs: u64,
+    ) -> Result<bool, CacheError> {
+        let threshold_ms = (threshold_secs * 1000).to_string();
+        let new_ttl_ms = (new_ttl_secs * 1000).to_string();
+        let refreshed: i64 = self
+            .redis
+            .eval_cached(REFRESH_TTL_SCRIPT, &[key], &[&threshold_ms, &new_ttl_ms])
+            .await?;
+        Ok(refreshed == 1)
+    }
 }
 
 #[async_trait]
@@ -45,21 +195,45 @@ impl Cache for RedisCache {
     async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
         debug!("Cache GET: {}", key);
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(key).await?;
 
-        match conn.get::<_, Option<String>>(key).await {
-            Ok(Some(value)) => {
-                self.stats.record_hit();
-                debug!("Cache HIT: {}", key);
-                Ok(Some(value))
-            }
+        self.stats.record_key(key);
+
+        let result = retry_redis_command(self.redis.config(), || async {
+            conn.get::<_, Option<String>>(key)
+                .await
+                .inspect_err(|_| self.stats.record_error())
+        })
+        .await;
+
+        match result {
+            Ok(Some(stored)) => match &self.crypto {
+                Some(crypto) => match crypto.decrypt(key, &stored) {
+                    Some(value) => {
+                        self.stats.record_hit();
+                        debug!("Cache HIT: {}", key);
+                        Ok(Some(value))
+                    }
+                    None => {
+                        // Authentication failure (e.g. stale key after rotation)
+                        // degrades to a cache miss rather than a hard error.
+                        self.stats.record_miss();
+                        warn!("Cache value for {} failed decryption, treating as miss", key);
+                        Ok(None)
+                    }
+                },
+                None => {
+                    self.stats.record_hit();
+                    debug!("Cache HIT: {}", key);
+                    Ok(Some(stored))
+                }
+            },
             Ok(None) => {
                 self.stats.record_miss();
                 debug!("Cache MISS: {}", key);
                 Ok(None)
             }
             Err(e) => {
-                self.stats.record_error();
                 error!("Cache GET error for key {}: {}", key, e);
                 Err(CacheError::Redis(e))
             }
@@ -69,27 +243,32 @@ impl Cache for RedisCache {
     async fn set(&self, key: &str, value: &str, ttl: CacheTTL) -> Result<(), CacheError> {
         debug!("Cache SET: {} (TTL: {:?})", key, ttl);
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(key).await?;
 
-        let result = match ttl.as_seconds() {
-            Some(seconds) => {
+        let stored = match &self.crypto {
+            Some(crypto) => crypto.encrypt(key, value)?,
+            None => value.to_string(),
+        };
+
+        let result = retry_redis_command(self.redis.config(), || async {
+            match ttl.as_seconds() {
                 // Set with expiration using SETEX
-                conn.set_ex::<_, _, ()>(key, value, seconds as u64).await
-            }
-            None => {
+                Some(seconds) => conn.set_ex::<_, _, ()>(key, &stored, seconds as u64).await,
                 // Set without expiration
-                conn.set::<_, _, ()>(key, value).await
+                None => conn.set::<_, _, ()>(key, &stored).await,
             }
-        };
+            .inspect_err(|_| self.stats.record_error())
+        })
+        .await;
 
         match result {
             Ok(_) => {
                 self.stats.record_set();
+                self.stats.record_key(key);
                 debug!("Cache SET successful: {}", key);
                 Ok(())
             }
             Err(e) => {
-                self.stats.record_error();
                 error!("Cache SET error for key {}: {}", key, e);
                 Err(CacheError::Redis(e))
             }
@@ -99,16 +278,22 @@ impl Cache for RedisCache {
     async fn delete(&self, key: &str) -> Result<(), CacheError> {
         debug!("Cache DELETE: {}", key);
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(key).await?;
+
+        let result = retry_redis_command(self.redis.config(), || async {
+            conn.del::<_, ()>(key)
+                .await
+                .inspect_err(|_| self.stats.record_error())
+        })
+        .await;
 
-        match conn.del::<_, ()>(key).await {
+        match result {
             Ok(_) => {
                 self.stats.record_delete();
                 debug!("Cache DELETE successful: {}", key);
                 Ok(())
             }
             Err(e) => {
-                self.stats.record_error();
                 error!("Cache DELETE error for key {}: {}", key, e);
                 Err(CacheError::Redis(e))
             }
@@ -118,15 +303,21 @@ impl Cache for RedisCache {
     async fn exists(&self, key: &str) -> Result<bool, CacheError> {
         debug!("Cache EXISTS: {}", key);
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(key).await?;
 
-        match conn.exists::<_, bool>(key).await {
+        let result = retry_redis_command(self.redis.config(), || async {
+            conn.exists::<_, bool>(key)
+                .await
+                .inspect_err(|_| self.stats.record_error())
+        })
+        .await;
+
+        match result {
             Ok(exists) => {
                 debug!("Cache EXISTS result for {}: {}", key, exists);
                 Ok(exists)
             }
             Err(e) => {
-                self.stats.record_error();
                 error!("Cache EXISTS error for key {}: {}", key, e);
                 Err(CacheError::Redis(e))
             }
@@ -141,48 +332,148 @@ impl Cache for RedisCache {
         // Validate pattern for safety
         validate_cache_pattern(pattern)?;
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(pattern).await?;
+        let scan_count = self.redis.config().scan_count;
+
+        let mut cursor: u64 = 0;
+        let mut deleted: u64 = 0;
+
+        loop {
+            let scan_result = retry_redis_command(self.redis.config(), || async {
+                redis::cmd("SCAN")
+                    .cursor_arg(cursor)
+                    .arg("MATCH")
+                    .arg(pattern)
+                    .arg("COUNT")
+                    .arg(scan_count)
+                    .query_async::<_, (u64, Vec<String>)>(&mut conn)
+                    .await
+                    .inspect_err(|_| self.stats.record_error())
+            })
+            .await;
+
+            let (next_cursor, keys) = match scan_result {
+                Ok(page) => page,
+                Err(e) => {
+                    error!("Cache pattern scan error: {}", e);
+                    return Err(CacheError::Redis(e));
+                }
+            };
+
+            if !keys.is_empty() {
+                let requested = keys.len() as u64;
+                let del_result = retry_redis_command(self.redis.config(), || async {
+                    conn.del::<_, u64>(&keys)
+                        .await
+                        .inspect_err(|_| self.stats.record_error())
+                })
+                .await;
+
+                match del_result {
+                    // A key that expired between SCAN and DEL just means DEL
+                    // removed fewer than we asked for; only count the ones
+                    // actually gone.
+                    Ok(removed) => {
+                        for _ in 0..removed {
+                            self.stats.record_delete();
+                        }
+                        deleted += removed;
+                        debug!(
+                            "Invalidated {}/{} scanned keys matching pattern: {}",
+                            removed, requested, pattern
+                        );
+                    }
+                    Err(e) => {
+                        error!("Cache bulk DELETE error: {}", e);
+                        return Err(CacheError::Redis(e));
+                    }
+                }
+            }
 
-        // Use KEYS for simplicity (note: blocks Redis, consider SCAN for production at scale)
-        let keys: Vec<String> = match conn.keys(pattern).await {
-            Ok(keys) => keys,
-            Err(e) => {
-                self.stats.record_error();
-                error!("Cache pattern lookup error: {}", e);
-                return Err(CacheError::Redis(e));
+            if next_cursor == 0 {
+                break;
             }
-        };
+            cursor = next_cursor;
+        }
 
-        if keys.is_empty() {
-            debug!("No keys found matching pattern: {}", pattern);
-            return Ok(0);
+        debug!(
+            "Successfully invalidated {} keys matching pattern: {}",
+            deleted, pattern
+        );
+
+        if deleted > 0 {
+            if let Some(invalidator) = &self.invalidator {
+                if let Err(e) = invalidator.publish(pattern).await {
+                    warn!(
+                        "Failed to announce invalidation for pattern {}: {}",
+                        pattern, e
+                    );
+                }
+            }
         }
 
-        let count = keys.len() as u64;
-        debug!("Found {} keys matching pattern: {}", count, pattern);
+        Ok(deleted)
+    }
 
-        // Delete all matching keys
-        match conn.del::<_, ()>(&keys).await {
-            Ok(_) => {
-                for _ in 0..count {
-                    self.stats.record_delete();
-                }
-                debug!(
-                    "Successfully invalidated {} keys matching pattern: {}",
-                    count, pattern
-                );
-                Ok(count)
+    fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+}
+
+/// Raw get/set/incr/expire primitives, implemented directly against Redis
+///
+/// `get`/`set`/`delete` reuse the [`Cache`] implementation above (so they
+/// still go through at-rest encryption when configured); `incr`/`expire`
+/// are new thin wrappers around `INCRBY`/`EXPIRE` for counters (e.g. rate
+/// limit buckets) that don't go through `Cache`'s crypto/TTL-enum layer.
+#[async_trait]
+impl CacheBackend for RedisCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        Cache::get(self, key).await
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl_secs: Option<u64>) -> Result<(), CacheError> {
+        let ttl = match ttl_secs {
+            Some(secs) => CacheTTL::Custom(secs),
+            None => CacheTTL::Persistent,
+        };
+        Cache::set(self, key, value, ttl).await
+    }
+
+    async fn incr(&self, key: &str, delta: i64) -> Result<i64, CacheError> {
+        debug!("Cache INCR: {} by {}", key, delta);
+
+        let mut conn = self.redis.get_connection(key).await?;
+
+        match conn.incr::<_, _, i64>(key, delta).await {
+            Ok(new_value) => {
+                self.stats.record_set();
+                Ok(new_value)
             }
             Err(e) => {
                 self.stats.record_error();
-                error!("Cache bulk DELETE error: {}", e);
+                error!("Cache INCR error for key {}: {}", key, e);
                 Err(CacheError::Redis(e))
             }
         }
     }
 
-    fn stats(&self) -> &CacheStats {
-        &self.stats
+    async fn expire(&self, key: &str, ttl_secs: u64) -> Result<bool, CacheError> {
+        debug!("Cache EXPIRE: {} in {}s", key, ttl_secs);
+
+        let mut conn = self.redis.get_connection(key).await?;
+
+        conn.expire::<_, bool>(key, ttl_secs as i64)
+            .await
+            .map_err(|e| {
+                self.stats.record_error();
+                error!("Cache EXPIRE error for key {}: {}", key, e);
+                CacheError::Redis(e)
+            })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        Cache::delete(self, key).await
     }
 }
 
@@ -199,6 +490,8 @@ mod tests {
             connection_timeout_ms: 5000,
             command_timeout_ms: 3000,
             cache_ttl_secs: 300,
+            scan_count: 500,
+            ..Default::default()
         }
     }
 
@@ -435,4 +728,71 @@ mod tests {
             cache.delete(key).await.unwrap();
         }
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_cache_backend_incr_and_expire() {
+        let cache = setup_cache().await.unwrap();
+        let key = generate_cache_key("test", "backend_incr").unwrap();
+
+        assert_eq!(CacheBackend::incr(&cache, &key, 5).await.unwrap(), 5);
+        assert_eq!(CacheBackend::incr(&cache, &key, 3).await.unwrap(), 8);
+        assert!(CacheBackend::expire(&cache, &key, 60).await.unwrap());
+
+        // Cleanup
+        CacheBackend::delete(&cache, &key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_cache_backend_expire_missing_key_returns_false() {
+        let cache = setup_cache().await.unwrap();
+        let key = generate_cache_key("test", "backend_expire_missing").unwrap();
+
+        assert!(!CacheBackend::expire(&cache, &key, 60).await.unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis to be running
+    async fn test_get_or_lock_acquires_then_reports_hit_after_set() {
+        let cache = setup_cache().await.unwrap();
+        let key = generate_cache_key("test", "get_or_lock").unwrap();
+
+        assert_eq!(cache.get_or_lock(&key, 1000).await.unwrap(), GetOrLock::Acquired);
+
+        cache.set(&key, "computed", CacheTTL::Short).await.unwrap();
+        assert_eq!(
+            cache.get_or_lock(&key, 1000).await.unwrap(),
+            GetOrLock::Hit("computed".to_string())
+        );
+
+        cache.delete(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_or_lock_reports_locked_while_lock_is_held() {
+        let cache = setup_cache().await.unwrap();
+        let key = generate_cache_key("test", "get_or_lock_contended").unwrap();
+
+        assert_eq!(cache.get_or_lock(&key, 5000).await.unwrap(), GetOrLock::Acquired);
+        assert_eq!(cache.get_or_lock(&key, 5000).await.unwrap(), GetOrLock::Locked);
+
+        cache.delete(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_refresh_ttl_if_low_refreshes_only_below_threshold() {
+        let cache = setup_cache().await.unwrap();
+        let key = generate_cache_key("test", "refresh_ttl").unwrap();
+
+        cache.set(&key, "value", CacheTTL::Custom(3600)).await.unwrap();
+        assert!(!cache.refresh_ttl_if_low(&key, 60, 3600).await.unwrap());
+
+        cache.set(&key, "value", CacheTTL::Custom(30)).await.unwrap();
+        assert!(cache.refresh_ttl_if_low(&key, 60, 3600).await.unwrap());
+
+        cache.delete(&key).await.unwrap();
+    }
 }
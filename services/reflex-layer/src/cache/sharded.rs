@@ -0,0 +1,314 @@
+//! Sharded in-memory LRU cache (L1)
+//!
+//! Independent `N`-way sharded LRU sitting in front of `RedisCache` (L2) so
+//! hot keys are served without a network round-trip. Each shard owns its
+//! own `Mutex<LruCache>` and `CacheStats`, so concurrent gets/sets on
+//! different shards never contend on the same lock, and a snapshot
+//! (`save`) only ever holds one shard's lock at a time rather than
+//! stopping the whole cache. Design follows Pingora's sharded eviction
+//! manager.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::types::{CacheError, CacheStats, CacheStatsSnapshot};
+
+/// One independently-locked LRU shard
+struct CacheShard {
+    lru: Mutex<LruCache<String, String>>,
+    stats: CacheStats,
+}
+
+impl CacheShard {
+    fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity.max(1)).unwrap();
+        Self {
+            lru: Mutex::new(LruCache::new(capacity)),
+            stats: CacheStats::new(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        let mut lru = self.lru.lock().unwrap();
+        match lru.get(key) {
+            Some(value) => {
+                self.stats.record_hit();
+                Some(value.clone())
+            }
+            None => {
+                self.stats.record_miss();
+                None
+            }
+        }
+    }
+
+    fn put(&self, key: String, value: String) {
+        let mut lru = self.lru.lock().unwrap();
+        self.stats.record_key(&key);
+        lru.put(key, value);
+        self.stats.record_set();
+    }
+
+    fn remove(&self, key: &str) {
+        let mut lru = self.lru.lock().unwrap();
+        if lru.pop(key).is_some() {
+            self.stats.record_delete();
+        }
+    }
+
+    fn clear(&self) {
+        let mut lru = self.lru.lock().unwrap();
+        lru.clear();
+    }
+
+    /// Entries ordered most-recently-used first
+    fn snapshot_entries(&self) -> Vec<(String, String)> {
+        let lru = self.lru.lock().unwrap();
+        lru.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+/// One shard's worth of entries, as persisted by [`ShardedLruCache::save`]
+#[derive(Debug, Serialize, Deserialize)]
+struct ShardSnapshot {
+    shard: usize,
+    /// Entries ordered most-recently-used first
+    entries: Vec<(String, String)>,
+}
+
+/// `N`-way sharded in-memory LRU cache
+pub struct ShardedLruCache {
+    shards: Vec<CacheShard>,
+}
+
+impl ShardedLruCache {
+    /// Create a new sharded cache with `shard_count` independent LRUs of
+    /// `shard_capacity` entries each
+    pub fn new(shard_count: usize, shard_capacity: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        let shards = (0..shard_count)
+            .map(|_| CacheShard::new(shard_capacity))
+            .collect();
+        Self { shards }
+    }
+
+    /// Number of shards in this cache
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard_index(&self, key: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) % self.shards.len()
+    }
+
+    /// Look up `key`, promoting it to most-recently-used on hit
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.shards[self.shard_index(key)].get(key)
+    }
+
+    /// Insert or update `key`, evicting the shard's least-recently-used
+    /// entry if it's at capacity
+    pub fn put(&self, key: &str, value: &str) {
+        let idx = self.shard_index(key);
+        self.shards[idx].put(key.to_string(), value.to_string());
+    }
+
+    /// Remove `key` if present
+    pub fn remove(&self, key: &str) {
+        self.shards[self.shard_index(key)].remove(key);
+    }
+
+    /// Evict every entry from every shard
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            shard.clear();
+        }
+    }
+
+    /// Hit/miss/set/delete counters for each shard, in shard order
+    pub fn shard_stats(&self) -> Vec<CacheStatsSnapshot> {
+        self.shards.iter().map(|s| s.stats.snapshot()).collect()
+    }
+
+    /// Estimated number of distinct keys across all shards combined
+    ///
+    /// Merges each shard's HyperLogLog into a scratch estimator rather
+    /// than summing per-shard estimates, since the same key can land in
+    /// only one shard but a naive sum would still double-count nothing
+    /// while understating nothing either -- merging keeps the estimate
+    /// correct regardless of shard count.
+    pub fn estimated_unique_keys(&self) -> u64 {
+        let global = CacheStats::new();
+        for shard in &self.shards {
+            global.merge_cardinality_from(&shard.stats);
+        }
+        global.estimated_unique_keys()
+    }
+
+    /// Serialize each shard independently to `path` for warm restarts
+    ///
+    /// Shards are written one at a time (as one JSON line each), so a
+    /// snapshot only ever holds a single shard's lock, never the whole
+    /// cache.
+    pub fn save(&self, path: &str) -> Result<(), CacheError> {
+        use std::io::Write;
+
+        let mut file = std::fs::File::create(path).map_err(|e| {
+            CacheError::Serialization(format!("Failed to create L1 snapshot file: {}", e))
+        })?;
+
+        for (idx, shard) in self.shards.iter().enumerate() {
+            let snapshot = ShardSnapshot {
+                shard: idx,
+                entries: shard.snapshot_entries(),
+            };
+            let line = serde_json::to_string(&snapshot)
+                .map_err(|e| CacheError::Serialization(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| {
+                CacheError::Serialization(format!("Failed to write L1 snapshot: {}", e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct a cache from a snapshot written by [`save`](Self::save)
+    ///
+    /// Entries for shards beyond `shard_count` are dropped, so the cache
+    /// can be warmed into a differently-sized shard layout than the one it
+    /// was saved from.
+    pub fn load(path: &str, shard_count: usize, shard_capacity: usize) -> Result<Self, CacheError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            CacheError::Serialization(format!("Failed to read L1 snapshot file: {}", e))
+        })?;
+
+        let cache = Self::new(shard_count, shard_capacity);
+        for line in contents.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let snapshot: ShardSnapshot =
+                serde_json::from_str(line).map_err(|e| CacheError::Serialization(e.to_string()))?;
+            if snapshot.shard >= cache.shards.len() {
+                continue;
+            }
+            // Entries are stored most-recent-first; re-insert oldest-first so
+            // the most-recently-used key ends up inserted (and thus most
+            // recent) last.
+            for (key, value) in snapshot.entries.into_iter().rev() {
+                cache.shards[snapshot.shard].put(key, value);
+            }
+        }
+
+        Ok(cache)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_put_round_trip() {
+        let cache = ShardedLruCache::new(4, 10);
+        cache.put("key1", "value1");
+        assert_eq!(cache.get("key1"), Some("value1".to_string()));
+    }
+
+    #[test]
+    fn test_get_miss_returns_none() {
+        let cache = ShardedLruCache::new(4, 10);
+        assert_eq!(cache.get("missing"), None);
+    }
+
+    #[test]
+    fn test_remove() {
+        let cache = ShardedLruCache::new(4, 10);
+        cache.put("key1", "value1");
+        cache.remove("key1");
+        assert_eq!(cache.get("key1"), None);
+    }
+
+    #[test]
+    fn test_clear_empties_all_shards() {
+        let cache = ShardedLruCache::new(4, 10);
+        for i in 0..10 {
+            cache.put(&format!("key-{i}"), "value");
+        }
+        cache.clear();
+
+        for i in 0..10 {
+            assert_eq!(cache.get(&format!("key-{i}")), None);
+        }
+    }
+
+    #[test]
+    fn test_shard_capacity_evicts_least_recently_used() {
+        let cache = ShardedLruCache::new(1, 2);
+        cache.put("a", "1");
+        cache.put("b", "2");
+        cache.put("c", "3"); // evicts "a", the least-recently-used
+
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some("2".to_string()));
+        assert_eq!(cache.get("c"), Some("3".to_string()));
+    }
+
+    #[test]
+    fn test_shard_stats_tracks_hits_and_misses() {
+        let cache = ShardedLruCache::new(1, 10);
+        cache.put("key1", "value1");
+        cache.get("key1"); // hit
+        cache.get("missing"); // miss
+
+        let stats = cache.shard_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].hits, 1);
+        assert_eq!(stats[0].misses, 1);
+        assert_eq!(stats[0].sets, 1);
+    }
+
+    #[test]
+    fn test_estimated_unique_keys_merges_across_shards() {
+        let cache = ShardedLruCache::new(4, 100);
+        for i in 0..40 {
+            cache.put(&format!("key-{i}"), "value");
+        }
+
+        assert_eq!(cache.estimated_unique_keys(), 40);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "reflex_l1_cache_test_{}.jsonl",
+            std::process::id()
+        ));
+        let path = path.to_str().unwrap();
+
+        let cache = ShardedLruCache::new(2, 10);
+        cache.put("key1", "value1");
+        cache.put("key2", "value2");
+        cache.save(path).unwrap();
+
+        let restored = ShardedLruCache::load(path, 2, 10).unwrap();
+        assert_eq!(restored.get("key1"), Some("value1".to_string()));
+        assert_eq!(restored.get("key2"), Some("value2".to_string()));
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_load_missing_file_errors() {
+        let result = ShardedLruCache::load("/nonexistent/path/to/snapshot.jsonl", 2, 10);
+        assert!(result.is_err());
+    }
+}
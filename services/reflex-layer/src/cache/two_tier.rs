@@ -0,0 +1,175 @@
+//! Two-tier cache: sharded in-memory LRU (L1) in front of Redis (L2)
+//!
+//! Lookups check L1 first and fall through to Redis on miss, populating L1
+//! on the way back so repeatedly-seen inputs stop paying a network
+//! round-trip. Writes go to both tiers so L1 and Redis never disagree for
+//! keys this process has written itself.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::cache::invalidator::CacheInvalidator;
+use crate::cache::redis_cache::RedisCache;
+use crate::cache::sharded::ShardedLruCache;
+use crate::cache::types::{Cache, CacheError, CacheStats, CacheStatsSnapshot, CacheTTL};
+
+/// Two-tier cache combining an in-process sharded LRU (L1) with `RedisCache` (L2)
+pub struct TwoTierCache {
+    l1: Arc<ShardedLruCache>,
+    l2: RedisCache,
+}
+
+impl TwoTierCache {
+    /// Create a new two-tier cache in front of `l2`, with `shard_count`
+    /// independent L1 LRU shards of `shard_capacity` entries each
+    pub fn new(l2: RedisCache, shard_count: usize, shard_capacity: usize) -> Self {
+        Self {
+            l1: Arc::new(ShardedLruCache::new(shard_count, shard_capacity)),
+            l2,
+        }
+    }
+
+    /// Subscribe this instance's L1 to a [`CacheInvalidator`], clearing L1
+    /// whenever a peer announces an invalidation
+    ///
+    /// L1 has no pattern index, so a peer's invalidation clears this
+    /// instance's entire L1 rather than only the matching keys -- coarser
+    /// than the per-key precision `RedisCache::invalidate_pattern` gets
+    /// against L2, but it keeps L1 from continuing to serve data a peer has
+    /// already purged.
+    pub fn with_invalidator(self, invalidator: &CacheInvalidator) -> Self {
+        let l1 = Arc::clone(&self.l1);
+        invalidator.subscribe(move |_pattern| l1.clear());
+        self
+    }
+
+    /// Per-shard L1 hit/miss/set/delete counters, for the `/metrics` endpoint
+    pub fn l1_shard_stats(&self) -> Vec<CacheStatsSnapshot> {
+        self.l1.shard_stats()
+    }
+
+    /// Serialize the L1 cache to `path` for a warm restart
+    pub fn save_l1_snapshot(&self, path: &str) -> Result<(), CacheError> {
+        self.l1.save(path)
+    }
+
+    /// Populate the L1 cache from a snapshot previously written by
+    /// [`save_l1_snapshot`](Self::save_l1_snapshot)
+    pub fn load_l1_snapshot(&mut self, path: &str, shard_capacity: usize) -> Result<(), CacheError> {
+        self.l1 = Arc::new(ShardedLruCache::load(
+            path,
+            self.l1.shard_count(),
+            shard_capacity,
+        )?);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Cache for TwoTierCache {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        if let Some(value) = self.l1.get(key) {
+            return Ok(Some(value));
+        }
+
+        match self.l2.get(key).await? {
+            Some(value) => {
+                self.l1.put(key, &value);
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: CacheTTL) -> Result<(), CacheError> {
+        self.l2.set(key, value, ttl).await?;
+        self.l1.put(key, value);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), CacheError> {
+        self.l2.delete(key).await?;
+        self.l1.remove(key);
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        if self.l1.get(key).is_some() {
+            return Ok(true);
+        }
+        self.l2.exists(key).await
+    }
+
+    async fn invalidate_pattern(&self, pattern: &str) -> Result<u64, CacheError> {
+        // L1 has no pattern index; Redis stays the source of truth and any
+        // stale L1 entries age out naturally via LRU eviction.
+        self.l2.invalidate_pattern(pattern).await
+    }
+
+    fn stats(&self) -> &CacheStats {
+        self.l2.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedisConfig;
+    use crate::redis_client::RedisClient;
+    use std::sync::Arc;
+
+    fn test_redis_config() -> RedisConfig {
+        RedisConfig {
+            url: "redis://localhost:6379".to_string(),
+            pool_size: 10,
+            connection_timeout_ms: 5000,
+            command_timeout_ms: 3000,
+            cache_ttl_secs: 300,
+            ..Default::default()
+        }
+    }
+
+    fn test_two_tier_cache() -> TwoTierCache {
+        let redis = RedisClient::new(test_redis_config()).unwrap();
+        let l2 = RedisCache::new(Arc::new(redis));
+        TwoTierCache::new(l2, 4, 10)
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis to be running
+    async fn test_get_populates_l1_on_l2_hit() {
+        let cache = test_two_tier_cache();
+        let key = "two_tier_test:populate";
+
+        cache.l2.set(key, "value", CacheTTL::Short).await.unwrap();
+        assert_eq!(cache.get(key).await.unwrap(), Some("value".to_string()));
+
+        // Second get should be served from L1 without touching Redis
+        cache.l2.delete(key).await.unwrap();
+        assert_eq!(cache.get(key).await.unwrap(), Some("value".to_string()));
+    }
+
+    #[test]
+    fn test_l1_shard_stats_reflects_shard_count() {
+        let cache = test_two_tier_cache();
+        assert_eq!(cache.l1_shard_stats().len(), 4);
+    }
+
+    #[test]
+    fn test_with_invalidator_clears_l1_on_peer_invalidation() {
+        let redis = RedisClient::new(test_redis_config()).unwrap();
+        let invalidator = CacheInvalidator::new(Arc::new(redis));
+
+        let cache = test_two_tier_cache().with_invalidator(&invalidator);
+        cache.l1.put("key1", "value1");
+        assert_eq!(cache.l1.get("key1"), Some("value1".to_string()));
+
+        // Simulate a peer-published invalidation without a live Redis
+        // connection by invoking the registered callbacks directly.
+        for listener in invalidator.listeners.iter() {
+            (listener.value())("reflex:pii:*");
+        }
+        assert_eq!(cache.l1.get("key1"), None);
+    }
+}
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 use thiserror::Error;
 
+use crate::cache::hll::HyperLogLog;
+
 /// Time-to-live (TTL) configuration for cache entries
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum CacheTTL {
@@ -79,6 +81,10 @@ pub enum CacheError {
     #[error("Invalid pattern: {0}")]
     InvalidPattern(String),
 
+    /// At-rest encryption/decryption error
+    #[error("Cache crypto error: {0}")]
+    Crypto(String),
+
     /// Reflex error (for compatibility with main error type)
     #[error("Reflex error: {0}")]
     Reflex(String),
@@ -107,6 +113,10 @@ pub struct CacheStats {
     pub deletes: AtomicU64,
     /// Total number of errors
     pub errors: AtomicU64,
+    /// Fixed-memory estimator for the number of distinct keys seen
+    pub unique_keys: HyperLogLog,
+    /// Fixed-memory estimator for the number of distinct requesters seen
+    pub unique_requesters: HyperLogLog,
 }
 
 impl CacheStats {
@@ -174,6 +184,35 @@ impl CacheStats {
         self.errors.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record an occurrence of `key`, feeding the unique-key cardinality
+    /// estimate
+    pub fn record_key(&self, key: &str) {
+        self.unique_keys.record(key);
+    }
+
+    /// Record an occurrence of `requester` (e.g. a user ID or client IP),
+    /// feeding the unique-requester cardinality estimate
+    pub fn record_requester(&self, requester: &str) {
+        self.unique_requesters.record(requester);
+    }
+
+    /// Estimated number of distinct cache keys seen
+    pub fn estimated_unique_keys(&self) -> u64 {
+        self.unique_keys.estimate()
+    }
+
+    /// Estimated number of distinct requesters seen
+    pub fn estimated_unique_requesters(&self) -> u64 {
+        self.unique_requesters.estimate()
+    }
+
+    /// Fold another `CacheStats`'s cardinality estimators into this one,
+    /// e.g. to combine per-shard estimates into a global one
+    pub fn merge_cardinality_from(&self, other: &CacheStats) {
+        self.unique_keys.merge(&other.unique_keys);
+        self.unique_requesters.merge(&other.unique_requesters);
+    }
+
     /// Reset all statistics
     pub fn reset(&self) {
         self.hits.store(0, Ordering::Relaxed);
@@ -181,6 +220,8 @@ impl CacheStats {
         self.sets.store(0, Ordering::Relaxed);
         self.deletes.store(0, Ordering::Relaxed);
         self.errors.store(0, Ordering::Relaxed);
+        self.unique_keys.reset();
+        self.unique_requesters.reset();
     }
 
     /// Get snapshot of current statistics
@@ -193,6 +234,8 @@ impl CacheStats {
             errors: self.errors.load(Ordering::Relaxed),
             hit_rate: self.hit_rate(),
             miss_rate: self.miss_rate(),
+            estimated_unique_keys: self.estimated_unique_keys(),
+            estimated_unique_requesters: self.estimated_unique_requesters(),
         }
     }
 }
@@ -207,6 +250,8 @@ pub struct CacheStatsSnapshot {
     pub errors: u64,
     pub hit_rate: f64,
     pub miss_rate: f64,
+    pub estimated_unique_keys: u64,
+    pub estimated_unique_requesters: u64,
 }
 
 /// Core cache trait defining all cache operations
@@ -371,4 +416,67 @@ mod tests {
         assert_eq!(snapshot.misses, 1);
         assert_eq!(snapshot.hit_rate, 2.0 / 3.0);
     }
+
+    #[test]
+    fn test_cache_stats_tracks_unique_key_cardinality() {
+        let stats = CacheStats::new();
+        for i in 0..50 {
+            stats.record_key(&format!("key-{i}"));
+        }
+        stats.record_key("key-0"); // repeat, should not inflate the estimate
+
+        assert_eq!(stats.estimated_unique_keys(), 50);
+    }
+
+    #[test]
+    fn test_cache_stats_tracks_unique_requester_cardinality() {
+        let stats = CacheStats::new();
+        stats.record_requester("user-a");
+        stats.record_requester("user-b");
+        stats.record_requester("user-a");
+
+        assert_eq!(stats.estimated_unique_requesters(), 2);
+    }
+
+    #[test]
+    fn test_cache_stats_reset_clears_cardinality_estimates() {
+        let stats = CacheStats::new();
+        stats.record_key("some-key");
+        stats.record_requester("some-user");
+
+        stats.reset();
+
+        assert_eq!(stats.estimated_unique_keys(), 0);
+        assert_eq!(stats.estimated_unique_requesters(), 0);
+    }
+
+    #[test]
+    fn test_cache_stats_snapshot_includes_cardinality_estimates() {
+        let stats = CacheStats::new();
+        stats.record_key("a");
+        stats.record_key("b");
+        stats.record_requester("user-a");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.estimated_unique_keys, 2);
+        assert_eq!(snapshot.estimated_unique_requesters, 1);
+    }
+
+    #[test]
+    fn test_cache_stats_merge_cardinality_from_combines_estimates() {
+        let shard_a = CacheStats::new();
+        let shard_b = CacheStats::new();
+        for i in 0..25 {
+            shard_a.record_key(&format!("a-{i}"));
+        }
+        for i in 0..25 {
+            shard_b.record_key(&format!("b-{i}"));
+        }
+
+        let global = CacheStats::new();
+        global.merge_cardinality_from(&shard_a);
+        global.merge_cardinality_from(&shard_b);
+
+        assert_eq!(global.estimated_unique_keys(), 50);
+    }
 }
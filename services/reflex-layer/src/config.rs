@@ -3,9 +3,17 @@
 //! Loads configuration from environment variables with sensible defaults.
 //! Supports environment-based overrides for development, staging, and production.
 
+use rand::Rng;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::cache::{CacheCrypto, CACHE_CRYPTO_KEY_LEN};
+use crate::injection::{DetectionMode, Severity};
+use crate::pii::{PatternSet, PolicyError, RedactionPolicy, RedactionStrategy};
+use crate::ratelimit::{ApiKeyTierTable, RateLimitError, RateLimitTier, TierConfigTable};
+use crate::redis_client::RedisDeploymentMode;
+
 /// Main configuration structure for the Reflex Layer
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -15,6 +23,16 @@ pub struct Config {
     /// Redis configuration
     pub redis: RedisConfig,
 
+    /// Per-usecase Redis pool overrides, keyed by usecase name (e.g.
+    /// `"pii"`, `"injection"`, `"reflex"`, `"misc"`). A usecase absent here,
+    /// or missing individual fields, falls back to `redis` (default: empty
+    /// map — every usecase shares the default pool)
+    #[serde(default)]
+    pub redis_usecases: HashMap<String, RedisUsecaseConfig>,
+
+    /// L1 in-memory cache configuration (sharded LRU in front of Redis)
+    pub l1_cache: L1CacheConfig,
+
     /// Security configuration (PII detection, injection detection)
     pub security: SecurityConfig,
 
@@ -41,11 +59,17 @@ pub struct ServerConfig {
 
     /// Request timeout in seconds (default: 30)
     pub request_timeout_secs: u64,
+
+    /// Error response envelope shape: `octo` (flat `ErrorResponse`) or
+    /// `openai` (`{ "error": { message, type, code, param } }`, for clients
+    /// built against the OpenAI API shape) (default: octo)
+    pub error_format: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct RedisConfig {
-    /// Redis connection URL
+    /// Redis connection URL. In `Cluster`/`Sentinel` mode this is used only
+    /// as a fallback when the corresponding node list is empty.
     pub url: String,
 
     /// Connection pool size (default: 10)
@@ -59,9 +83,173 @@ pub struct RedisConfig {
 
     /// Default cache TTL in seconds (default: 3600 = 1 hour)
     pub cache_ttl_secs: u64,
+
+    /// Deployment topology: `standalone` (default), `cluster`, or `sentinel`.
+    /// See [`RedisConfig::deployment_mode`].
+    pub mode: String,
+
+    /// Seed node URLs for `Cluster` mode, comma-separated (ignored in other
+    /// modes)
+    pub cluster_nodes: String,
+
+    /// Master name to resolve via `SENTINEL get-master-addr-by-name`
+    /// (`Sentinel` mode only)
+    pub sentinel_master_name: String,
+
+    /// Sentinel node URLs, comma-separated (`Sentinel` mode only)
+    pub sentinel_nodes: String,
+
+    /// Path to a PEM-encoded CA certificate used to verify the server's TLS
+    /// certificate for `rediss://` URLs. Empty disables custom CA
+    /// verification (the platform trust store is used instead).
+    pub tls_ca_cert_path: String,
+
+    /// Path to a PEM-encoded client certificate for mutual TLS. Requires
+    /// `tls_client_key_path` to also be set (default: "")
+    pub tls_client_cert_path: String,
+
+    /// Path to the PEM-encoded private key matching `tls_client_cert_path`
+    /// (default: "")
+    pub tls_client_key_path: String,
+
+    /// Skip TLS certificate verification for `rediss://` URLs. Only ever
+    /// meant for local/staging endpoints with self-signed certs (default:
+    /// false)
+    pub tls_insecure_skip_verify: bool,
+
+    /// Username for Redis ACL auth (Redis 6+/Valkey), sent via `AUTH` on
+    /// every connection the pool (re)establishes. Empty disables ACL auth
+    /// (default: "")
+    pub acl_username: String,
+
+    /// Password for Redis ACL auth, paired with `acl_username` (or used
+    /// alone for legacy `requirepass`-style auth). Empty disables password
+    /// auth (default: "")
+    pub acl_password: String,
+
+    /// Maximum connection attempts before giving up with a pool-exhausted
+    /// error (default: 3)
+    pub max_retries: u32,
+
+    /// Starting delay before the first retry, in milliseconds; also the
+    /// floor for each subsequent decorrelated-jitter delay (default: 100)
+    pub base_backoff_ms: u64,
+
+    /// Upper bound on any single retry delay, in milliseconds (default: 5000)
+    pub max_backoff_ms: u64,
+
+    /// Retry a command that fails with a transient timeout/connection-reset
+    /// error up to `max_retries` times, rather than surfacing it to the
+    /// caller immediately (default: true)
+    pub retry_on_timeout: bool,
+
+    /// `COUNT` hint passed to each `SCAN` cursor step when walking the
+    /// keyspace (e.g. `RedisCache::invalidate_pattern`); larger values mean
+    /// fewer round trips but a longer-held server-side scan cursor per step
+    /// (default: 500)
+    pub scan_count: u64,
+
+    /// Opt into cross-instance cache invalidation over Redis Pub/Sub (see
+    /// [`CacheInvalidator`](crate::cache::CacheInvalidator)): a
+    /// `RedisCache::invalidate_pattern` on one replica announces the
+    /// purged pattern so peers clear their own L1/in-process copies instead
+    /// of serving them until TTL (default: false)
+    pub enable_invalidation_pubsub: bool,
+}
+
+/// Per-usecase override for `RedisConfig`; any field left `None` falls back
+/// to the corresponding field on the default `RedisConfig` (see
+/// [`RedisUsecaseConfig::resolve`])
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RedisUsecaseConfig {
+    /// Override connection URL; falls back to the default pool's URL
+    pub url: Option<String>,
+    /// Override pool size; falls back to the default pool's size
+    pub pool_size: Option<usize>,
+    /// Override connection timeout in milliseconds
+    pub connection_timeout_ms: Option<u64>,
+    /// Override command timeout in milliseconds
+    pub command_timeout_ms: Option<u64>,
+    /// Override default cache TTL in seconds
+    pub cache_ttl_secs: Option<u64>,
+    /// Override maximum connection retry attempts
+    pub max_retries: Option<u32>,
+    /// Override the decorrelated-jitter base backoff, in milliseconds
+    pub base_backoff_ms: Option<u64>,
+    /// Override the decorrelated-jitter backoff cap, in milliseconds
+    pub max_backoff_ms: Option<u64>,
+    /// Override whether transient timeout/connection-reset errors are retried
+    pub retry_on_timeout: Option<bool>,
+    /// Override the `SCAN` cursor `COUNT` hint
+    pub scan_count: Option<u64>,
+    /// Override whether cross-instance Pub/Sub invalidation is enabled
+    pub enable_invalidation_pubsub: Option<bool>,
+}
+
+impl RedisUsecaseConfig {
+    /// Layer this usecase's overrides on top of `default`, producing a
+    /// complete `RedisConfig` for this usecase's own connection pool
+    pub fn resolve(&self, default: &RedisConfig) -> RedisConfig {
+        RedisConfig {
+            url: self.url.clone().unwrap_or_else(|| default.url.clone()),
+            pool_size: self.pool_size.unwrap_or(default.pool_size),
+            connection_timeout_ms: self
+                .connection_timeout_ms
+                .unwrap_or(default.connection_timeout_ms),
+            command_timeout_ms: self
+                .command_timeout_ms
+                .unwrap_or(default.command_timeout_ms),
+            cache_ttl_secs: self.cache_ttl_secs.unwrap_or(default.cache_ttl_secs),
+            max_retries: self.max_retries.unwrap_or(default.max_retries),
+            base_backoff_ms: self.base_backoff_ms.unwrap_or(default.base_backoff_ms),
+            max_backoff_ms: self.max_backoff_ms.unwrap_or(default.max_backoff_ms),
+            retry_on_timeout: self.retry_on_timeout.unwrap_or(default.retry_on_timeout),
+            scan_count: self.scan_count.unwrap_or(default.scan_count),
+            enable_invalidation_pubsub: self
+                .enable_invalidation_pubsub
+                .unwrap_or(default.enable_invalidation_pubsub),
+            // Deployment topology and transport security aren't overridable
+            // per usecase: a usecase pool always shares the default pool's
+            // Cluster/Sentinel, TLS, and ACL wiring.
+            mode: default.mode.clone(),
+            cluster_nodes: default.cluster_nodes.clone(),
+            sentinel_master_name: default.sentinel_master_name.clone(),
+            sentinel_nodes: default.sentinel_nodes.clone(),
+            tls_ca_cert_path: default.tls_ca_cert_path.clone(),
+            tls_client_cert_path: default.tls_client_cert_path.clone(),
+            tls_client_key_path: default.tls_client_key_path.clone(),
+            tls_insecure_skip_verify: default.tls_insecure_skip_verify,
+            acl_username: default.acl_username.clone(),
+            acl_password: default.acl_password.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
+pub struct L1CacheConfig {
+    /// Number of independent LRU shards (default: 16)
+    pub shard_count: usize,
+
+    /// Maximum entries per shard (default: 1000)
+    pub shard_capacity: usize,
+
+    /// File to snapshot the L1 cache to/from for warm restarts; empty
+    /// disables snapshotting (default: "")
+    pub snapshot_path: String,
+}
+
+impl L1CacheConfig {
+    /// Snapshot path, or `None` if snapshotting is disabled
+    pub fn snapshot_path(&self) -> Option<&str> {
+        if self.snapshot_path.is_empty() {
+            None
+        } else {
+            Some(&self.snapshot_path)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct SecurityConfig {
     /// Enable PII detection (default: true)
     pub enable_pii_detection: bool,
@@ -77,6 +265,31 @@ pub struct SecurityConfig {
 
     /// Maximum query length in characters (default: 10000)
     pub max_query_length: usize,
+
+    /// PII pattern set: strict, standard, or relaxed (default: standard)
+    pub pattern_set: String,
+
+    /// Enable structural validation of PII matches, e.g. Luhn/SSN checks
+    /// (default: true)
+    pub enable_validation: bool,
+
+    /// Injection detection mode: strict, standard, or relaxed (default: standard)
+    pub detection_mode: String,
+
+    /// Minimum injection severity to report: low, medium, high, or critical
+    /// (default: low)
+    pub severity_threshold: String,
+
+    /// Redaction policy rules, as semicolon-separated `<condition> =>
+    /// <strategy>` pairs evaluated per PII match in order, first match
+    /// wins (e.g. `pii.type == "SSN" => hash; pii.type == "Email" =>
+    /// token`). See `reflex_layer::pii::policy` for the condition
+    /// grammar. Empty disables rule-based overrides (default: "")
+    pub redaction_policy_rules: String,
+
+    /// Redaction strategy applied when no policy rule matches: mask,
+    /// hash, partial, remove, or token (default: mask)
+    pub default_redaction_strategy: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -84,20 +297,25 @@ pub struct RateLimitConfig {
     /// Enable rate limiting (default: true)
     pub enabled: bool,
 
-    /// Free tier: requests per minute (default: 10)
-    pub free_tier_rpm: usize,
+    /// Free tier window, parsed by [`RateLimitConfig::tier_config_table`]
+    /// (default: "100/hour")
+    pub free_tier_window: String,
 
-    /// Basic tier: requests per minute (default: 60)
-    pub basic_tier_rpm: usize,
+    /// Basic tier window (default: "1000/hour")
+    pub basic_tier_window: String,
 
-    /// Pro tier: requests per minute (default: 300)
-    pub pro_tier_rpm: usize,
+    /// Pro tier window (default: "10000/hour")
+    pub pro_tier_window: String,
 
-    /// Token bucket capacity (default: 60)
-    pub capacity: usize,
+    /// Enterprise tier window (default: "100000/hour")
+    pub enterprise_tier_window: String,
 
-    /// Token refill rate per second (default: 1.0)
-    pub refill_rate: f64,
+    /// API keys mapped to rate-limit tiers, as semicolon-separated
+    /// `<api_key>=<tier>` pairs (e.g.
+    /// `"sk-live-abc=pro;sk-live-xyz=enterprise"`). A caller's `x-api-key`
+    /// header is looked up here by `process_text`; an absent or
+    /// unrecognized key falls back to the Free tier (default: "")
+    pub api_keys: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -107,6 +325,49 @@ pub struct PerformanceConfig {
 
     /// Worker threads (default: number of CPU cores)
     pub worker_threads: usize,
+
+    /// Number of independent LRU shards in the `VerdictCache` sitting in
+    /// front of `InjectionDetector::detect` (default: 16)
+    pub verdict_cache_shard_count: usize,
+
+    /// Maximum entries per `VerdictCache` shard (default: 1000)
+    pub verdict_cache_shard_capacity: usize,
+
+    /// Default TTL, in seconds, for a cached detection verdict (default: 300)
+    pub verdict_cache_ttl_secs: u64,
+
+    /// File to snapshot the `VerdictCache` to/from for warm restarts; empty
+    /// disables snapshotting (default: "")
+    pub verdict_cache_snapshot_path: String,
+
+    /// Base64-encoded 32-byte key for at-rest encryption of cached values
+    /// (see [`CacheCrypto`]); empty disables encryption (default: "")
+    pub cache_encryption_key: String,
+}
+
+impl PerformanceConfig {
+    /// Verdict cache snapshot path, or `None` if snapshotting is disabled
+    pub fn verdict_cache_snapshot_path(&self) -> Option<&str> {
+        if self.verdict_cache_snapshot_path.is_empty() {
+            None
+        } else {
+            Some(&self.verdict_cache_snapshot_path)
+        }
+    }
+
+    /// Build a [`CacheCrypto`] from `cache_encryption_key`, or `None` if
+    /// encryption is disabled (empty key) or the key isn't valid base64-
+    /// encoded `CACHE_CRYPTO_KEY_LEN` bytes
+    pub fn cache_crypto(&self) -> Option<CacheCrypto> {
+        if self.cache_encryption_key.is_empty() {
+            return None;
+        }
+
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let key_bytes = STANDARD.decode(&self.cache_encryption_key).ok()?;
+        let key: [u8; CACHE_CRYPTO_KEY_LEN] = key_bytes.try_into().ok()?;
+        Some(CacheCrypto::new(&key))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -127,28 +388,60 @@ impl Config {
             .set_default("server.port", 8080)?
             .set_default("server.max_body_size", 10_485_760)? // 10MB
             .set_default("server.request_timeout_secs", 30)?
+            .set_default("server.error_format", "octo")?
             // Redis defaults
             .set_default("redis.url", "redis://localhost:6379")?
             .set_default("redis.pool_size", 10)?
             .set_default("redis.connection_timeout_ms", 1000)?
             .set_default("redis.command_timeout_ms", 100)?
             .set_default("redis.cache_ttl_secs", 3600)?
+            .set_default("redis.mode", "standalone")?
+            .set_default("redis.cluster_nodes", "")?
+            .set_default("redis.sentinel_master_name", "")?
+            .set_default("redis.sentinel_nodes", "")?
+            .set_default("redis.tls_ca_cert_path", "")?
+            .set_default("redis.tls_client_cert_path", "")?
+            .set_default("redis.tls_client_key_path", "")?
+            .set_default("redis.tls_insecure_skip_verify", false)?
+            .set_default("redis.acl_username", "")?
+            .set_default("redis.acl_password", "")?
+            .set_default("redis.max_retries", 3)?
+            .set_default("redis.base_backoff_ms", 100)?
+            .set_default("redis.max_backoff_ms", 5000)?
+            .set_default("redis.retry_on_timeout", true)?
+            .set_default("redis.scan_count", 500)?
+            .set_default("redis.enable_invalidation_pubsub", false)?
+            // L1 cache defaults
+            .set_default("l1_cache.shard_count", 16)?
+            .set_default("l1_cache.shard_capacity", 1000)?
+            .set_default("l1_cache.snapshot_path", "")?
             // Security defaults
             .set_default("security.enable_pii_detection", true)?
             .set_default("security.enable_injection_detection", true)?
             .set_default("security.block_on_high_risk", true)?
             .set_default("security.alert_on_critical", true)?
             .set_default("security.max_query_length", 10000)?
+            .set_default("security.pattern_set", "standard")?
+            .set_default("security.enable_validation", true)?
+            .set_default("security.detection_mode", "standard")?
+            .set_default("security.severity_threshold", "low")?
+            .set_default("security.redaction_policy_rules", "")?
+            .set_default("security.default_redaction_strategy", "mask")?
             // Rate limiting defaults
             .set_default("rate_limit.enabled", true)?
-            .set_default("rate_limit.free_tier_rpm", 10)?
-            .set_default("rate_limit.basic_tier_rpm", 60)?
-            .set_default("rate_limit.pro_tier_rpm", 300)?
-            .set_default("rate_limit.capacity", 60)?
-            .set_default("rate_limit.refill_rate", 1.0)?
+            .set_default("rate_limit.free_tier_window", "100/hour")?
+            .set_default("rate_limit.basic_tier_window", "1000/hour")?
+            .set_default("rate_limit.pro_tier_window", "10000/hour")?
+            .set_default("rate_limit.enterprise_tier_window", "100000/hour")?
+            .set_default("rate_limit.api_keys", "")?
             // Performance defaults
             .set_default("performance.max_concurrent_requests", 1000)?
             .set_default("performance.worker_threads", num_cpus::get() as i64)?
+            .set_default("performance.verdict_cache_shard_count", 16)?
+            .set_default("performance.verdict_cache_shard_capacity", 1000)?
+            .set_default("performance.verdict_cache_ttl_secs", 300)?
+            .set_default("performance.verdict_cache_snapshot_path", "")?
+            .set_default("performance.cache_encryption_key", "")?
             // Logging defaults
             .set_default("logging.level", "info")?
             .set_default("logging.format", "json")?
@@ -170,6 +463,15 @@ impl ServerConfig {
     pub fn request_timeout(&self) -> Duration {
         Duration::from_secs(self.request_timeout_secs)
     }
+
+    /// Parse `error_format` into an `ErrorFormat`, falling back to `Octo`
+    /// for unrecognized values
+    pub fn error_format(&self) -> crate::error::ErrorFormat {
+        match self.error_format.to_lowercase().as_str() {
+            "openai" => crate::error::ErrorFormat::OpenAI,
+            _ => crate::error::ErrorFormat::Octo,
+        }
+    }
 }
 
 impl RedisConfig {
@@ -187,6 +489,161 @@ impl RedisConfig {
     pub fn cache_ttl(&self) -> Duration {
         Duration::from_secs(self.cache_ttl_secs)
     }
+
+    /// The decorrelated-jitter delay sequence a caller would sleep between
+    /// retries, up to `max_retries` terms, each one a fresh random draw in
+    /// `next_delay_ms`'s style (`min(max_backoff_ms, random(base_backoff_ms,
+    /// prev * 3))`). Exposed so command-level retry call sites (cache
+    /// gets/sets, rate-limit checks) can share the same backoff shape the
+    /// connection-acquisition retry in `redis_client` already uses, without
+    /// reaching into that module's private `RetryPolicy`.
+    pub fn backoff_schedule(&self) -> BackoffSchedule {
+        BackoffSchedule {
+            base_backoff_ms: self.base_backoff_ms,
+            max_backoff_ms: self.max_backoff_ms,
+            remaining: self.max_retries,
+            prev_delay_ms: self.base_backoff_ms,
+        }
+    }
+
+    /// Parse `mode` (plus the matching node-list fields) into a
+    /// `RedisDeploymentMode`, falling back to `Standalone` for unrecognized
+    /// `mode` values
+    pub fn deployment_mode(&self) -> RedisDeploymentMode {
+        match self.mode.to_lowercase().as_str() {
+            "cluster" => {
+                let nodes = split_node_list(&self.cluster_nodes);
+                RedisDeploymentMode::Cluster {
+                    nodes: if nodes.is_empty() {
+                        vec![self.url.clone()]
+                    } else {
+                        nodes
+                    },
+                }
+            }
+            "sentinel" => {
+                let sentinels = split_node_list(&self.sentinel_nodes);
+                RedisDeploymentMode::Sentinel {
+                    master_name: self.sentinel_master_name.clone(),
+                    sentinels: if sentinels.is_empty() {
+                        vec![self.url.clone()]
+                    } else {
+                        sentinels
+                    },
+                }
+            }
+            _ => RedisDeploymentMode::Standalone,
+        }
+    }
+}
+
+/// Decorrelated-jitter delay sequence produced by
+/// [`RedisConfig::backoff_schedule`], one `Duration` per retry attempt
+pub struct BackoffSchedule {
+    base_backoff_ms: u64,
+    max_backoff_ms: u64,
+    remaining: u32,
+    prev_delay_ms: u64,
+}
+
+impl Iterator for BackoffSchedule {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let upper = self
+            .prev_delay_ms
+            .saturating_mul(3)
+            .max(self.base_backoff_ms);
+        let jittered = rand::thread_rng().gen_range(self.base_backoff_ms..=upper);
+        self.prev_delay_ms = jittered.min(self.max_backoff_ms);
+
+        Some(Duration::from_millis(self.prev_delay_ms))
+    }
+}
+
+/// Split a comma-separated node-list config value into trimmed, non-empty
+/// entries
+fn split_node_list(nodes: &str) -> Vec<String> {
+    nodes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl SecurityConfig {
+    /// Parse `pattern_set` into a `PatternSet`, falling back to `Standard`
+    /// for unrecognized values
+    pub fn pii_pattern_set(&self) -> PatternSet {
+        match self.pattern_set.to_lowercase().as_str() {
+            "strict" => PatternSet::Strict,
+            "relaxed" => PatternSet::Relaxed,
+            _ => PatternSet::Standard,
+        }
+    }
+
+    /// Parse `detection_mode` into a `DetectionMode`, falling back to
+    /// `Standard` for unrecognized values
+    pub fn injection_detection_mode(&self) -> DetectionMode {
+        match self.detection_mode.to_lowercase().as_str() {
+            "strict" => DetectionMode::Strict,
+            "relaxed" => DetectionMode::Relaxed,
+            _ => DetectionMode::Standard,
+        }
+    }
+
+    /// Parse `severity_threshold` into a `Severity`, falling back to `Low`
+    /// for unrecognized values
+    pub fn injection_severity_threshold(&self) -> Severity {
+        match self.severity_threshold.to_lowercase().as_str() {
+            "medium" => Severity::Medium,
+            "high" => Severity::High,
+            "critical" => Severity::Critical,
+            _ => Severity::Low,
+        }
+    }
+
+    /// Parse `default_redaction_strategy` into a `RedactionStrategy`,
+    /// falling back to `Mask` for unrecognized values
+    pub fn default_redaction_strategy(&self) -> RedactionStrategy {
+        self.default_redaction_strategy
+            .parse()
+            .unwrap_or(RedactionStrategy::Mask)
+    }
+
+    /// Build the `RedactionPolicy` described by `redaction_policy_rules`
+    /// and `default_redaction_strategy`
+    pub fn redaction_policy(&self) -> Result<RedactionPolicy, PolicyError> {
+        RedactionPolicy::from_rules_str(
+            &self.redaction_policy_rules,
+            self.default_redaction_strategy(),
+        )
+    }
+}
+
+impl RateLimitConfig {
+    /// Parse each tier's configured window into a `TierConfigTable`,
+    /// starting from every tier's compiled-in default and overriding only
+    /// the tiers parsed here successfully
+    pub fn tier_config_table(&self) -> Result<TierConfigTable, RateLimitError> {
+        let table = TierConfigTable::new();
+        table.update_from_window(RateLimitTier::Free, &self.free_tier_window)?;
+        table.update_from_window(RateLimitTier::Basic, &self.basic_tier_window)?;
+        table.update_from_window(RateLimitTier::Pro, &self.pro_tier_window)?;
+        table.update_from_window(RateLimitTier::Enterprise, &self.enterprise_tier_window)?;
+        Ok(table)
+    }
+
+    /// Parse `api_keys` into an `ApiKeyTierTable`
+    pub fn api_key_tier_table(&self) -> Result<ApiKeyTierTable, RateLimitError> {
+        ApiKeyTierTable::from_rules_str(&self.api_keys)
+    }
 }
 
 #[cfg(test)]
@@ -215,8 +672,286 @@ mod tests {
             port: 9000,
             max_body_size: 1024,
             request_timeout_secs: 10,
+            error_format: "octo".to_string(),
         };
 
         assert_eq!(server_config.bind_address(), "127.0.0.1:9000");
     }
+
+    #[test]
+    fn test_error_format_parses_known_values_and_falls_back_to_octo() {
+        let mut server_config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 9000,
+            max_body_size: 1024,
+            request_timeout_secs: 10,
+            error_format: "OpenAI".to_string(),
+        };
+        assert_eq!(server_config.error_format(), crate::error::ErrorFormat::OpenAI);
+
+        server_config.error_format = "nonsense".to_string();
+        assert_eq!(server_config.error_format(), crate::error::ErrorFormat::Octo);
+    }
+
+    fn test_performance_config(cache_encryption_key: &str) -> PerformanceConfig {
+        PerformanceConfig {
+            max_concurrent_requests: 1000,
+            worker_threads: 4,
+            verdict_cache_shard_count: 16,
+            verdict_cache_shard_capacity: 1000,
+            verdict_cache_ttl_secs: 300,
+            verdict_cache_snapshot_path: "".to_string(),
+            cache_encryption_key: cache_encryption_key.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cache_crypto_disabled_when_key_empty() {
+        assert!(test_performance_config("").cache_crypto().is_none());
+    }
+
+    #[test]
+    fn test_cache_crypto_builds_from_valid_base64_key() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let key = STANDARD.encode([7u8; CACHE_CRYPTO_KEY_LEN]);
+        assert!(test_performance_config(&key).cache_crypto().is_some());
+    }
+
+    #[test]
+    fn test_cache_crypto_none_for_invalid_key() {
+        // Valid base64, but wrong decoded length
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let key = STANDARD.encode([7u8; 10]);
+        assert!(test_performance_config(&key).cache_crypto().is_none());
+
+        assert!(test_performance_config("not valid base64!!")
+            .cache_crypto()
+            .is_none());
+    }
+
+    fn test_security_config(pattern_set: &str, detection_mode: &str, severity_threshold: &str) -> SecurityConfig {
+        SecurityConfig {
+            enable_pii_detection: true,
+            enable_injection_detection: true,
+            block_on_high_risk: true,
+            alert_on_critical: true,
+            max_query_length: 10000,
+            pattern_set: pattern_set.to_string(),
+            enable_validation: true,
+            detection_mode: detection_mode.to_string(),
+            severity_threshold: severity_threshold.to_string(),
+            redaction_policy_rules: "".to_string(),
+            default_redaction_strategy: "mask".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_security_config_parses_known_values() {
+        let security = test_security_config("Strict", "Relaxed", "High");
+        assert_eq!(security.pii_pattern_set(), PatternSet::Strict);
+        assert_eq!(security.injection_detection_mode(), DetectionMode::Relaxed);
+        assert_eq!(security.injection_severity_threshold(), Severity::High);
+    }
+
+    #[test]
+    fn test_security_config_falls_back_to_defaults_on_unknown_values() {
+        let security = test_security_config("nonsense", "nonsense", "nonsense");
+        assert_eq!(security.pii_pattern_set(), PatternSet::Standard);
+        assert_eq!(security.injection_detection_mode(), DetectionMode::Standard);
+        assert_eq!(security.injection_severity_threshold(), Severity::Low);
+    }
+
+    #[test]
+    fn test_default_redaction_strategy_falls_back_to_mask_on_unknown_value() {
+        let mut security = test_security_config("standard", "standard", "low");
+        security.default_redaction_strategy = "nonsense".to_string();
+        assert_eq!(security.default_redaction_strategy(), RedactionStrategy::Mask);
+    }
+
+    #[test]
+    fn test_redaction_policy_builds_from_rules() {
+        let mut security = test_security_config("standard", "standard", "low");
+        security.redaction_policy_rules = r#"pii.type == "SSN" => hash"#.to_string();
+        assert!(security.redaction_policy().is_ok());
+    }
+
+    fn test_redis_config() -> RedisConfig {
+        RedisConfig {
+            url: "redis://localhost:6379".to_string(),
+            pool_size: 10,
+            connection_timeout_ms: 1000,
+            command_timeout_ms: 100,
+            cache_ttl_secs: 3600,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_redis_deployment_mode_defaults_to_standalone() {
+        let redis = test_redis_config();
+        assert_eq!(redis.deployment_mode(), RedisDeploymentMode::Standalone);
+    }
+
+    #[test]
+    fn test_redis_deployment_mode_parses_cluster() {
+        let mut redis = test_redis_config();
+        redis.mode = "Cluster".to_string();
+        redis.cluster_nodes = "redis://node-a:6379, redis://node-b:6379".to_string();
+        assert_eq!(
+            redis.deployment_mode(),
+            RedisDeploymentMode::Cluster {
+                nodes: vec![
+                    "redis://node-a:6379".to_string(),
+                    "redis://node-b:6379".to_string()
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_redis_deployment_mode_parses_sentinel() {
+        let mut redis = test_redis_config();
+        redis.mode = "sentinel".to_string();
+        redis.sentinel_master_name = "mymaster".to_string();
+        redis.sentinel_nodes = "redis://sentinel-a:26379,redis://sentinel-b:26379".to_string();
+        assert_eq!(
+            redis.deployment_mode(),
+            RedisDeploymentMode::Sentinel {
+                master_name: "mymaster".to_string(),
+                sentinels: vec![
+                    "redis://sentinel-a:26379".to_string(),
+                    "redis://sentinel-b:26379".to_string()
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_redis_deployment_mode_falls_back_to_standalone_on_unknown_mode() {
+        let mut redis = test_redis_config();
+        redis.mode = "nonsense".to_string();
+        assert_eq!(redis.deployment_mode(), RedisDeploymentMode::Standalone);
+    }
+
+    #[test]
+    fn test_redis_usecase_config_resolve_falls_back_to_default() {
+        let usecase = RedisUsecaseConfig::default();
+        let resolved = usecase.resolve(&test_redis_config());
+        assert_eq!(resolved.url, "redis://localhost:6379");
+        assert_eq!(resolved.pool_size, 10);
+    }
+
+    #[test]
+    fn test_redis_usecase_config_resolve_applies_overrides() {
+        let usecase = RedisUsecaseConfig {
+            url: Some("redis://pii-pool:6379".to_string()),
+            pool_size: Some(50),
+            ..Default::default()
+        };
+        let resolved = usecase.resolve(&test_redis_config());
+        assert_eq!(resolved.url, "redis://pii-pool:6379");
+        assert_eq!(resolved.pool_size, 50);
+        assert_eq!(resolved.connection_timeout_ms, 1000); // inherited from default
+    }
+
+    #[test]
+    fn test_backoff_schedule_yields_max_retries_terms_within_bounds() {
+        let mut redis = test_redis_config();
+        redis.max_retries = 5;
+        redis.base_backoff_ms = 100;
+        redis.max_backoff_ms = 5000;
+
+        let delays: Vec<_> = redis.backoff_schedule().collect();
+        assert_eq!(delays.len(), 5);
+        for delay in delays {
+            assert!(delay.as_millis() as u64 >= 100);
+            assert!(delay.as_millis() as u64 <= 5000);
+        }
+    }
+
+    #[test]
+    fn test_backoff_schedule_is_empty_when_max_retries_is_zero() {
+        let mut redis = test_redis_config();
+        redis.max_retries = 0;
+        assert_eq!(redis.backoff_schedule().count(), 0);
+    }
+
+    #[test]
+    fn test_retry_on_timeout_overridable_per_usecase() {
+        let mut default = test_redis_config();
+        default.retry_on_timeout = true;
+
+        let usecase = RedisUsecaseConfig {
+            retry_on_timeout: Some(false),
+            ..Default::default()
+        };
+        assert!(!usecase.resolve(&default).retry_on_timeout);
+    }
+
+    #[test]
+    fn test_invalidation_pubsub_disabled_by_default_and_overridable_per_usecase() {
+        let default = test_redis_config();
+        assert!(!default.enable_invalidation_pubsub);
+
+        let usecase = RedisUsecaseConfig {
+            enable_invalidation_pubsub: Some(true),
+            ..Default::default()
+        };
+        assert!(usecase.resolve(&default).enable_invalidation_pubsub);
+    }
+
+    fn test_rate_limit_config(api_keys: &str) -> RateLimitConfig {
+        RateLimitConfig {
+            enabled: true,
+            free_tier_window: "100/hour".to_string(),
+            basic_tier_window: "1000/hour".to_string(),
+            pro_tier_window: "10000/hour".to_string(),
+            enterprise_tier_window: "100000/hour".to_string(),
+            api_keys: api_keys.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tier_config_table_parses_configured_windows() {
+        let rate_limit = test_rate_limit_config("");
+        let table = rate_limit.tier_config_table().unwrap();
+
+        assert!((table.get(RateLimitTier::Pro).requests_per_hour() - 10000.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_tier_config_table_rejects_malformed_window() {
+        let mut rate_limit = test_rate_limit_config("");
+        rate_limit.pro_tier_window = "nonsense".to_string();
+        assert!(rate_limit.tier_config_table().is_err());
+    }
+
+    #[test]
+    fn test_api_key_tier_table_resolves_configured_keys() {
+        let rate_limit = test_rate_limit_config("sk-live-pro=pro");
+        let table = rate_limit.api_key_tier_table().unwrap();
+
+        assert_eq!(table.resolve(Some("sk-live-pro")), RateLimitTier::Pro);
+        assert_eq!(table.resolve(Some("unknown")), RateLimitTier::Free);
+    }
+
+    #[test]
+    fn test_l1_cache_config_snapshot_path_disabled_when_empty() {
+        let l1_cache = L1CacheConfig {
+            shard_count: 16,
+            shard_capacity: 1000,
+            snapshot_path: "".to_string(),
+        };
+        assert_eq!(l1_cache.snapshot_path(), None);
+    }
+
+    #[test]
+    fn test_l1_cache_config_snapshot_path_set_when_nonempty() {
+        let l1_cache = L1CacheConfig {
+            shard_count: 16,
+            shard_capacity: 1000,
+            snapshot_path: "/tmp/reflex_l1_cache.jsonl".to_string(),
+        };
+        assert_eq!(l1_cache.snapshot_path(), Some("/tmp/reflex_l1_cache.jsonl"));
+    }
 }
@@ -3,13 +3,86 @@
 //! Provides comprehensive error types and conversions for all reflex layer operations.
 
 use axum::{
-    http::StatusCode,
+    http::{HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
 use thiserror::Error;
+use uuid::Uuid;
+
+/// Output shape `IntoResponse` impls serialize an error into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ErrorFormat {
+    /// OctoLLM's native flat `ErrorResponse` shape
+    #[default]
+    Octo,
+    /// OpenAI-compatible `{ "error": { message, type, code, param } }`
+    /// envelope, for clients built against the OpenAI API shape
+    OpenAI,
+}
+
+static ERROR_FORMAT: AtomicU8 = AtomicU8::new(0); // 0 = Octo, 1 = OpenAI
+
+/// Set the process-wide error envelope format `IntoResponse` impls
+/// serialize into; call once at startup (and again on config reload) from
+/// `ServerConfig::error_format()`
+pub fn set_error_format(format: ErrorFormat) {
+    let value = match format {
+        ErrorFormat::Octo => 0,
+        ErrorFormat::OpenAI => 1,
+    };
+    ERROR_FORMAT.store(value, Ordering::Relaxed);
+}
+
+/// Read the currently configured error envelope format (`Octo` until
+/// `set_error_format` has been called)
+pub fn error_format() -> ErrorFormat {
+    match ERROR_FORMAT.load(Ordering::Relaxed) {
+        1 => ErrorFormat::OpenAI,
+        _ => ErrorFormat::Octo,
+    }
+}
+
+/// An OpenAI-compatible error envelope: `{ "error": { message, type, code,
+/// param } }`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIErrorEnvelope {
+    /// The error body
+    pub error: OpenAIErrorBody,
+}
+
+/// The `error` object inside an [`OpenAIErrorEnvelope`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenAIErrorBody {
+    /// Human-readable error message
+    pub message: String,
+    /// OpenAI error type, e.g. `invalid_request_error`, `rate_limit_exceeded`, `api_error`
+    #[serde(rename = "type")]
+    pub error_type: String,
+    /// Machine-readable error code, if any
+    pub code: Option<String>,
+    /// The request parameter this error relates to, if any
+    pub param: Option<String>,
+}
+
+tokio::task_local! {
+    /// The current request's correlation id, set by the request-ID
+    /// middleware for the lifetime of request handling so `IntoResponse`
+    /// impls deep in the call stack can attach it to `ErrorResponse` and log
+    /// lines without it being threaded through every function signature
+    pub static REQUEST_ID: String;
+}
+
+/// Read the current request's correlation id, if the request-ID middleware
+/// has set one for this task; `None` outside request handling (e.g. tests,
+/// or code running off the request task)
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
 
 /// Main error type for the Reflex Layer
 #[derive(Error, Debug)]
@@ -27,8 +100,15 @@ pub enum ReflexError {
     Cache(String),
 
     /// Rate limit exceeded
-    #[error("Rate limit exceeded: {0}")]
-    RateLimit(String),
+    #[error("Rate limit exceeded: {remaining}/{limit} remaining, resets in {reset:?}")]
+    RateLimit {
+        /// Configured request limit for the window
+        limit: u64,
+        /// Requests remaining in the current window (0 when exceeded)
+        remaining: u64,
+        /// Time until the window resets
+        reset: Duration,
+    },
 
     /// PII detection error
     #[error("PII detection error: {0}")]
@@ -65,6 +145,13 @@ pub enum ReflexError {
     /// HTTP error
     #[error("HTTP error: {0}")]
     Http(String),
+
+    /// Catch-all for arbitrary upstream errors that don't map to a more
+    /// specific variant. Still produces a correlatable `error_id` like every
+    /// other variant, so folding an error in here never costs us the ability
+    /// to trace a user's report back to the exact log line
+    #[error("Unexpected error: {0}")]
+    Unexpected(#[from] anyhow::Error),
 }
 
 /// Result type alias for Reflex Layer operations
@@ -79,26 +166,142 @@ pub struct ErrorResponse {
     /// Error message
     pub message: String,
 
-    /// Detailed error (optional, for debugging)
+    /// Detailed error (optional, for debugging; only populated in debug
+    /// builds so internal detail never leaks to clients in release builds)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
+    /// Short random id correlating this response with the full error logged
+    /// server-side, so a user-reported id can be grepped straight out of the
+    /// logs regardless of build profile
+    pub error_id: String,
+
     /// Request ID for tracing
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_id: Option<String>,
 
     /// Timestamp of error
     pub timestamp: String,
+
+    /// Configured request limit for the window (rate-limit errors only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Requests remaining in the current window (rate-limit errors only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u64>,
+
+    /// Seconds until the window resets (rate-limit errors only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset: Option<u64>,
 }
 
-impl ReflexError {
+/// Build the `Retry-After`/`X-RateLimit-*` headers a rate-limit response
+/// should carry, so well-behaved clients can back off precisely instead of
+/// guessing
+fn rate_limit_headers(limit: u64, remaining: u64, reset: Duration) -> [(&'static str, HeaderValue); 4] {
+    let reset_secs = reset.as_secs();
+    [
+        ("Retry-After", HeaderValue::from(reset_secs)),
+        ("X-RateLimit-Limit", HeaderValue::from(limit)),
+        ("X-RateLimit-Remaining", HeaderValue::from(remaining)),
+        ("X-RateLimit-Reset", HeaderValue::from(reset_secs)),
+    ]
+}
+
+/// Shared error-response behavior for the crate's error enums (`ReflexError`,
+/// `ApiError`). Both used to carry hand-written, copy-pasted
+/// `status_code`/`client_message`/`IntoResponse` logic that could quietly
+/// drift apart; implementing this trait instead keeps that logic in one
+/// place and lets both `IntoResponse` impls be a one-line delegation
+pub trait ReflexResponseError: std::fmt::Display {
     /// Convert error to HTTP status code
-    pub fn status_code(&self) -> StatusCode {
+    fn status_code(&self) -> StatusCode;
+
+    /// Get error message suitable for client display
+    fn client_message(&self) -> String;
+
+    /// Check if error should be logged at ERROR level (vs WARN)
+    fn is_severe(&self) -> bool;
+
+    /// Map this error to an OpenAI-compatible error body
+    fn to_openai_error(&self) -> OpenAIErrorBody;
+
+    /// Rate-limit fields to surface as response headers and `ErrorResponse`
+    /// fields, for variants that represent a rate-limit condition
+    fn rate_limit(&self) -> Option<(u64, u64, Duration)> {
+        None
+    }
+
+    /// Build the HTTP response: the `ErrorResponse`/OpenAI envelope selected
+    /// by the configured [`ErrorFormat`], rate-limit headers when
+    /// applicable, and a log line carrying the same `error_id`/`request_id`
+    /// returned to the client
+    fn into_response(self) -> Response
+    where
+        Self: Sized,
+    {
+        let status = self.status_code();
+        let message = self.client_message();
+        let detail = if cfg!(debug_assertions) {
+            Some(self.to_string())
+        } else {
+            None
+        };
+
+        let rate_limit = self.rate_limit();
+        let request_id = current_request_id();
+        let error_id = Uuid::new_v4().to_string();
+
+        let error_response = ErrorResponse {
+            code: status.as_u16(),
+            message,
+            detail,
+            error_id: error_id.clone(),
+            request_id: request_id.clone(),
+            timestamp: Utc::now().to_rfc3339(),
+            limit: rate_limit.map(|(limit, _, _)| limit),
+            remaining: rate_limit.map(|(_, remaining, _)| remaining),
+            reset: rate_limit.map(|(_, _, reset)| reset.as_secs()),
+        };
+
+        let openai_error = self.to_openai_error();
+        let is_severe = self.is_severe();
+
+        // Log the full error and its id, tagged with the same correlation id
+        // returned to the client, so a user-reported error_id (always
+        // present, unlike `detail`) can be grepped straight out of the logs
+        // regardless of build profile
+        let request_id = request_id.unwrap_or_else(|| "unknown".to_string());
+        if is_severe {
+            tracing::error!(request_id = %request_id, error_id = %error_id, error = %self, "Request error");
+        } else {
+            tracing::warn!(request_id = %request_id, error_id = %error_id, error = %self, "Request error");
+        }
+
+        let mut response = match error_format() {
+            ErrorFormat::Octo => (status, Json(error_response)).into_response(),
+            ErrorFormat::OpenAI => {
+                (status, Json(OpenAIErrorEnvelope { error: openai_error })).into_response()
+            }
+        };
+        if let Some((limit, remaining, reset)) = rate_limit {
+            let headers = response.headers_mut();
+            for (name, value) in rate_limit_headers(limit, remaining, reset) {
+                headers.insert(name, value);
+            }
+        }
+        response
+    }
+}
+
+impl ReflexResponseError for ReflexError {
+    fn status_code(&self) -> StatusCode {
         match self {
             ReflexError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ReflexError::Redis(_) => StatusCode::SERVICE_UNAVAILABLE,
             ReflexError::Cache(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ReflexError::RateLimit(_) => StatusCode::TOO_MANY_REQUESTS,
+            ReflexError::RateLimit { .. } => StatusCode::TOO_MANY_REQUESTS,
             ReflexError::PiiDetection(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ReflexError::InjectionDetected(_) => StatusCode::BAD_REQUEST,
             ReflexError::Validation(_) => StatusCode::BAD_REQUEST,
@@ -108,16 +311,16 @@ impl ReflexError {
             ReflexError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ReflexError::Serialization(_) => StatusCode::BAD_REQUEST,
             ReflexError::Http(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ReflexError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
-    /// Get error message suitable for client display
-    pub fn client_message(&self) -> String {
+    fn client_message(&self) -> String {
         match self {
             ReflexError::Config(_) => "Service configuration error".to_string(),
             ReflexError::Redis(_) => "Cache service unavailable".to_string(),
             ReflexError::Cache(_) => "Cache operation failed".to_string(),
-            ReflexError::RateLimit(msg) => msg.clone(),
+            ReflexError::RateLimit { .. } => "Rate limit exceeded".to_string(),
             ReflexError::PiiDetection(_) => "Security check failed".to_string(),
             ReflexError::InjectionDetected(msg) => msg.clone(),
             ReflexError::Validation(msg) => msg.clone(),
@@ -127,48 +330,87 @@ impl ReflexError {
             ReflexError::Database(_) => "Database error".to_string(),
             ReflexError::Serialization(_) => "Invalid request format".to_string(),
             ReflexError::Http(msg) => msg.clone(),
+            ReflexError::Unexpected(_) => "Internal server error".to_string(),
         }
     }
 
-    /// Check if error should be logged at ERROR level (vs WARN)
-    pub fn is_severe(&self) -> bool {
+    fn is_severe(&self) -> bool {
         matches!(
             self,
             ReflexError::Config(_)
                 | ReflexError::Redis(_)
                 | ReflexError::Internal(_)
                 | ReflexError::Database(_)
+                | ReflexError::Unexpected(_)
         )
     }
+
+    /// `InjectionDetected`/`Validation` become `invalid_request_error`,
+    /// `RateLimit` becomes `rate_limit_exceeded`, and backend/service
+    /// failures (`Internal`, `Redis`, `Database`, and anything else whose
+    /// status isn't itself a 4xx) become `api_error`
+    fn to_openai_error(&self) -> OpenAIErrorBody {
+        let error_type = match self {
+            ReflexError::InjectionDetected(_) | ReflexError::Validation(_) => {
+                "invalid_request_error"
+            }
+            ReflexError::RateLimit { .. } => "rate_limit_exceeded",
+            ReflexError::Internal(_) | ReflexError::Redis(_) | ReflexError::Database(_) => {
+                "api_error"
+            }
+            _ if self.status_code().is_client_error() => "invalid_request_error",
+            _ => "api_error",
+        };
+
+        OpenAIErrorBody {
+            message: self.client_message(),
+            error_type: error_type.to_string(),
+            code: None,
+            param: None,
+        }
+    }
+
+    fn rate_limit(&self) -> Option<(u64, u64, Duration)> {
+        match self {
+            ReflexError::RateLimit {
+                limit,
+                remaining,
+                reset,
+            } => Some((*limit, *remaining, *reset)),
+            _ => None,
+        }
+    }
 }
 
 /// Convert ReflexError to HTTP response
 impl IntoResponse for ReflexError {
     fn into_response(self) -> Response {
-        let status = self.status_code();
-        let message = self.client_message();
-        let detail = if cfg!(debug_assertions) {
-            Some(self.to_string())
-        } else {
-            None
-        };
-
-        let error_response = ErrorResponse {
-            code: status.as_u16(),
-            message,
-            detail,
-            request_id: None, // TODO(#1): Extract request ID from middleware context
-            timestamp: Utc::now().to_rfc3339(),
-        };
+        ReflexResponseError::into_response(self)
+    }
+}
 
-        // Log the error
-        if self.is_severe() {
-            tracing::error!(error = %self, "Reflex layer error");
-        } else {
-            tracing::warn!(error = %self, "Reflex layer warning");
+/// Best-effort, lossy conversion for variants that have no exact `ApiError`
+/// counterpart; handlers that need precise status-code fidelity should
+/// construct the target variant directly instead of relying on this
+impl From<ApiError> for ReflexError {
+    fn from(err: ApiError) -> Self {
+        match err {
+            ApiError::ValidationError(msg) => ReflexError::Validation(msg),
+            ApiError::RateLimitError {
+                limit,
+                remaining,
+                reset,
+            } => ReflexError::RateLimit {
+                limit,
+                remaining,
+                reset,
+            },
+            ApiError::CacheError(msg) => ReflexError::Cache(msg),
+            ApiError::DetectionError(msg) => {
+                ReflexError::Internal(format!("Detection error: {}", msg))
+            }
+            ApiError::InternalError(msg) => ReflexError::Internal(msg),
         }
-
-        (status, Json(error_response)).into_response()
     }
 }
 
@@ -193,7 +435,12 @@ mod tests {
     #[test]
     fn test_error_status_codes() {
         assert_eq!(
-            ReflexError::RateLimit("test".to_string()).status_code(),
+            ReflexError::RateLimit {
+                limit: 100,
+                remaining: 0,
+                reset: Duration::from_secs(30),
+            }
+            .status_code(),
             StatusCode::TOO_MANY_REQUESTS
         );
         assert_eq!(
@@ -215,17 +462,141 @@ mod tests {
         )))
         .is_severe());
         assert!(!ReflexError::Validation("test".to_string()).is_severe());
-        assert!(!ReflexError::RateLimit("test".to_string()).is_severe());
+        assert!(!ReflexError::RateLimit {
+            limit: 100,
+            remaining: 0,
+            reset: Duration::from_secs(30),
+        }
+        .is_severe());
     }
 
     #[test]
     fn test_client_messages() {
-        let err = ReflexError::RateLimit("Rate limit exceeded for user".to_string());
-        assert_eq!(err.client_message(), "Rate limit exceeded for user");
+        let err = ReflexError::RateLimit {
+            limit: 100,
+            remaining: 0,
+            reset: Duration::from_secs(30),
+        };
+        assert_eq!(err.client_message(), "Rate limit exceeded");
 
         let err = ReflexError::Internal("Database connection pool exhausted".to_string());
         assert_eq!(err.client_message(), "Internal server error");
     }
+
+    #[test]
+    fn test_rate_limit_response_carries_headers_and_body_fields() {
+        let err = ReflexError::RateLimit {
+            limit: 100,
+            remaining: 5,
+            reset: Duration::from_secs(42),
+        };
+        let response = err.into_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let headers = response.headers();
+        assert_eq!(headers.get("Retry-After").unwrap(), "42");
+        assert_eq!(headers.get("X-RateLimit-Limit").unwrap(), "100");
+        assert_eq!(headers.get("X-RateLimit-Remaining").unwrap(), "5");
+        assert_eq!(headers.get("X-RateLimit-Reset").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_current_request_id_absent_outside_task_local_scope() {
+        assert_eq!(current_request_id(), None);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_picks_up_request_id_from_task_local() {
+        REQUEST_ID
+            .scope("req-abc-123".to_string(), async {
+                assert_eq!(current_request_id(), Some("req-abc-123".to_string()));
+
+                let response = ReflexError::Internal("boom".to_string()).into_response();
+                assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+            })
+            .await;
+
+        // The task-local doesn't leak outside its scope
+        assert_eq!(current_request_id(), None);
+    }
+
+    #[test]
+    fn test_to_openai_error_maps_known_variants() {
+        assert_eq!(
+            ReflexError::InjectionDetected("nope".to_string())
+                .to_openai_error()
+                .error_type,
+            "invalid_request_error"
+        );
+        assert_eq!(
+            ReflexError::Validation("bad field".to_string())
+                .to_openai_error()
+                .error_type,
+            "invalid_request_error"
+        );
+        assert_eq!(
+            ReflexError::RateLimit {
+                limit: 100,
+                remaining: 0,
+                reset: Duration::from_secs(1),
+            }
+            .to_openai_error()
+            .error_type,
+            "rate_limit_exceeded"
+        );
+        for err in [
+            ReflexError::Internal("boom".to_string()),
+            ReflexError::Database("boom".to_string()),
+            ReflexError::Redis(redis::RedisError::from((
+                redis::ErrorKind::IoError,
+                "connection failed",
+            ))),
+        ] {
+            assert_eq!(err.to_openai_error().error_type, "api_error");
+        }
+    }
+
+    #[test]
+    fn test_error_format_defaults_to_octo() {
+        assert_eq!(error_format(), ErrorFormat::Octo);
+    }
+
+    #[test]
+    fn test_unexpected_error_status_and_severity() {
+        let err = ReflexError::Unexpected(anyhow::anyhow!("pool connector panicked"));
+        assert_eq!(err.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.client_message(), "Internal server error");
+        assert!(err.is_severe());
+        assert_eq!(err.to_openai_error().error_type, "api_error");
+    }
+
+    #[tokio::test]
+    async fn test_error_response_always_carries_an_error_id() {
+        let response = ReflexError::Internal("boom".to_string()).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert!(!parsed.error_id.is_empty());
+        assert!(Uuid::parse_str(&parsed.error_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_error_ids_differ_between_responses() {
+        let first = ReflexError::Internal("boom".to_string()).into_response();
+        let second = ReflexError::Internal("boom".to_string()).into_response();
+
+        let first_body = axum::body::to_bytes(first.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let second_body = axum::body::to_bytes(second.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let first: ErrorResponse = serde_json::from_slice(&first_body).unwrap();
+        let second: ErrorResponse = serde_json::from_slice(&second_body).unwrap();
+
+        assert_ne!(first.error_id, second.error_id);
+    }
 }
 
 /// API-specific error type for handlers
@@ -239,8 +610,15 @@ pub enum ApiError {
     ValidationError(String),
 
     /// Rate limit exceeded (429 Too Many Requests)
-    #[error("Rate limit exceeded: {0}")]
-    RateLimitError(String),
+    #[error("Rate limit exceeded: {remaining}/{limit} remaining, resets in {reset:?}")]
+    RateLimitError {
+        /// Configured request limit for the window
+        limit: u64,
+        /// Requests remaining in the current window (0 when exceeded)
+        remaining: u64,
+        /// Time until the window resets
+        reset: Duration,
+    },
 
     /// Cache error (500 Internal Server Error)
     #[error("Cache error: {0}")]
@@ -255,51 +633,189 @@ pub enum ApiError {
     InternalError(String),
 }
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let (status, message) = match &self {
-            ApiError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            ApiError::RateLimitError(_) => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "Rate limit exceeded".to_string(),
-            ),
-            ApiError::CacheError(_) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "Cache error".to_string())
-            }
-            ApiError::DetectionError(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Detection error".to_string(),
-            ),
-            ApiError::InternalError(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            ),
-        };
+impl ReflexResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ApiError::RateLimitError { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::CacheError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::DetectionError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
 
-        let detail = if cfg!(debug_assertions) {
-            Some(self.to_string())
-        } else {
-            None
+    fn client_message(&self) -> String {
+        match self {
+            ApiError::ValidationError(msg) => msg.clone(),
+            ApiError::RateLimitError { .. } => "Rate limit exceeded".to_string(),
+            ApiError::CacheError(_) => "Cache error".to_string(),
+            ApiError::DetectionError(_) => "Detection error".to_string(),
+            ApiError::InternalError(_) => "Internal server error".to_string(),
+        }
+    }
+
+    fn is_severe(&self) -> bool {
+        matches!(
+            self,
+            ApiError::CacheError(_) | ApiError::DetectionError(_) | ApiError::InternalError(_)
+        )
+    }
+
+    /// Follows the same mapping as [`ReflexError::to_openai_error`]
+    fn to_openai_error(&self) -> OpenAIErrorBody {
+        let (error_type, message) = match self {
+            ApiError::ValidationError(msg) => ("invalid_request_error", msg.clone()),
+            ApiError::RateLimitError { .. } => {
+                ("rate_limit_exceeded", "Rate limit exceeded".to_string())
+            }
+            ApiError::CacheError(_) => ("api_error", "Cache error".to_string()),
+            ApiError::DetectionError(_) => ("api_error", "Detection error".to_string()),
+            ApiError::InternalError(_) => ("api_error", "Internal server error".to_string()),
         };
 
-        let error_response = ErrorResponse {
-            code: status.as_u16(),
+        OpenAIErrorBody {
             message,
-            detail,
-            request_id: None,
-            timestamp: Utc::now().to_rfc3339(),
-        };
+            error_type: error_type.to_string(),
+            code: None,
+            param: None,
+        }
+    }
 
-        // Log the error
+    fn rate_limit(&self) -> Option<(u64, u64, Duration)> {
         match self {
-            ApiError::ValidationError(_) | ApiError::RateLimitError(_) => {
-                tracing::warn!(error = %self, "API request rejected");
+            ApiError::RateLimitError {
+                limit,
+                remaining,
+                reset,
+            } => Some((*limit, *remaining, *reset)),
+            _ => None,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        ReflexResponseError::into_response(self)
+    }
+}
+
+/// Best-effort, lossy conversion for variants that have no exact
+/// `ReflexError` counterpart (e.g. `InjectionDetected`'s 400 collapses into
+/// `ValidationError` since `ApiError` has no dedicated detection-rejection
+/// variant); handlers that need precise status-code fidelity should
+/// construct the target variant directly instead of relying on this
+impl From<ReflexError> for ApiError {
+    fn from(err: ReflexError) -> Self {
+        match err {
+            ReflexError::Config(msg) => {
+                ApiError::InternalError(format!("Configuration error: {}", msg))
+            }
+            ReflexError::Redis(e) => ApiError::CacheError(e.to_string()),
+            ReflexError::Cache(msg) => ApiError::CacheError(msg),
+            ReflexError::RateLimit {
+                limit,
+                remaining,
+                reset,
+            } => ApiError::RateLimitError {
+                limit,
+                remaining,
+                reset,
+            },
+            ReflexError::PiiDetection(msg) => ApiError::DetectionError(msg),
+            ReflexError::InjectionDetected(msg) => ApiError::ValidationError(msg),
+            ReflexError::Validation(msg) => ApiError::ValidationError(msg),
+            ReflexError::RequestTooLarge(msg) => ApiError::ValidationError(msg),
+            ReflexError::Timeout(msg) => ApiError::InternalError(msg),
+            ReflexError::Internal(msg) => ApiError::InternalError(msg),
+            ReflexError::Database(msg) => ApiError::InternalError(msg),
+            ReflexError::Serialization(e) => ApiError::ValidationError(e.to_string()),
+            ReflexError::Http(msg) => ApiError::InternalError(msg),
+            ReflexError::Unexpected(e) => ApiError::InternalError(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod api_error_tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_to_openai_error_maps_known_variants() {
+        assert_eq!(
+            ApiError::ValidationError("bad field".to_string())
+                .to_openai_error()
+                .error_type,
+            "invalid_request_error"
+        );
+        assert_eq!(
+            ApiError::RateLimitError {
+                limit: 100,
+                remaining: 0,
+                reset: Duration::from_secs(1),
             }
-            _ => {
-                tracing::error!(error = %self, "API error");
+            .to_openai_error()
+            .error_type,
+            "rate_limit_exceeded"
+        );
+        for err in [
+            ApiError::CacheError("boom".to_string()),
+            ApiError::DetectionError("boom".to_string()),
+            ApiError::InternalError("boom".to_string()),
+        ] {
+            assert_eq!(err.to_openai_error().error_type, "api_error");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_error_response_always_carries_an_error_id() {
+        let response = ApiError::InternalError("boom".to_string()).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let parsed: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert!(!parsed.error_id.is_empty());
+        assert!(Uuid::parse_str(&parsed.error_id).is_ok());
+    }
+
+    #[test]
+    fn test_api_error_from_reflex_error_preserves_rate_limit_fields() {
+        let reflex_err = ReflexError::RateLimit {
+            limit: 100,
+            remaining: 3,
+            reset: Duration::from_secs(7),
+        };
+        match ApiError::from(reflex_err) {
+            ApiError::RateLimitError {
+                limit,
+                remaining,
+                reset,
+            } => {
+                assert_eq!(limit, 100);
+                assert_eq!(remaining, 3);
+                assert_eq!(reset, Duration::from_secs(7));
             }
+            other => panic!("expected RateLimitError, got {:?}", other),
         }
+    }
 
-        (status, Json(error_response)).into_response()
+    #[test]
+    fn test_reflex_error_from_api_error_preserves_rate_limit_fields() {
+        let api_err = ApiError::RateLimitError {
+            limit: 50,
+            remaining: 1,
+            reset: Duration::from_secs(9),
+        };
+        match ReflexError::from(api_err) {
+            ReflexError::RateLimit {
+                limit,
+                remaining,
+                reset,
+            } => {
+                assert_eq!(limit, 50);
+                assert_eq!(remaining, 1);
+                assert_eq!(reset, Duration::from_secs(9));
+            }
+            other => panic!("expected RateLimit, got {:?}", other),
+        }
     }
 }
@@ -4,13 +4,14 @@
 //! that orchestrates PII detection, injection detection, caching, and rate limiting.
 
 use axum::{
-    extract::{ConnectInfo, State},
+    extract::{ConnectInfo, Query, State},
+    http::HeaderMap,
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 use crate::AppState;
@@ -19,9 +20,13 @@ use reflex_layer::{
     error::ApiError,
     injection::{InjectionMatch, Severity},
     pii::PIIMatch,
-    ratelimit::{RateLimitKey, RateLimitTier},
+    ratelimit::{RateLimitBackend, RateLimitKey, RateLimitResult},
 };
 
+/// Header a caller sets to authenticate as a specific rate-limit tier; see
+/// [`AppState::api_key_tiers`]
+pub const API_KEY_HEADER: &str = "x-api-key";
+
 /// Request payload for /process endpoint
 #[derive(Debug, Deserialize)]
 pub struct ProcessRequest {
@@ -49,6 +54,53 @@ fn default_true() -> bool {
     true
 }
 
+/// Query parameters for `GET /process`
+///
+/// A URL-addressable equivalent of `ProcessRequest` for callers that can't
+/// easily issue a JSON POST body (CDN workers, curl probes, log-scrubbing
+/// proxies): `GET /process?text=...&checks=pii,injection&cache=false`.
+#[derive(Debug, Deserialize)]
+pub struct ProcessQuery {
+    /// Text to analyze
+    pub text: String,
+
+    /// Optional user ID for rate limiting
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Comma-separated list of checks to run (`pii`, `injection`). Omit to
+    /// run both, matching `ProcessRequest`'s defaults.
+    #[serde(default)]
+    pub checks: Option<String>,
+
+    /// Whether to use caching (default: true)
+    #[serde(default = "default_true")]
+    pub cache: bool,
+}
+
+impl From<ProcessQuery> for ProcessRequest {
+    fn from(query: ProcessQuery) -> Self {
+        let (check_pii, check_injection) = match &query.checks {
+            Some(list) => {
+                let requested: Vec<&str> = list.split(',').map(str::trim).collect();
+                (
+                    requested.contains(&"pii"),
+                    requested.contains(&"injection"),
+                )
+            }
+            None => (true, true),
+        };
+
+        ProcessRequest {
+            text: query.text,
+            user_id: query.user_id,
+            check_pii,
+            check_injection,
+            use_cache: query.cache,
+        }
+    }
+}
+
 /// Response payload for /process endpoint
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProcessResponse {
@@ -64,6 +116,10 @@ pub struct ProcessResponse {
     /// PII matches found
     pub pii_matches: Vec<PIIMatch>,
 
+    /// `text` with detected PII redacted per the server's `RedactionPolicy`;
+    /// `None` when no PII was detected or PII checking was disabled
+    pub redacted_text: Option<String>,
+
     /// Whether injection attempt was detected
     pub injection_detected: bool,
 
@@ -75,6 +131,12 @@ pub struct ProcessResponse {
 
     /// Processing time in milliseconds
     pub processing_time_ms: f64,
+
+    /// Name of the rate-limit tier that was applied to this request
+    /// (resolved from the `x-api-key` header, falling back to `"free"`
+    /// when absent or unrecognized)
+    #[serde(default)]
+    pub rate_limit_tier: String,
 }
 
 /// Processing status enum
@@ -94,7 +156,7 @@ pub enum ProcessStatus {
     Error,
 }
 
-/// Main processing endpoint handler
+/// Main processing endpoint handler (`POST /process`)
 ///
 /// Processes text through the full Reflex Layer pipeline:
 /// 1. Rate limiting (IP-based and optionally user-based)
@@ -115,7 +177,32 @@ pub enum ProcessStatus {
 pub async fn process_text(
     State(state): State<Arc<AppState>>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(request): Json<ProcessRequest>,
+) -> Result<Json<ProcessResponse>, ApiError> {
+    process(state, addr, headers, request).await
+}
+
+/// Query-string equivalent of [`process_text`] (`GET /process`), for
+/// callers that can't issue a JSON POST body
+///
+/// Runs the same validation, rate-limiting, and caching pipeline; only the
+/// extraction layer differs.
+pub async fn process_text_query(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<ProcessQuery>,
+) -> Result<Json<ProcessResponse>, ApiError> {
+    process(state, addr, headers, query.into()).await
+}
+
+/// Shared implementation behind both `process_text` and `process_text_query`
+async fn process(
+    state: Arc<AppState>,
+    addr: SocketAddr,
+    headers: HeaderMap,
+    request: ProcessRequest,
 ) -> Result<Json<ProcessResponse>, ApiError> {
     let request_id = Uuid::new_v4().to_string();
     let start = Instant::now();
@@ -134,58 +221,65 @@ pub async fn process_text(
     }
 
     // 1. Rate Limiting
+    // Resolve the caller's tier from their x-api-key header, falling back to
+    // the Free tier (the same limit unauthenticated callers always got) when
+    // the header is absent or doesn't match a configured key.
+    let api_key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok());
+    let tier = state.api_key_tiers.resolve(api_key);
+    let tier_config = state.tier_config_table.get(tier);
+
     let ip = addr.ip().to_string();
     let rate_limit_key = RateLimitKey::IP(ip.clone());
 
-    // Use Free tier config for IP: 100 requests/hour, burst of 10
-    let ip_config = RateLimitTier::Free.config();
-
     let result = state
         .rate_limiter
-        .check_rate_limit(&rate_limit_key, &ip_config, 1.0)
+        .incr(&rate_limit_key, &tier_config, 1.0)
         .await
-        .map_err(|e| ApiError::RateLimitError(format!("Rate limit check failed: {}", e)))?;
+        .map_err(|e| ApiError::InternalError(format!("Rate limit check failed: {}", e)))?;
 
-    if !result.is_allowed() {
+    if let RateLimitResult::Limited {
+        retry_after_ms,
+        current_tokens,
+        ..
+    } = result
+    {
         tracing::warn!("Rate limit exceeded for IP: {}", ip);
-        return Ok(Json(ProcessResponse {
-            request_id,
-            status: ProcessStatus::RateLimited,
-            pii_detected: false,
-            pii_matches: vec![],
-            injection_detected: false,
-            injection_matches: vec![],
-            cache_hit: false,
-            processing_time_ms: start.elapsed().as_secs_f64() * 1000.0,
-        }));
+        crate::metrics::record_rate_limited_client(&ip);
+        return Err(ApiError::RateLimitError {
+            limit: tier_config.capacity,
+            remaining: current_tokens.max(0.0) as u64,
+            reset: Duration::from_millis(retry_after_ms),
+        });
     }
 
-    // Also check user-based rate limit if user_id provided
+    // Also check user-based rate limit if user_id provided, against the
+    // same resolved tier
     if let Some(ref user_id) = request.user_id {
         let user_rate_limit_key = RateLimitKey::User(user_id.clone());
-        // Use Basic tier for users: 1000 requests/hour, burst of 50
-        let user_config = RateLimitTier::Basic.config();
 
         let user_result = state
             .rate_limiter
-            .check_rate_limit(&user_rate_limit_key, &user_config, 1.0)
+            .incr(&user_rate_limit_key, &tier_config, 1.0)
             .await
             .map_err(|e| {
-                ApiError::RateLimitError(format!("User rate limit check failed: {}", e))
+                ApiError::InternalError(format!("User rate limit check failed: {}", e))
             })?;
 
-        if !user_result.is_allowed() {
+        if let RateLimitResult::Limited {
+            retry_after_ms,
+            current_tokens,
+            ..
+        } = user_result
+        {
             tracing::warn!("Rate limit exceeded for user: {}", user_id);
-            return Ok(Json(ProcessResponse {
-                request_id,
-                status: ProcessStatus::RateLimited,
-                pii_detected: false,
-                pii_matches: vec![],
-                injection_detected: false,
-                injection_matches: vec![],
-                cache_hit: false,
-                processing_time_ms: start.elapsed().as_secs_f64() * 1000.0,
-            }));
+            crate::metrics::record_rate_limited_client(user_id);
+            return Err(ApiError::RateLimitError {
+                limit: tier_config.capacity,
+                remaining: current_tokens.max(0.0) as u64,
+                reset: Duration::from_millis(retry_after_ms),
+            });
         }
     }
 
@@ -213,6 +307,7 @@ pub async fn process_text(
                 response.cache_hit = true;
                 response.request_id = request_id; // Update with new request ID
                 response.processing_time_ms = start.elapsed().as_secs_f64() * 1000.0;
+                response.rate_limit_tier = tier.to_string();
                 return Ok(Json(response));
             } else {
                 tracing::warn!("Failed to deserialize cached response, proceeding with detection");
@@ -225,7 +320,7 @@ pub async fn process_text(
     // 3. PII Detection
     let pii_matches = if request.check_pii {
         let pii_start = Instant::now();
-        let matches = state.pii_detector.detect(&request.text);
+        let matches = state.pii_detector.load().detect(&request.text);
         let pii_duration = pii_start.elapsed();
         tracing::debug!(
             "PII detection completed in {:?}, found {} matches",
@@ -239,10 +334,23 @@ pub async fn process_text(
 
     let pii_detected = !pii_matches.is_empty();
 
-    // 4. Injection Detection
+    // Redact any detected PII per the server's policy, choosing a strategy
+    // independently for each match (e.g. hash SSNs, token-replace emails)
+    let redacted_text = pii_detected.then(|| {
+        for pii_match in &pii_matches {
+            crate::telemetry::record_pii_redaction(pii_match.pii_type.clone());
+        }
+        state
+            .redaction_policy
+            .load()
+            .redact(&request.text, &pii_matches, "/process")
+    });
+
+    // 4. Injection Detection (verdict cache consulted before, populated after)
     let injection_matches = if request.check_injection {
         let injection_start = Instant::now();
-        let matches = state.injection_detector.detect(&request.text);
+        let detector = state.injection_detector.load();
+        let matches = state.verdict_cache.detect_cached(&detector, &request.text);
         let injection_duration = injection_start.elapsed();
         tracing::debug!(
             "Injection detection completed in {:?}, found {} matches",
@@ -255,6 +363,12 @@ pub async fn process_text(
     };
 
     let injection_detected = !injection_matches.is_empty();
+    for injection_match in &injection_matches {
+        crate::telemetry::record_injection_detection(
+            injection_match.injection_type.clone(),
+            injection_match.severity,
+        );
+    }
 
     // 5. Determine Status
     // Block if critical injection detected
@@ -275,10 +389,12 @@ pub async fn process_text(
         status,
         pii_detected,
         pii_matches,
+        redacted_text,
         injection_detected,
         injection_matches,
         cache_hit,
         processing_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+        rate_limit_tier: tier.to_string(),
     };
 
     // 7. Cache Response (if caching enabled and processing was successful)
@@ -336,14 +452,83 @@ mod tests {
             status: ProcessStatus::Success,
             pii_detected: false,
             pii_matches: vec![],
+            redacted_text: None,
             injection_detected: false,
             injection_matches: vec![],
             cache_hit: false,
             processing_time_ms: 1.23,
+            rate_limit_tier: "free".to_string(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("test-123"));
         assert!(json.contains("success"));
+        assert!(json.contains("free"));
+    }
+
+    #[test]
+    fn test_process_response_deserializes_without_rate_limit_tier_field() {
+        // A response cached before this field existed should still
+        // deserialize, defaulting the new field to an empty string.
+        let json = r#"{
+            "request_id": "test-123",
+            "status": "success",
+            "pii_detected": false,
+            "pii_matches": [],
+            "redacted_text": null,
+            "injection_detected": false,
+            "injection_matches": [],
+            "cache_hit": false,
+            "processing_time_ms": 1.23
+        }"#;
+
+        let response: ProcessResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.rate_limit_tier, "");
+    }
+
+    #[test]
+    fn test_process_query_defaults_to_both_checks_and_cache_on() {
+        let query = ProcessQuery {
+            text: "hello".to_string(),
+            user_id: None,
+            checks: None,
+            cache: true,
+        };
+        let req: ProcessRequest = query.into();
+
+        assert_eq!(req.text, "hello");
+        assert!(req.check_pii);
+        assert!(req.check_injection);
+        assert!(req.use_cache);
+    }
+
+    #[test]
+    fn test_process_query_checks_list_enables_only_named_checks() {
+        let query = ProcessQuery {
+            text: "hello".to_string(),
+            user_id: Some("user123".to_string()),
+            checks: Some("pii".to_string()),
+            cache: false,
+        };
+        let req: ProcessRequest = query.into();
+
+        assert!(req.check_pii);
+        assert!(!req.check_injection);
+        assert!(!req.use_cache);
+        assert_eq!(req.user_id, Some("user123".to_string()));
+    }
+
+    #[test]
+    fn test_process_query_checks_list_is_comma_separated_and_trims_whitespace() {
+        let query = ProcessQuery {
+            text: "hello".to_string(),
+            user_id: None,
+            checks: Some("pii, injection".to_string()),
+            cache: true,
+        };
+        let req: ProcessRequest = query.into();
+
+        assert!(req.check_pii);
+        assert!(req.check_injection);
     }
 }
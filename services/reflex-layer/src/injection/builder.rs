@@ -0,0 +1,176 @@
+// Fluent Builder for InjectionConfig
+//
+// Building an `InjectionConfig` by hand means remembering which field combinations actually make
+// sense together; `InjectionConfigBuilder` validates that at `build()` time instead of leaving it
+// to be discovered at detection time.
+
+use thiserror::Error;
+
+use crate::injection::types::{DetectionMode, InjectionConfig, Severity};
+
+/// Error returned when an `InjectionConfigBuilder` can't produce a valid config
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuilderError {
+    /// `DetectionMode::Strict` only evaluates critical patterns, so a
+    /// sub-critical threshold would silently admit nothing
+    #[error(
+        "severity_threshold {0} is incompatible with DetectionMode::Strict \
+         (only Critical patterns are evaluated in strict mode)"
+    )]
+    ThresholdBelowStrictMode(Severity),
+}
+
+/// Fluent builder for `InjectionConfig`
+///
+/// Unset fields fall back to `InjectionConfig::default()`'s values at
+/// `build()` time.
+#[derive(Debug, Clone, Default)]
+pub struct InjectionConfigBuilder {
+    detection_mode: Option<DetectionMode>,
+    enable_context_analysis: Option<bool>,
+    enable_entropy_check: Option<bool>,
+    severity_threshold: Option<Severity>,
+    enable_homoglyph_normalization: Option<bool>,
+    enable_leet_folding: Option<bool>,
+    enable_decode_rescan: Option<bool>,
+}
+
+impl InjectionConfigBuilder {
+    /// Start a new builder with no fields set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the detection mode
+    pub fn detection_mode(mut self, mode: DetectionMode) -> Self {
+        self.detection_mode = Some(mode);
+        self
+    }
+
+    /// Enable or disable context analysis
+    pub fn enable_context_analysis(mut self, enabled: bool) -> Self {
+        self.enable_context_analysis = Some(enabled);
+        self
+    }
+
+    /// Enable or disable entropy checking
+    pub fn enable_entropy_check(mut self, enabled: bool) -> Self {
+        self.enable_entropy_check = Some(enabled);
+        self
+    }
+
+    /// Set the minimum severity threshold to report
+    pub fn severity_threshold(mut self, threshold: Severity) -> Self {
+        self.severity_threshold = Some(threshold);
+        self
+    }
+
+    /// Enable or disable matching against a homoglyph-normalized view of the text
+    pub fn enable_homoglyph_normalization(mut self, enabled: bool) -> Self {
+        self.enable_homoglyph_normalization = Some(enabled);
+        self
+    }
+
+    /// Enable or disable matching against a further leet-folded view of the text
+    pub fn enable_leet_folding(mut self, enabled: bool) -> Self {
+        self.enable_leet_folding = Some(enabled);
+        self
+    }
+
+    /// Enable or disable decoding and rescanning candidate encoded regions
+    /// (base64/base32/hex/ROT13/URL-percent)
+    pub fn enable_decode_rescan(mut self, enabled: bool) -> Self {
+        self.enable_decode_rescan = Some(enabled);
+        self
+    }
+
+    /// Validate the accumulated fields and build an `InjectionConfig`
+    pub fn build(self) -> Result<InjectionConfig, BuilderError> {
+        let default = InjectionConfig::default();
+        let detection_mode = self.detection_mode.unwrap_or(default.detection_mode);
+        let severity_threshold = self
+            .severity_threshold
+            .unwrap_or(default.severity_threshold);
+
+        if detection_mode == DetectionMode::Strict && severity_threshold < Severity::Critical {
+            return Err(BuilderError::ThresholdBelowStrictMode(severity_threshold));
+        }
+
+        Ok(InjectionConfig {
+            detection_mode,
+            enable_context_analysis: self
+                .enable_context_analysis
+                .unwrap_or(default.enable_context_analysis),
+            enable_entropy_check: self
+                .enable_entropy_check
+                .unwrap_or(default.enable_entropy_check),
+            severity_threshold,
+            enable_homoglyph_normalization: self
+                .enable_homoglyph_normalization
+                .unwrap_or(default.enable_homoglyph_normalization),
+            enable_leet_folding: self
+                .enable_leet_folding
+                .unwrap_or(default.enable_leet_folding),
+            enable_decode_rescan: self
+                .enable_decode_rescan
+                .unwrap_or(default.enable_decode_rescan),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults_match_injection_config_default() {
+        let built = InjectionConfigBuilder::new().build().unwrap();
+        assert_eq!(built.detection_mode, InjectionConfig::default().detection_mode);
+        assert_eq!(
+            built.severity_threshold,
+            InjectionConfig::default().severity_threshold
+        );
+    }
+
+    #[test]
+    fn test_builder_sets_provided_fields() {
+        let built = InjectionConfigBuilder::new()
+            .detection_mode(DetectionMode::Relaxed)
+            .enable_context_analysis(false)
+            .enable_entropy_check(false)
+            .severity_threshold(Severity::Medium)
+            .enable_homoglyph_normalization(false)
+            .enable_leet_folding(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(built.detection_mode, DetectionMode::Relaxed);
+        assert!(!built.enable_context_analysis);
+        assert!(!built.enable_entropy_check);
+        assert_eq!(built.severity_threshold, Severity::Medium);
+        assert!(!built.enable_homoglyph_normalization);
+        assert!(built.enable_leet_folding);
+    }
+
+    #[test]
+    fn test_builder_rejects_strict_mode_with_low_threshold() {
+        let err = InjectionConfigBuilder::new()
+            .detection_mode(DetectionMode::Strict)
+            .severity_threshold(Severity::Medium)
+            .build()
+            .unwrap_err();
+
+        assert_eq!(err, BuilderError::ThresholdBelowStrictMode(Severity::Medium));
+    }
+
+    #[test]
+    fn test_builder_accepts_strict_mode_with_critical_threshold() {
+        let built = InjectionConfigBuilder::new()
+            .detection_mode(DetectionMode::Strict)
+            .severity_threshold(Severity::Critical)
+            .build()
+            .unwrap();
+
+        assert_eq!(built.detection_mode, DetectionMode::Strict);
+    }
+}
@@ -0,0 +1,275 @@
+//! Composite pattern detection: co-occurrence scoring across base matches
+//!
+//! Some injection attempts only become recognizable when several weaker
+//! signals appear together — e.g. an "ignore previous instructions" phrase
+//! near an encoded payload near a command injection marker is a far
+//! stronger signal than any one alone. `CombinedPattern` describes such a
+//! rule, and `detect_composites` scans a set of already-detected
+//! `InjectionMatch`es for the tightest group that satisfies one.
+
+use crate::injection::types::{InjectionMatch, InjectionType, Severity};
+
+/// A rule describing a combination of base injection types that, seen
+/// together within an optional byte window, constitute a stronger signal
+/// than any of them individually
+#[derive(Debug, Clone)]
+pub struct CombinedPattern {
+    /// Base injection types that must all be present to trigger this rule
+    pub required: Vec<InjectionType>,
+    /// Maximum byte distance allowed between the earliest and latest
+    /// contributing match; `None` means no distance limit
+    pub window: Option<usize>,
+    /// Severity assigned to the synthesized composite match
+    pub severity: Severity,
+}
+
+impl CombinedPattern {
+    /// Create a new combined pattern rule
+    pub fn new(required: Vec<InjectionType>, window: Option<usize>, severity: Severity) -> Self {
+        Self {
+            required,
+            window,
+            severity,
+        }
+    }
+}
+
+/// Default set of combined pattern rules shipped with the detector
+pub fn default_combined_patterns() -> Vec<CombinedPattern> {
+    vec![
+        CombinedPattern::new(
+            vec![
+                InjectionType::IgnorePreviousInstructions,
+                InjectionType::EncodedInstruction,
+                InjectionType::CommandInjection,
+            ],
+            Some(200),
+            Severity::Critical,
+        ),
+        CombinedPattern::new(
+            vec![
+                InjectionType::SystemRoleManipulation,
+                InjectionType::DirectPromptExtraction,
+            ],
+            Some(150),
+            Severity::Critical,
+        ),
+        CombinedPattern::new(
+            vec![
+                InjectionType::DelimiterInjection,
+                InjectionType::NestedPrompt,
+            ],
+            Some(100),
+            Severity::High,
+        ),
+    ]
+}
+
+/// Scan `matches` for the tightest group satisfying each `CombinedPattern`
+/// rule, emitting one synthesized `InjectionType::Composite` match per
+/// satisfied rule. The synthesized match's indicators record which spans
+/// contributed, so downstream consumers can see the full chain.
+pub fn detect_composites(
+    matches: &[InjectionMatch],
+    rules: &[CombinedPattern],
+) -> Vec<InjectionMatch> {
+    let mut composites = Vec::new();
+
+    for rule in rules {
+        let contributors: Vec<Vec<&InjectionMatch>> = rule
+            .required
+            .iter()
+            .map(|required_type| {
+                matches
+                    .iter()
+                    .filter(|m| &m.injection_type == required_type)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        if contributors.iter().any(|c| c.is_empty()) {
+            continue;
+        }
+
+        if let Some(combo) = tightest_combo(&contributors, rule.window) {
+            let start = combo.iter().map(|m| m.start).min().unwrap();
+            let end = combo.iter().map(|m| m.end).max().unwrap();
+            let matched_text = combo
+                .iter()
+                .map(|m| m.matched_text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ... ");
+            let indicators = combo
+                .iter()
+                .map(|m| format!("{}@{}-{}", m.injection_type, m.start, m.end))
+                .collect();
+            let confidence = combo
+                .iter()
+                .map(|m| m.confidence)
+                .fold(0.0_f64, f64::max);
+
+            composites.push(InjectionMatch::new(
+                InjectionType::Composite,
+                start,
+                end,
+                matched_text,
+                rule.severity,
+                confidence,
+                indicators,
+            ));
+        }
+    }
+
+    composites
+}
+
+/// Find the combination of one match per required type whose overall span
+/// is narrowest and within `window` (if set). The number of required types
+/// per rule is small, so an exhaustive search over contributors is cheap.
+fn tightest_combo<'a>(
+    contributors: &[Vec<&'a InjectionMatch>],
+    window: Option<usize>,
+) -> Option<Vec<&'a InjectionMatch>> {
+    let mut best: Option<Vec<&'a InjectionMatch>> = None;
+    let mut current: Vec<&'a InjectionMatch> = Vec::with_capacity(contributors.len());
+
+    fn search<'a>(
+        contributors: &[Vec<&'a InjectionMatch>],
+        idx: usize,
+        current: &mut Vec<&'a InjectionMatch>,
+        window: Option<usize>,
+        best: &mut Option<Vec<&'a InjectionMatch>>,
+    ) {
+        if idx == contributors.len() {
+            let start = current.iter().map(|m| m.start).min().unwrap();
+            let end = current.iter().map(|m| m.end).max().unwrap();
+            let span = end - start;
+
+            if window.is_some_and(|w| span > w) {
+                return;
+            }
+
+            let is_better = match best {
+                Some(existing) => {
+                    let existing_start = existing.iter().map(|m| m.start).min().unwrap();
+                    let existing_end = existing.iter().map(|m| m.end).max().unwrap();
+                    span < existing_end - existing_start
+                }
+                None => true,
+            };
+
+            if is_better {
+                *best = Some(current.clone());
+            }
+            return;
+        }
+
+        for candidate in &contributors[idx] {
+            current.push(candidate);
+            search(contributors, idx + 1, current, window, best);
+            current.pop();
+        }
+    }
+
+    search(contributors, 0, &mut current, window, &mut best);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_match(injection_type: InjectionType, start: usize, end: usize) -> InjectionMatch {
+        InjectionMatch::new(
+            injection_type,
+            start,
+            end,
+            "x".to_string(),
+            Severity::High,
+            0.8,
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_detect_composites_fires_when_all_required_present_in_window() {
+        let matches = vec![
+            make_match(InjectionType::SystemRoleManipulation, 0, 10),
+            make_match(InjectionType::DirectPromptExtraction, 20, 30),
+        ];
+        let rules = vec![CombinedPattern::new(
+            vec![
+                InjectionType::SystemRoleManipulation,
+                InjectionType::DirectPromptExtraction,
+            ],
+            Some(150),
+            Severity::Critical,
+        )];
+
+        let composites = detect_composites(&matches, &rules);
+        assert_eq!(composites.len(), 1);
+        assert_eq!(composites[0].injection_type, InjectionType::Composite);
+        assert_eq!(composites[0].severity, Severity::Critical);
+        assert_eq!(composites[0].indicators.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_composites_respects_window() {
+        let matches = vec![
+            make_match(InjectionType::SystemRoleManipulation, 0, 10),
+            make_match(InjectionType::DirectPromptExtraction, 500, 510),
+        ];
+        let rules = vec![CombinedPattern::new(
+            vec![
+                InjectionType::SystemRoleManipulation,
+                InjectionType::DirectPromptExtraction,
+            ],
+            Some(150),
+            Severity::Critical,
+        )];
+
+        assert!(detect_composites(&matches, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_detect_composites_requires_all_types() {
+        let matches = vec![make_match(InjectionType::SystemRoleManipulation, 0, 10)];
+        let rules = vec![CombinedPattern::new(
+            vec![
+                InjectionType::SystemRoleManipulation,
+                InjectionType::DirectPromptExtraction,
+            ],
+            None,
+            Severity::Critical,
+        )];
+
+        assert!(detect_composites(&matches, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_detect_composites_picks_tightest_combo() {
+        let matches = vec![
+            make_match(InjectionType::SystemRoleManipulation, 0, 10),
+            make_match(InjectionType::DirectPromptExtraction, 900, 910),
+            make_match(InjectionType::DirectPromptExtraction, 15, 25),
+        ];
+        let rules = vec![CombinedPattern::new(
+            vec![
+                InjectionType::SystemRoleManipulation,
+                InjectionType::DirectPromptExtraction,
+            ],
+            None,
+            Severity::Critical,
+        )];
+
+        let composites = detect_composites(&matches, &rules);
+        assert_eq!(composites.len(), 1);
+        assert_eq!(composites[0].start, 0);
+        assert_eq!(composites[0].end, 25);
+    }
+
+    #[test]
+    fn test_default_combined_patterns_nonempty() {
+        assert!(!default_combined_patterns().is_empty());
+    }
+}
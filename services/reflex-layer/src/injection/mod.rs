@@ -27,6 +27,7 @@
 //     enable_context_analysis: true,
 //     enable_entropy_check: true,
 //     severity_threshold: Severity::Low,
+//     ..Default::default()
 // };
 // let detector = InjectionDetector::new(config);
 //
@@ -67,18 +68,48 @@
 // - Throughput: >5,000 detections/sec
 
 pub mod analyzer;
+pub mod builder;
+pub mod composite;
+pub mod decode_rescan;
 pub mod detector;
+pub mod escalation;
+pub mod fragments;
+pub mod golomb_filter;
+pub mod modules;
 pub mod patterns;
+pub mod policy;
+pub mod profiling;
+pub mod report;
+pub mod streaming;
 pub mod types;
+pub mod verdict_cache;
 
 // Re-export commonly used types
 pub use analyzer::{
-    adjust_severity, analyze_context, calculate_entropy, detect_encoding, ContextAnalysis,
-    EncodingType,
+    adjust_severity, analyze_context, calculate_entropy, decode_and_rescan, detect_encoding,
+    detect_rot13, extract_indicators_with_denylist, scan_entropy_windows, ContextAnalysis,
+    DecodedLayer, EncodingType, Rot13Detection,
 };
+pub use builder::{BuilderError, InjectionConfigBuilder};
+pub use composite::{default_combined_patterns, detect_composites, CombinedPattern};
+pub use decode_rescan::rescan_encoded_regions;
 pub use detector::InjectionDetector;
-pub use patterns::{get_pattern_metadata, get_patterns, PatternMetadata};
+pub use escalation::{EscalatingDetector, EscalationTier};
+pub use fragments::{expand_fragments, fragment, INSTRUCTION_NOUN, MODIFIER, ROLE_VERB};
+pub use golomb_filter::{GolombCodedSet, GolombFilterError};
+pub use modules::{
+    DetectionContext, DetectionModule, ModuleRegistry, PatternPackModule, SharedModuleRegistry,
+};
+pub use patterns::{
+    get_pattern_metadata, get_patterns, CustomPatternMetadata, InjectionPatternDefinition,
+    PatternKind, PatternMetadata, PatternRegistry, PatternRegistryError,
+};
+pub use policy::{get_patterns_for_policy, DetectionPolicy, RolePolicy, TypeOverride};
+pub use profiling::{LatencyPercentiles, PatternStats, Profiler};
+pub use report::{DetectionReport, Finding, Fix};
+pub use streaming::{detect_stream, StreamVerdict, StreamingDetector};
 pub use types::{DetectionMode, InjectionConfig, InjectionMatch, InjectionType, Severity};
+pub use verdict_cache::VerdictCache;
 
 #[cfg(test)]
 mod tests;
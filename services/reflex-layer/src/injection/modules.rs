@@ -0,0 +1,308 @@
+//! Pluggable third-party detection modules
+//!
+//! `InjectionDetector` only ships the built-in pattern types
+//! (`IgnorePreviousInstructions`, `DANVariant`, `EncodedInstruction`, ...).
+//! Borrowing the importable-module idea from Pingora's HTTP modules, a
+//! `DetectionModule` lets a caller register an additional scanner — a
+//! domain-specific jailbreak set, an org's blocklist, a vendor's pattern
+//! pack — without forking the built-in pattern list. `detect()` fans out
+//! across every registered module alongside its own passes and merges the
+//! results, re-sorting by severity the same way it already does for
+//! composites and normalized-text hits.
+//!
+//! [`PatternPackModule`] covers the common case of a module that's just a
+//! declarative regex/type/severity pack: it wraps a
+//! [`PatternRegistry`](crate::injection::patterns::PatternRegistry) loaded
+//! from a config file, so non-Rust users can ship a pack without
+//! recompiling anything.
+
+use std::sync::RwLock;
+
+use crate::injection::analyzer::ContextAnalysis;
+use crate::injection::patterns::{PatternRegistry, PatternRegistryError};
+use crate::injection::types::{DetectionMode, InjectionMatch, Severity};
+
+/// Read-only signals a `DetectionModule` can use while scanning, mirroring
+/// what `InjectionDetector`'s own passes already compute so a module isn't
+/// forced to recompute context/entropy analysis itself
+#[derive(Debug, Clone, Copy)]
+pub struct DetectionContext<'a> {
+    /// The detector's configured detection mode
+    pub detection_mode: &'a DetectionMode,
+    /// Academic/testing-context analysis of the scanned text
+    pub context: &'a ContextAnalysis,
+    /// Shannon entropy of the scanned text
+    pub entropy: f64,
+}
+
+/// A pluggable third-party detection scanner
+///
+/// Implementations are expected to be cheap to call repeatedly and safe to
+/// share across threads, the same way the built-in pattern set is: a
+/// `ModuleRegistry` is held behind a `RwLock` and read on every `detect()`
+/// call.
+pub trait DetectionModule: Send + Sync {
+    /// Human-readable name for this module, used in indicators and logs
+    fn name(&self) -> &str;
+
+    /// Severity to report when a hit doesn't carry its own, more specific
+    /// severity
+    fn default_severity(&self) -> Severity;
+
+    /// Scan `text` and return every match this module finds
+    fn scan(&self, text: &str, ctx: &DetectionContext<'_>) -> Vec<InjectionMatch>;
+}
+
+/// Holds the third-party modules registered with an `InjectionDetector`
+#[derive(Default)]
+pub struct ModuleRegistry {
+    modules: Vec<Box<dyn DetectionModule>>,
+}
+
+impl ModuleRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an additional module
+    pub fn register(&mut self, module: Box<dyn DetectionModule>) {
+        self.modules.push(module);
+    }
+
+    /// Number of registered modules
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Whether no modules are registered
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Run every registered module against `text` and return the combined
+    /// matches, in registration order (the caller re-sorts by severity)
+    pub fn scan_all(&self, text: &str, ctx: &DetectionContext<'_>) -> Vec<InjectionMatch> {
+        self.modules.iter().flat_map(|module| module.scan(text, ctx)).collect()
+    }
+}
+
+/// Thread-safe wrapper an `InjectionDetector` holds so modules can be
+/// registered after construction (`&self`, not `&mut self`), the same way
+/// `register_combined_pattern` works for composite rules
+#[derive(Default)]
+pub struct SharedModuleRegistry(RwLock<ModuleRegistry>);
+
+impl SharedModuleRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a registry pre-populated with `modules`
+    pub fn from_modules(modules: Vec<Box<dyn DetectionModule>>) -> Self {
+        Self(RwLock::new(ModuleRegistry { modules }))
+    }
+
+    /// Register an additional module
+    pub fn register(&self, module: Box<dyn DetectionModule>) {
+        self.0.write().unwrap().register(module);
+    }
+
+    /// Run every registered module against `text`
+    pub fn scan_all(&self, text: &str, ctx: &DetectionContext<'_>) -> Vec<InjectionMatch> {
+        self.0.read().unwrap().scan_all(text, ctx)
+    }
+
+    /// Number of registered modules
+    pub fn len(&self) -> usize {
+        self.0.read().unwrap().len()
+    }
+
+    /// Whether no modules are registered
+    pub fn is_empty(&self) -> bool {
+        self.0.read().unwrap().is_empty()
+    }
+}
+
+/// A declarative pattern pack loaded from a config file: just
+/// regex/injection-type/severity rules, wired up as a `DetectionModule` so
+/// non-Rust users can ship a pack without compiling anything
+///
+/// Reuses `PatternRegistry`'s JSON rule format and atomic batch-compile
+/// semantics (see [`PatternRegistry::load_from_file`]) rather than
+/// introducing a second declarative format.
+pub struct PatternPackModule {
+    name: String,
+    registry: PatternRegistry,
+    mode: DetectionMode,
+}
+
+impl PatternPackModule {
+    /// Load a pattern pack from a JSON rule file
+    pub fn from_file(
+        name: impl Into<String>,
+        path: &str,
+        mode: DetectionMode,
+    ) -> Result<Self, PatternRegistryError> {
+        let registry = PatternRegistry::new();
+        registry.load_from_file(path)?;
+        Ok(Self {
+            name: name.into(),
+            registry,
+            mode,
+        })
+    }
+
+    /// Load a pattern pack from a JSON rule string
+    pub fn from_json(
+        name: impl Into<String>,
+        json: &str,
+        mode: DetectionMode,
+    ) -> Result<Self, PatternRegistryError> {
+        let registry = PatternRegistry::new();
+        registry.load_from_json(json)?;
+        Ok(Self {
+            name: name.into(),
+            registry,
+            mode,
+        })
+    }
+}
+
+impl DetectionModule for PatternPackModule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Medium
+    }
+
+    fn scan(&self, text: &str, _ctx: &DetectionContext<'_>) -> Vec<InjectionMatch> {
+        let mut matches = Vec::new();
+
+        for (injection_type, (regex, severity)) in self.registry.get_patterns(&self.mode) {
+            for capture in regex.find_iter(text) {
+                matches.push(InjectionMatch::new(
+                    injection_type.clone(),
+                    capture.start(),
+                    capture.end(),
+                    capture.as_str().to_string(),
+                    severity,
+                    0.8,
+                    vec![format!("pattern pack: {}", self.name)],
+                ));
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::injection::types::InjectionType;
+
+    fn test_context() -> (DetectionMode, ContextAnalysis) {
+        (DetectionMode::Standard, ContextAnalysis::default())
+    }
+
+    struct AlwaysHitsModule;
+
+    impl DetectionModule for AlwaysHitsModule {
+        fn name(&self) -> &str {
+            "always-hits"
+        }
+
+        fn default_severity(&self) -> Severity {
+            Severity::High
+        }
+
+        fn scan(&self, text: &str, _ctx: &DetectionContext<'_>) -> Vec<InjectionMatch> {
+            vec![InjectionMatch::new(
+                InjectionType::Custom("always-hits".to_string()),
+                0,
+                text.len(),
+                text.to_string(),
+                self.default_severity(),
+                0.9,
+                vec!["org blocklist".to_string()],
+            )]
+        }
+    }
+
+    #[test]
+    fn test_module_registry_starts_empty() {
+        let registry = ModuleRegistry::new();
+        assert!(registry.is_empty());
+        assert_eq!(registry.len(), 0);
+    }
+
+    #[test]
+    fn test_module_registry_scans_registered_modules() {
+        let mut registry = ModuleRegistry::new();
+        registry.register(Box::new(AlwaysHitsModule));
+
+        let (mode, context) = test_context();
+        let ctx = DetectionContext {
+            detection_mode: &mode,
+            context: &context,
+            entropy: 0.0,
+        };
+        let matches = registry.scan_all("anything", &ctx);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].severity, Severity::High);
+    }
+
+    #[test]
+    fn test_shared_module_registry_register_is_visible_immediately() {
+        let shared = SharedModuleRegistry::new();
+        assert!(shared.is_empty());
+
+        shared.register(Box::new(AlwaysHitsModule));
+        assert_eq!(shared.len(), 1);
+
+        let (mode, context) = test_context();
+        let ctx = DetectionContext {
+            detection_mode: &mode,
+            context: &context,
+            entropy: 0.0,
+        };
+        assert_eq!(shared.scan_all("x", &ctx).len(), 1);
+    }
+
+    #[test]
+    fn test_pattern_pack_module_loads_from_json_and_scans() {
+        let json = r#"[{"name": "OrgBlocklist", "content": "forbidden\\s+phrase", "severity": "High"}]"#;
+        let module =
+            PatternPackModule::from_json("org-pack", json, DetectionMode::Standard).unwrap();
+
+        let (mode, context) = test_context();
+        let ctx = DetectionContext {
+            detection_mode: &mode,
+            context: &context,
+            entropy: 0.0,
+        };
+        let matches = module.scan("this has a forbidden phrase in it", &ctx);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].severity, Severity::High);
+        assert!(matches[0].indicators.iter().any(|i| i == "pattern pack: org-pack"));
+    }
+
+    #[test]
+    fn test_pattern_pack_module_rejects_invalid_rule_file() {
+        let err = PatternPackModule::from_json("bad-pack", "not json", DetectionMode::Standard);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_pattern_pack_module_name_and_default_severity() {
+        let module =
+            PatternPackModule::from_json("my-pack", "[]", DetectionMode::Standard).unwrap();
+        assert_eq!(module.name(), "my-pack");
+        assert_eq!(module.default_severity(), Severity::Medium);
+    }
+}
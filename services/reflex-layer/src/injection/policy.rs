@@ -0,0 +1,267 @@
+//! Role-based detection policies layered over `DetectionMode`
+//!
+//! A single global `DetectionMode` can't express what real deployments
+//! need: an internal admin tool can tolerate `Relaxed`, while an anonymous
+//! public endpoint needs `Strict` plus a few extra rules the built-in modes
+//! don't carry. `DetectionPolicy` maps a caller role to a `RolePolicy` —
+//! a base `DetectionMode` plus per-`InjectionType` overrides and a block
+//! threshold — the way a role database resolves permissions through
+//! inheritance: start from the role's base set, then apply its overrides.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::injection::patterns::get_patterns;
+use crate::injection::types::{DetectionMode, InjectionType, Severity};
+
+/// How a role's policy changes one `InjectionType`'s membership or severity
+/// relative to its base `DetectionMode`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeOverride {
+    /// Include this type in the effective pattern set even if the role's
+    /// base mode wouldn't, at the given severity
+    ///
+    /// Only built-in types can be force-enabled this way: the regex itself
+    /// comes from the full built-in set (`DetectionMode::Relaxed`), so a
+    /// `Custom` type with no registered pattern anywhere has nothing to
+    /// enable and the override is a no-op.
+    ForceEnable(Severity),
+    /// Exclude this type from the effective pattern set even if the role's
+    /// base mode would include it
+    ForceDisable,
+    /// Raise or lower the severity this type is reported at, if the role's
+    /// base mode (after `ForceEnable`/`ForceDisable`) includes it
+    AdjustSeverity(Severity),
+}
+
+/// A caller role's detection policy: a base `DetectionMode`, overrides on
+/// top of it, and the severity at which a match should be treated as a
+/// block rather than just a flagged finding
+#[derive(Debug, Clone)]
+pub struct RolePolicy {
+    /// Base pattern set and severity this role starts from
+    pub mode: DetectionMode,
+    /// Per-`InjectionType` adjustments applied on top of `mode`
+    pub overrides: HashMap<InjectionType, TypeOverride>,
+    /// Minimum severity at which a match under this role should block
+    /// rather than just be reported
+    pub block_threshold: Severity,
+}
+
+impl RolePolicy {
+    /// A role policy with no overrides: just `mode`'s pattern set, blocking
+    /// at `block_threshold` and above
+    pub fn new(mode: DetectionMode, block_threshold: Severity) -> Self {
+        Self {
+            mode,
+            overrides: HashMap::new(),
+            block_threshold,
+        }
+    }
+
+    /// Add an override for one `InjectionType`, replacing any existing
+    /// override for that type
+    pub fn with_override(mut self, injection_type: InjectionType, rule: TypeOverride) -> Self {
+        self.overrides.insert(injection_type, rule);
+        self
+    }
+
+    /// Whether a match of `severity` under this role should block rather
+    /// than just be reported
+    pub fn should_block(&self, severity: Severity) -> bool {
+        severity >= self.block_threshold
+    }
+}
+
+/// Maps caller roles to their `RolePolicy`, falling back to a default
+/// policy for any role that isn't explicitly registered
+#[derive(Debug, Clone)]
+pub struct DetectionPolicy {
+    roles: HashMap<String, RolePolicy>,
+    default: RolePolicy,
+}
+
+impl DetectionPolicy {
+    /// Create a policy whose unregistered roles fall back to `default`
+    pub fn new(default: RolePolicy) -> Self {
+        Self {
+            roles: HashMap::new(),
+            default,
+        }
+    }
+
+    /// Register (or replace) the policy for a specific role
+    pub fn register_role(&mut self, role: impl Into<String>, policy: RolePolicy) {
+        self.roles.insert(role.into(), policy);
+    }
+
+    /// Resolve the policy for `role`, falling back to the default policy if
+    /// `role` has no registered policy of its own
+    pub fn policy_for(&self, role: &str) -> &RolePolicy {
+        self.roles.get(role).unwrap_or(&self.default)
+    }
+}
+
+/// Resolve the effective pattern set for `role` under `policy`: start from
+/// the role's base `DetectionMode` (the same set `InjectionDetector` would
+/// use), then apply the role's overrides in order
+///
+/// Mirrors `get_patterns`'s return shape so it can be used anywhere a mode's
+/// pattern set is already consumed.
+pub fn get_patterns_for_policy(
+    policy: &DetectionPolicy,
+    role: &str,
+) -> HashMap<InjectionType, (&'static Regex, Severity)> {
+    let role_policy = policy.policy_for(role);
+    let mut patterns = get_patterns(&role_policy.mode);
+
+    for (injection_type, rule) in &role_policy.overrides {
+        match rule {
+            TypeOverride::ForceDisable => {
+                patterns.remove(injection_type);
+            }
+            TypeOverride::ForceEnable(severity) => {
+                if let Some((regex, _)) = get_patterns(&DetectionMode::Relaxed).get(injection_type)
+                {
+                    patterns.insert(injection_type.clone(), (*regex, *severity));
+                }
+            }
+            TypeOverride::AdjustSeverity(severity) => {
+                if let Some(entry) = patterns.get_mut(injection_type) {
+                    entry.1 = *severity;
+                }
+            }
+        }
+    }
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_for_unregistered_role_falls_back_to_default() {
+        let policy = DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        let resolved = policy.policy_for("unknown-role");
+        assert_eq!(resolved.mode, DetectionMode::Standard);
+        assert_eq!(resolved.block_threshold, Severity::High);
+    }
+
+    #[test]
+    fn test_policy_for_registered_role_returns_its_own_policy() {
+        let mut policy =
+            DetectionPolicy::new(RolePolicy::new(DetectionMode::Strict, Severity::Critical));
+        policy.register_role(
+            "internal-admin",
+            RolePolicy::new(DetectionMode::Relaxed, Severity::Critical),
+        );
+
+        assert_eq!(policy.policy_for("internal-admin").mode, DetectionMode::Relaxed);
+        assert_eq!(policy.policy_for("anonymous").mode, DetectionMode::Strict);
+    }
+
+    #[test]
+    fn test_get_patterns_for_policy_with_no_overrides_matches_base_mode() {
+        let policy = DetectionPolicy::new(RolePolicy::new(DetectionMode::Strict, Severity::Critical));
+        let patterns = get_patterns_for_policy(&policy, "anonymous");
+        let base = get_patterns(&DetectionMode::Strict);
+
+        assert_eq!(patterns.len(), base.len());
+        for (injection_type, (_, severity)) in &base {
+            assert_eq!(patterns.get(injection_type).map(|(_, s)| *s), Some(*severity));
+        }
+    }
+
+    #[test]
+    fn test_force_disable_removes_type_from_effective_set() {
+        let role = RolePolicy::new(DetectionMode::Relaxed, Severity::High)
+            .with_override(InjectionType::RolePlayingJailbreak, TypeOverride::ForceDisable);
+        let mut policy = DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        policy.register_role("trusted-partner", role);
+
+        let patterns = get_patterns_for_policy(&policy, "trusted-partner");
+        assert!(!patterns.contains_key(&InjectionType::RolePlayingJailbreak));
+    }
+
+    #[test]
+    fn test_force_enable_adds_type_not_present_in_base_mode() {
+        // Strict mode doesn't include DataExfiltration; force-enable it for
+        // a role that needs extra coverage beyond Strict's core four.
+        let role = RolePolicy::new(DetectionMode::Strict, Severity::Critical).with_override(
+            InjectionType::DataExfiltration,
+            TypeOverride::ForceEnable(Severity::High),
+        );
+        let mut policy =
+            DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        policy.register_role("public-endpoint", role);
+
+        let patterns = get_patterns_for_policy(&policy, "public-endpoint");
+        let (_, severity) = patterns.get(&InjectionType::DataExfiltration).unwrap();
+        assert_eq!(*severity, Severity::High);
+    }
+
+    #[test]
+    fn test_force_enable_is_noop_for_type_with_no_built_in_pattern() {
+        let role = RolePolicy::new(DetectionMode::Strict, Severity::Critical).with_override(
+            InjectionType::Custom("NoSuchRule".to_string()),
+            TypeOverride::ForceEnable(Severity::High),
+        );
+        let mut policy =
+            DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        policy.register_role("role-a", role);
+
+        let patterns = get_patterns_for_policy(&policy, "role-a");
+        assert!(!patterns.contains_key(&InjectionType::Custom("NoSuchRule".to_string())));
+    }
+
+    #[test]
+    fn test_adjust_severity_changes_severity_of_present_type() {
+        let role = RolePolicy::new(DetectionMode::Relaxed, Severity::Critical).with_override(
+            InjectionType::EncodedInstruction,
+            TypeOverride::AdjustSeverity(Severity::Critical),
+        );
+        let mut policy =
+            DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        policy.register_role("strict-tenant", role);
+
+        let patterns = get_patterns_for_policy(&policy, "strict-tenant");
+        let (_, severity) = patterns.get(&InjectionType::EncodedInstruction).unwrap();
+        assert_eq!(*severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_adjust_severity_is_noop_for_type_absent_from_base_mode() {
+        let role = RolePolicy::new(DetectionMode::Strict, Severity::Critical).with_override(
+            InjectionType::MemoryStateAccess,
+            TypeOverride::AdjustSeverity(Severity::Low),
+        );
+        let mut policy =
+            DetectionPolicy::new(RolePolicy::new(DetectionMode::Standard, Severity::High));
+        policy.register_role("role-b", role);
+
+        let patterns = get_patterns_for_policy(&policy, "role-b");
+        assert!(!patterns.contains_key(&InjectionType::MemoryStateAccess));
+    }
+
+    #[test]
+    fn test_should_block_respects_role_threshold() {
+        let strict = RolePolicy::new(DetectionMode::Strict, Severity::Medium);
+        assert!(strict.should_block(Severity::High));
+        assert!(!strict.should_block(Severity::Low));
+    }
+
+    #[test]
+    fn test_with_override_replaces_previous_override_for_same_type() {
+        let role = RolePolicy::new(DetectionMode::Relaxed, Severity::High)
+            .with_override(InjectionType::DANVariant, TypeOverride::ForceDisable)
+            .with_override(InjectionType::DANVariant, TypeOverride::AdjustSeverity(Severity::Low));
+
+        assert_eq!(
+            role.overrides.get(&InjectionType::DANVariant),
+            Some(&TypeOverride::AdjustSeverity(Severity::Low))
+        );
+    }
+}
@@ -0,0 +1,173 @@
+// Per-Pattern Profiling and Hit-Rate Metrics
+//
+// Opt-in self-profiling for `InjectionDetector`: cumulative timing and hit-rate stats per
+// pattern, plus a P50/P95 latency histogram for whole-`detect` calls, so operators can see which
+// patterns are expensive or rarely fire and verify the detector's latency claims on their own
+// traffic.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::injection::types::InjectionType;
+
+/// Cumulative timing and hit-rate stats for one pattern
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PatternStats {
+    /// Number of times this pattern was scanned
+    pub invocations: u64,
+    /// Number of those scans that found at least one match
+    pub hits: u64,
+    /// Cumulative time spent scanning this pattern
+    pub total_scan_time: Duration,
+}
+
+impl PatternStats {
+    /// Fraction of invocations that found at least one match
+    pub fn hit_rate(&self) -> f64 {
+        if self.invocations == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.invocations as f64
+        }
+    }
+
+    /// Mean time spent per invocation
+    pub fn mean_scan_time(&self) -> Duration {
+        if self.invocations == 0 {
+            Duration::ZERO
+        } else {
+            self.total_scan_time / self.invocations as u32
+        }
+    }
+}
+
+/// P50/P95 latency snapshot for whole-`detect` calls
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LatencyPercentiles {
+    /// Median latency across observed `detect` calls
+    pub p50: Duration,
+    /// 95th percentile latency across observed `detect` calls
+    pub p95: Duration,
+    /// Number of samples the percentiles were computed from
+    pub sample_count: usize,
+}
+
+/// Profiling state for a detector: per-pattern stats plus a latency
+/// histogram for whole-`detect` calls
+///
+/// Kept behind a `Mutex` (profiling is opt-in and off the hot path by
+/// default, so lock contention isn't a concern) so the owning detector
+/// stays `Sync`.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    pattern_stats: Mutex<HashMap<InjectionType, PatternStats>>,
+    detect_latencies: Mutex<Vec<Duration>>,
+}
+
+impl Profiler {
+    /// Create a new, empty profiler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one scan of `injection_type`'s pattern
+    pub(crate) fn record_pattern(&self, injection_type: &InjectionType, elapsed: Duration, hit: bool) {
+        let mut stats = self.pattern_stats.lock().unwrap();
+        let entry = stats.entry(injection_type.clone()).or_default();
+        entry.invocations += 1;
+        entry.total_scan_time += elapsed;
+        if hit {
+            entry.hits += 1;
+        }
+    }
+
+    /// Record one whole-`detect` call's latency
+    pub(crate) fn record_detect(&self, elapsed: Duration) {
+        self.detect_latencies.lock().unwrap().push(elapsed);
+    }
+
+    /// Snapshot of per-pattern hit-rate and timing stats
+    pub fn metrics(&self) -> HashMap<InjectionType, PatternStats> {
+        self.pattern_stats.lock().unwrap().clone()
+    }
+
+    /// P50/P95 latency of whole `detect` calls observed so far
+    pub fn detect_latency_percentiles(&self) -> LatencyPercentiles {
+        let mut samples = self.detect_latencies.lock().unwrap().clone();
+        samples.sort();
+
+        LatencyPercentiles {
+            p50: percentile(&samples, 0.50),
+            p95: percentile(&samples, 0.95),
+            sample_count: samples.len(),
+        }
+    }
+}
+
+/// Nearest-rank percentile over an already-sorted sample set
+fn percentile(sorted_samples: &[Duration], p: f64) -> Duration {
+    if sorted_samples.is_empty() {
+        return Duration::ZERO;
+    }
+    let idx = (((sorted_samples.len() - 1) as f64) * p).round() as usize;
+    sorted_samples[idx.min(sorted_samples.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_stats_hit_rate_and_mean_scan_time() {
+        let mut stats = PatternStats::default();
+        stats.invocations = 4;
+        stats.hits = 1;
+        stats.total_scan_time = Duration::from_micros(40);
+
+        assert_eq!(stats.hit_rate(), 0.25);
+        assert_eq!(stats.mean_scan_time(), Duration::from_micros(10));
+    }
+
+    #[test]
+    fn test_pattern_stats_defaults_avoid_division_by_zero() {
+        let stats = PatternStats::default();
+        assert_eq!(stats.hit_rate(), 0.0);
+        assert_eq!(stats.mean_scan_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_profiler_records_pattern_hits_and_misses() {
+        let profiler = Profiler::new();
+        profiler.record_pattern(&InjectionType::DANVariant, Duration::from_micros(5), true);
+        profiler.record_pattern(&InjectionType::DANVariant, Duration::from_micros(7), false);
+
+        let metrics = profiler.metrics();
+        let stats = metrics[&InjectionType::DANVariant];
+        assert_eq!(stats.invocations, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.total_scan_time, Duration::from_micros(12));
+    }
+
+    #[test]
+    fn test_profiler_latency_percentiles() {
+        let profiler = Profiler::new();
+        for ms in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10] {
+            profiler.record_detect(Duration::from_millis(ms));
+        }
+
+        let percentiles = profiler.detect_latency_percentiles();
+        assert_eq!(percentiles.sample_count, 10);
+        assert_eq!(percentiles.p50, Duration::from_millis(6));
+        assert_eq!(percentiles.p95, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn test_profiler_percentiles_empty_is_zero() {
+        let profiler = Profiler::new();
+        let percentiles = profiler.detect_latency_percentiles();
+        assert_eq!(percentiles.sample_count, 0);
+        assert_eq!(percentiles.p50, Duration::ZERO);
+        assert_eq!(percentiles.p95, Duration::ZERO);
+    }
+}
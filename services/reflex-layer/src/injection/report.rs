@@ -0,0 +1,264 @@
+// Structured Detection Reports
+//
+// This module turns raw `InjectionMatch`es into a single serializable report that callers can
+// log as a structured audit record, and which also knows how to sanitize the offending text.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::injection::analyzer::ContextAnalysis;
+use crate::injection::types::{InjectionMatch, Severity};
+
+/// A suggested remediation for a single finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Fix {
+    /// Replace the span with a fixed redaction marker
+    RedactSpan { start: usize, end: usize },
+    /// Wrap the span in quotes so it reads as inert quoted text
+    QuoteSpan { start: usize, end: usize },
+}
+
+impl Fix {
+    fn start(&self) -> usize {
+        match self {
+            Fix::RedactSpan { start, .. } | Fix::QuoteSpan { start, .. } => *start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Fix::RedactSpan { end, .. } | Fix::QuoteSpan { end, .. } => *end,
+        }
+    }
+}
+
+/// One finding in a `DetectionReport`: a match's span and level, a
+/// human-readable message, and a suggested fix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    /// Start byte offset of the flagged span in the original text
+    pub span_start: usize,
+    /// End byte offset of the flagged span in the original text
+    pub span_end: usize,
+    /// Severity level of this finding
+    pub level: Severity,
+    /// Human-readable summary of what was detected
+    pub message: String,
+    /// Suggested remediation for this finding
+    pub fix: Fix,
+}
+
+impl Finding {
+    fn from_match(m: &InjectionMatch) -> Self {
+        let fix = if m.severity >= Severity::High {
+            Fix::RedactSpan {
+                start: m.start,
+                end: m.end,
+            }
+        } else {
+            Fix::QuoteSpan {
+                start: m.start,
+                end: m.end,
+            }
+        };
+
+        Self {
+            span_start: m.start,
+            span_end: m.end,
+            level: m.severity,
+            message: format!(
+                "{} detected ({:.0}% confidence)",
+                m.injection_type,
+                m.confidence * 100.0
+            ),
+            fix,
+        }
+    }
+}
+
+/// Aggregated, serializable detection results plus remediation guidance
+///
+/// Built from a single `InjectionDetector::analyze` call, `DetectionReport`
+/// is the "cook raw findings into a unified representation" counterpart to
+/// `detect`: it's meant to be logged as a structured audit record and/or
+/// passed to `apply_fixes` to emit a sanitized copy of the input in one pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionReport {
+    /// Every finding, one per detected match
+    pub findings: Vec<Finding>,
+    /// Highest severity across all findings, if any were found
+    pub highest_severity: Option<Severity>,
+    /// Number of findings per injection type (keyed by its `Display` form)
+    pub counts_by_type: HashMap<String, usize>,
+    /// Shannon entropy computed over the analyzed text
+    pub entropy: f64,
+    /// Contextual indicators (academic, quoted, negated, ...) for the text
+    pub context: ContextAnalysis,
+}
+
+impl DetectionReport {
+    /// Build a report from a set of matches and the context/entropy that
+    /// were computed alongside them
+    pub fn from_matches(
+        matches: &[InjectionMatch],
+        entropy: f64,
+        context: ContextAnalysis,
+    ) -> Self {
+        let highest_severity = matches.iter().map(|m| m.severity).max();
+
+        let mut counts_by_type: HashMap<String, usize> = HashMap::new();
+        for m in matches {
+            *counts_by_type
+                .entry(m.injection_type.to_string())
+                .or_insert(0) += 1;
+        }
+
+        let findings = matches.iter().map(Finding::from_match).collect();
+
+        Self {
+            findings,
+            highest_severity,
+            counts_by_type,
+            entropy,
+            context,
+        }
+    }
+
+    /// Return a sanitized copy of `text` with every flagged span neutralized
+    ///
+    /// Fixes are applied left-to-right in span order; a fix whose span
+    /// overlaps one already applied is skipped so edits never conflict.
+    pub fn apply_fixes(&self, text: &str) -> String {
+        let mut fixes: Vec<&Fix> = self.findings.iter().map(|f| &f.fix).collect();
+        fixes.sort_by_key(|f| f.start());
+
+        let mut result = String::with_capacity(text.len());
+        let mut cursor = 0;
+
+        for fix in fixes {
+            let (start, end) = (fix.start(), fix.end());
+            if start < cursor || end < start || end > text.len() {
+                continue;
+            }
+
+            result.push_str(&text[cursor..start]);
+            match fix {
+                Fix::RedactSpan { .. } => result.push_str("[REDACTED]"),
+                Fix::QuoteSpan { .. } => {
+                    result.push('"');
+                    result.push_str(&text[start..end]);
+                    result.push('"');
+                }
+            }
+            cursor = end;
+        }
+
+        result.push_str(&text[cursor..]);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::injection::types::InjectionType;
+
+    fn make_match(
+        injection_type: InjectionType,
+        start: usize,
+        end: usize,
+        severity: Severity,
+    ) -> InjectionMatch {
+        InjectionMatch::new(
+            injection_type,
+            start,
+            end,
+            "x".to_string(),
+            severity,
+            0.9,
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_from_matches_aggregates_highest_severity_and_counts() {
+        let matches = vec![
+            make_match(InjectionType::DANVariant, 0, 3, Severity::Critical),
+            make_match(InjectionType::DANVariant, 10, 13, Severity::Critical),
+            make_match(InjectionType::NestedPrompt, 20, 25, Severity::Medium),
+        ];
+        let report = DetectionReport::from_matches(&matches, 2.0, ContextAnalysis::default());
+
+        assert_eq!(report.highest_severity, Some(Severity::Critical));
+        assert_eq!(report.counts_by_type["DANVariant"], 2);
+        assert_eq!(report.counts_by_type["NestedPrompt"], 1);
+        assert_eq!(report.findings.len(), 3);
+    }
+
+    #[test]
+    fn test_from_matches_empty_has_no_highest_severity() {
+        let report = DetectionReport::from_matches(&[], 0.0, ContextAnalysis::default());
+        assert_eq!(report.highest_severity, None);
+        assert!(report.findings.is_empty());
+    }
+
+    #[test]
+    fn test_high_severity_finding_gets_redact_fix() {
+        let matches = vec![make_match(
+            InjectionType::DANVariant,
+            0,
+            3,
+            Severity::Critical,
+        )];
+        let report = DetectionReport::from_matches(&matches, 0.0, ContextAnalysis::default());
+        assert_eq!(
+            report.findings[0].fix,
+            Fix::RedactSpan { start: 0, end: 3 }
+        );
+    }
+
+    #[test]
+    fn test_low_severity_finding_gets_quote_fix() {
+        let matches = vec![make_match(
+            InjectionType::NestedPrompt,
+            0,
+            3,
+            Severity::Medium,
+        )];
+        let report = DetectionReport::from_matches(&matches, 0.0, ContextAnalysis::default());
+        assert_eq!(report.findings[0].fix, Fix::QuoteSpan { start: 0, end: 3 });
+    }
+
+    #[test]
+    fn test_apply_fixes_redacts_and_quotes_disjoint_spans() {
+        let matches = vec![
+            make_match(InjectionType::DANVariant, 0, 3, Severity::Critical),
+            make_match(InjectionType::NestedPrompt, 9, 12, Severity::Medium),
+        ];
+        let report = DetectionReport::from_matches(&matches, 0.0, ContextAnalysis::default());
+        let text = "DAN says hey there";
+
+        let sanitized = report.apply_fixes(text);
+        assert_eq!(sanitized, "[REDACTED] says \"hey\" there");
+    }
+
+    #[test]
+    fn test_apply_fixes_skips_overlapping_span() {
+        let matches = vec![
+            make_match(InjectionType::DANVariant, 0, 10, Severity::Critical),
+            make_match(InjectionType::NestedPrompt, 5, 8, Severity::Medium),
+        ];
+        let report = DetectionReport::from_matches(&matches, 0.0, ContextAnalysis::default());
+        let text = "0123456789 tail";
+
+        let sanitized = report.apply_fixes(text);
+        assert_eq!(sanitized, "[REDACTED] tail");
+    }
+
+    #[test]
+    fn test_apply_fixes_with_no_findings_returns_original_text() {
+        let report = DetectionReport::from_matches(&[], 0.0, ContextAnalysis::default());
+        assert_eq!(report.apply_fixes("unchanged"), "unchanged");
+    }
+}
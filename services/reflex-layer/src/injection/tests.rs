@@ -56,6 +56,7 @@ fn test_full_pipeline_context_aware() {
         enable_context_analysis: true,
         enable_entropy_check: false,
         severity_threshold: Severity::Low,
+        ..Default::default()
     });
 
     // Academic context should reduce severity
@@ -92,6 +93,7 @@ fn test_full_pipeline_encoding_detection() {
         enable_context_analysis: false,
         enable_entropy_check: true,
         severity_threshold: Severity::Low,
+        ..Default::default()
     });
 
     // Base64 encoded text with decode instruction
@@ -177,6 +179,7 @@ fn test_detection_modes_comparison() {
         enable_context_analysis: false,
         enable_entropy_check: false,
         severity_threshold: Severity::Low,
+        ..Default::default()
     });
 
     let standard = InjectionDetector::new(InjectionConfig {
@@ -184,6 +187,7 @@ fn test_detection_modes_comparison() {
         enable_context_analysis: false,
         enable_entropy_check: false,
         severity_threshold: Severity::Low,
+        ..Default::default()
     });
 
     let relaxed = InjectionDetector::new(InjectionConfig {
@@ -191,6 +195,7 @@ fn test_detection_modes_comparison() {
         enable_context_analysis: false,
         enable_entropy_check: false,
         severity_threshold: Severity::Low,
+        ..Default::default()
     });
 
     // Critical injection should be detected in all modes
@@ -36,6 +36,13 @@ pub enum InjectionType {
     DataExfiltration,
     /// Memory/State access attempts
     MemoryStateAccess,
+    /// Synthesized match from several base patterns co-occurring (see `injection::composite`)
+    Composite,
+    /// Injection pattern matched only after decoding an encoded region of the
+    /// input (base64/base32/hex/ROT13/URL-percent-encoding); the underlying
+    /// pattern that fired and the decode chain are recorded in the match's
+    /// `indicators` (see `injection::decode_rescan`)
+    EncodedPayload,
     /// Custom user-defined injection pattern
     Custom(String),
 }
@@ -57,6 +64,8 @@ impl fmt::Display for InjectionType {
             InjectionType::TemplateInjection => write!(f, "TemplateInjection"),
             InjectionType::DataExfiltration => write!(f, "DataExfiltration"),
             InjectionType::MemoryStateAccess => write!(f, "MemoryStateAccess"),
+            InjectionType::Composite => write!(f, "Composite"),
+            InjectionType::EncodedPayload => write!(f, "EncodedPayload"),
             InjectionType::Custom(name) => write!(f, "Custom({})", name),
         }
     }
@@ -173,6 +182,19 @@ pub struct InjectionConfig {
     pub enable_entropy_check: bool,
     /// Minimum severity threshold to report
     pub severity_threshold: Severity,
+    /// Also run patterns against a homoglyph-normalized view of the text, to
+    /// catch Cyrillic/Greek/fullwidth lookalike evasion (e.g. `V1agr@` spoofing
+    /// a blocked word)
+    pub enable_homoglyph_normalization: bool,
+    /// Also run patterns against a further leet-folded view (`1`->`i`, `0`->`o`,
+    /// etc.), on top of homoglyph normalization. Off by default: folding digits
+    /// into letters is much more prone to false positives than homoglyph mapping.
+    pub enable_leet_folding: bool,
+    /// Scan for candidate encoded regions (base64/base32/hex runs,
+    /// ROT13-looking ASCII, URL-percent-encoding), decode each one, and
+    /// rescan the decoded text for injections nested a few layers deep (see
+    /// `injection::decode_rescan`)
+    pub enable_decode_rescan: bool,
 }
 
 impl Default for InjectionConfig {
@@ -182,6 +204,9 @@ impl Default for InjectionConfig {
             enable_context_analysis: true,
             enable_entropy_check: true,
             severity_threshold: Severity::Low,
+            enable_homoglyph_normalization: true,
+            enable_leet_folding: false,
+            enable_decode_rescan: true,
         }
     }
 }
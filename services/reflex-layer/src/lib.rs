@@ -8,20 +8,51 @@ pub mod cache;
 pub mod config;
 pub mod error;
 pub mod injection;
+pub mod normalize;
 pub mod pii;
 pub mod ratelimit;
 pub mod redis_client;
+pub mod update;
+pub mod validation;
 
 // Re-export commonly used items
-pub use cache::{generate_cache_key, Cache, CacheError, CacheStats, CacheTTL, RedisCache};
-pub use config::{Config, RedisConfig};
+pub use cache::{
+    generate_cache_key, Cache, CacheBackend, CacheCrypto, CacheError, CacheInvalidator,
+    CacheStats, CacheStatsSnapshot, CacheTTL, HyperLogLog, InMemoryCache, InvalidationCallback,
+    RedisCache, ShardedLruCache, TwoTierCache, INVALIDATION_CHANNEL,
+};
+#[cfg(feature = "mocks")]
+pub use cache::MockCache;
+pub use config::{Config, RedisConfig, RedisUsecaseConfig};
 pub use error::{ApiError, ReflexError, ReflexResult};
+pub use normalize::{fold_leet, normalize_homoglyphs, NormalizedText};
 pub use injection::{
-    DetectionMode, InjectionConfig, InjectionDetector, InjectionMatch, InjectionType, Severity,
+    detect_stream, expand_fragments, fragment, get_patterns_for_policy, rescan_encoded_regions,
+    BuilderError, CombinedPattern, DetectionContext, DetectionMode, DetectionModule,
+    DetectionPolicy, DetectionReport, EscalatingDetector, EscalationTier, Finding, Fix,
+    InjectionConfig, InjectionConfigBuilder, InjectionDetector, InjectionMatch,
+    InjectionPatternDefinition, InjectionType, LatencyPercentiles, ModuleRegistry, PatternKind,
+    PatternPackModule, PatternStats, Profiler, RolePolicy, Severity, SharedModuleRegistry,
+    StreamVerdict, StreamingDetector, TypeOverride, VerdictCache, INSTRUCTION_NOUN, MODIFIER,
+    ROLE_VERB,
+};
+pub use pii::{
+    redact, redact_per_match, redact_reversible, redact_with_context, redact_with_policy,
+    restore, CryptoConfig, CustomPatternMetadata, EvalContext, PIIConfig, PIIDetector, PIIMatch,
+    PIIType, PatternDefinition, PatternRegistry, PatternRegistryError, PatternSet, PolicyError,
+    RedactedPIIMatch, RedactionContext, RedactionPolicy, RedactionStrategy, RedactionVault,
+    SecretPIIMatch, Validator, ValidatorRegistry,
 };
-pub use pii::{redact, PIIConfig, PIIDetector, PIIMatch, PIIType, PatternSet, RedactionStrategy};
 pub use ratelimit::{
-    MultiDimensionalRateLimiter, RateLimitConfig, RateLimitError, RateLimitKey, RateLimitResult,
-    RateLimitTier, RedisRateLimiter, TokenBucket,
+    ApiKeyTierTable, CircuitBreakerRateLimiter, Clock, ConcurrencyLimiter, ConcurrencyPermit,
+    DeferredRateLimiter, DeferredResult, FakeClock, InMemoryRateLimiter,
+    MultiDimensionalRateLimiter, NoopMetricsSink, RateLimitBackend, RateLimitConfig,
+    RateLimitError, RateLimitKey, RateLimitMetricsSink, RateLimitResult, RateLimitTier,
+    RateLimiterMode, RedisRateLimiter, RedisTierResolver, StaticTierResolver, SystemClock,
+    TierConfigTable, TierResolver, TokenBucket, TokenBucketRegistry, TokenType, parse_rate_window,
+};
+pub use redis_client::{
+    is_retryable_redis_error, retry_redis_command, PoolStatus, RedisClient, RedisClientSet,
+    RedisDeploymentMode,
 };
-pub use redis_client::RedisClient;
+pub use update::{SignedBundle, TrustRoot, TrustedKey, UpdateError};
@@ -5,7 +5,9 @@
 mod handlers;
 mod metrics;
 mod middleware;
+mod telemetry;
 
+use arc_swap::ArcSwap;
 use axum::{
     extract::State,
     http::StatusCode,
@@ -17,6 +19,7 @@ use axum::{
 use prometheus::TextEncoder;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 use tower_http::{
     cors::CorsLayer,
@@ -25,26 +28,197 @@ use tower_http::{
 use tracing::Level;
 
 use reflex_layer::{
-    cache::RedisCache,
-    config::Config,
+    cache::{Cache, CacheInvalidator, CacheTTL, InMemoryCache, RedisCache, TwoTierCache},
+    config::{Config, RateLimitConfig as RateLimitSettings, SecurityConfig},
     error::{ReflexError, ReflexResult},
-    injection::{DetectionMode, InjectionConfig, InjectionDetector, Severity},
-    pii::{PIIConfig, PIIDetector, PatternSet},
-    ratelimit::RedisRateLimiter,
-    redis_client::RedisClient,
+    injection::{InjectionConfig, InjectionDetector, VerdictCache},
+    pii::{PIIConfig, PIIDetector, RedactionPolicy},
+    ratelimit::{
+        ApiKeyTierTable, CircuitBreakerRateLimiter, InMemoryRateLimiter, RateLimitBackend,
+        RedisRateLimiter, TierConfigTable,
+    },
+    redis_client::RedisClientSet,
 };
 
 /// Application state shared across all handlers
 pub struct AppState {
-    pub config: Arc<Config>,
-    pub redis: RedisClient,
-    pub pii_detector: Arc<PIIDetector>,
-    pub injection_detector: Arc<InjectionDetector>,
-    pub cache: Arc<RedisCache>,
-    pub rate_limiter: Arc<RedisRateLimiter>,
+    pub config: ArcSwap<Config>,
+    pub redis: RedisClientSet,
+    pub pii_detector: ArcSwap<PIIDetector>,
+    pub injection_detector: ArcSwap<InjectionDetector>,
+    /// Per-match redaction strategy selection, hot-reloadable alongside the
+    /// detectors
+    pub redaction_policy: ArcSwap<RedactionPolicy>,
+    /// Request-processing cache; a two-tier Redis-backed cache when Redis is
+    /// reachable at startup, or an in-memory fallback when it isn't
+    pub cache: Arc<dyn Cache>,
+    /// The same two-tier cache as `cache`, kept concrete so `/metrics` and
+    /// shutdown can reach its L1-specific snapshot/stats methods; `None`
+    /// when running on the in-memory fallback (nothing to snapshot)
+    pub two_tier_cache: Option<Arc<TwoTierCache>>,
+    /// Sharded cache of `InjectionDetector::detect` verdicts, consulted
+    /// before and populated after every `/process` injection check
+    pub verdict_cache: Arc<VerdictCache>,
+    pub rate_limiter: Arc<dyn RateLimitBackend>,
+    /// Per-tier rate-limit configs, seeded from `config.rate_limit`'s
+    /// human-readable windows
+    pub tier_config_table: Arc<TierConfigTable>,
+    /// `x-api-key` header -> rate-limit tier lookup, built from
+    /// `config.rate_limit.api_keys`
+    pub api_key_tiers: Arc<ApiKeyTierTable>,
     pub start_time: std::time::Instant,
 }
 
+/// Build a `PIIConfig` from the current security settings
+fn build_pii_config(security: &SecurityConfig) -> PIIConfig {
+    PIIConfig {
+        pattern_set: security.pii_pattern_set(),
+        enable_validation: security.enable_validation,
+        enable_context: false,
+        ..Default::default()
+    }
+}
+
+/// Build an `InjectionConfig` from the current security settings
+fn build_injection_config(security: &SecurityConfig) -> InjectionConfig {
+    InjectionConfig {
+        detection_mode: security.injection_detection_mode(),
+        enable_context_analysis: true,
+        enable_entropy_check: true,
+        severity_threshold: security.injection_severity_threshold(),
+        ..Default::default()
+    }
+}
+
+/// Build a `RedactionPolicy` from the current security settings, falling
+/// back to an always-`default_redaction_strategy` policy if the configured
+/// rules fail to parse (logged, so a typo in an env var degrades gracefully
+/// instead of failing startup)
+fn build_redaction_policy(security: &SecurityConfig) -> RedactionPolicy {
+    security.redaction_policy().unwrap_or_else(|e| {
+        tracing::warn!(
+            "Failed to parse redaction_policy_rules, falling back to {:?} for all matches: {}",
+            security.default_redaction_strategy(),
+            e
+        );
+        RedactionPolicy::new(security.default_redaction_strategy())
+    })
+}
+
+/// Build the per-tier rate-limit config table from `rate_limit`'s
+/// human-readable windows, falling back to every tier's compiled-in
+/// default if a window fails to parse (logged, so a typo in an env var
+/// degrades gracefully instead of failing startup)
+fn build_tier_config_table(rate_limit: &RateLimitSettings) -> TierConfigTable {
+    rate_limit.tier_config_table().unwrap_or_else(|e| {
+        tracing::warn!(
+            "Failed to parse rate_limit tier windows, falling back to compiled-in tier defaults: {}",
+            e
+        );
+        TierConfigTable::new()
+    })
+}
+
+/// Build the `x-api-key` -> tier lookup from `rate_limit.api_keys`,
+/// falling back to an empty table (every key resolves to Free) if the rule
+/// string fails to parse
+fn build_api_key_tiers(rate_limit: &RateLimitSettings) -> ApiKeyTierTable {
+    rate_limit.api_key_tier_table().unwrap_or_else(|e| {
+        tracing::warn!(
+            "Failed to parse rate_limit.api_keys, no API key will receive an elevated tier: {}",
+            e
+        );
+        ApiKeyTierTable::default()
+    })
+}
+
+/// Per-field diff between two `SecurityConfig`s, reported back to the caller
+/// of `/reload` so operators can see exactly what changed
+#[derive(Debug, Serialize, Deserialize)]
+struct ReloadReport {
+    reloaded: bool,
+    changed_fields: Vec<String>,
+}
+
+/// Re-read configuration from the environment and, if the security section
+/// changed, atomically swap in freshly built detectors and the new config.
+///
+/// Returns an error (without swapping anything) if the new configuration
+/// fails to load, so a bad reload leaves the running config intact.
+async fn reload_config(state: &AppState) -> ReflexResult<ReloadReport> {
+    let new_config = Config::from_env().map_err(|e| ReflexError::Config(e.to_string()))?;
+    let old_config = state.config.load();
+
+    let changed_fields = diff_security_config(&old_config.security, &new_config.security);
+    if changed_fields.is_empty() {
+        return Ok(ReloadReport {
+            reloaded: false,
+            changed_fields,
+        });
+    }
+
+    state
+        .pii_detector
+        .store(Arc::new(PIIDetector::new(build_pii_config(
+            &new_config.security,
+        ))));
+    state
+        .injection_detector
+        .store(Arc::new(InjectionDetector::new(build_injection_config(
+            &new_config.security,
+        ))));
+    state
+        .redaction_policy
+        .store(Arc::new(build_redaction_policy(&new_config.security)));
+    state.config.store(Arc::new(new_config));
+
+    Ok(ReloadReport {
+        reloaded: true,
+        changed_fields,
+    })
+}
+
+/// Names of the `SecurityConfig` fields that differ between `old` and `new`
+fn diff_security_config(old: &SecurityConfig, new: &SecurityConfig) -> Vec<String> {
+    let mut changed = Vec::new();
+
+    if old.enable_pii_detection != new.enable_pii_detection {
+        changed.push("enable_pii_detection".to_string());
+    }
+    if old.enable_injection_detection != new.enable_injection_detection {
+        changed.push("enable_injection_detection".to_string());
+    }
+    if old.block_on_high_risk != new.block_on_high_risk {
+        changed.push("block_on_high_risk".to_string());
+    }
+    if old.alert_on_critical != new.alert_on_critical {
+        changed.push("alert_on_critical".to_string());
+    }
+    if old.max_query_length != new.max_query_length {
+        changed.push("max_query_length".to_string());
+    }
+    if old.pattern_set != new.pattern_set {
+        changed.push("pattern_set".to_string());
+    }
+    if old.enable_validation != new.enable_validation {
+        changed.push("enable_validation".to_string());
+    }
+    if old.detection_mode != new.detection_mode {
+        changed.push("detection_mode".to_string());
+    }
+    if old.severity_threshold != new.severity_threshold {
+        changed.push("severity_threshold".to_string());
+    }
+    if old.redaction_policy_rules != new.redaction_policy_rules {
+        changed.push("redaction_policy_rules".to_string());
+    }
+    if old.default_redaction_strategy != new.default_redaction_strategy {
+        changed.push("default_redaction_strategy".to_string());
+    }
+
+    changed
+}
+
 /// Health check response
 #[derive(Debug, Serialize, Deserialize)]
 struct HealthResponse {
@@ -92,6 +266,9 @@ async fn main() -> ReflexResult<()> {
             .init(),
     }
 
+    // Select the error response envelope `IntoResponse` impls serialize into
+    reflex_layer::error::set_error_format(config.server.error_format());
+
     tracing::info!(
         "Starting Reflex Layer v{} on {}:{}",
         env!("CARGO_PKG_VERSION"),
@@ -99,72 +276,241 @@ async fn main() -> ReflexResult<()> {
         config.server.port
     );
 
-    // Create Redis client (wrapped in Arc for sharing)
-    let redis_client = Arc::new(RedisClient::new(config.redis.clone()).map_err(|e| {
-        ReflexError::Redis(redis::RedisError::from((
-            redis::ErrorKind::IoError,
-            "Failed to create Redis client",
-            e.to_string(),
-        )))
-    })?);
-    tracing::info!("Redis client initialized");
-
-    // Verify Redis connectivity
-    match redis_client.health_check().await {
-        Ok(_) => tracing::info!("Redis connection verified"),
+    // Create the Redis client set: one pool per cache usecase (PII,
+    // injection, general reflex caching, ...), each falling back to the
+    // default `redis` config for any field it doesn't override.
+    let redis_set = Arc::new(
+        RedisClientSet::new(config.redis.clone(), &config.redis_usecases).map_err(|e| {
+            ReflexError::Redis(redis::RedisError::from((
+                redis::ErrorKind::IoError,
+                "Failed to create Redis client set",
+                e.to_string(),
+            )))
+        })?,
+    );
+    tracing::info!(
+        "Redis client set initialized with {} usecase-specific pool(s)",
+        config.redis_usecases.len()
+    );
+
+    // Verify Redis connectivity; a failed check degrades the cache and rate
+    // limiter to in-memory backends below instead of half-working against a
+    // Redis connection that was never actually there.
+    let redis_available = match redis_set.health_check("reflex").await {
+        Ok(_) => {
+            tracing::info!("Redis connection verified");
+            true
+        }
         Err(e) => {
-            tracing::warn!("Redis health check failed: {}. Continuing without Redis", e);
-            // Note: In production, you might want to fail fast here
+            tracing::warn!(
+                "Redis health check failed: {}. Falling back to in-memory cache and rate limiter",
+                e
+            );
+            false
         }
-    }
+    };
 
     // Initialize PII detector
-    let pii_config = PIIConfig {
-        pattern_set: PatternSet::Standard,
-        enable_validation: true,
-        enable_context: false,
-    };
-    let pii_detector = Arc::new(PIIDetector::new(pii_config));
-    tracing::info!("PII detector initialized with Standard pattern set");
+    let pii_detector = Arc::new(PIIDetector::new(build_pii_config(&config.security)));
+    tracing::info!(
+        "PII detector initialized with {:?} pattern set",
+        config.security.pii_pattern_set()
+    );
 
     // Initialize Injection detector
-    let injection_config = InjectionConfig {
-        detection_mode: DetectionMode::Standard,
-        enable_context_analysis: true,
-        enable_entropy_check: true,
-        severity_threshold: Severity::Low,
+    let injection_detector = Arc::new(InjectionDetector::new(build_injection_config(
+        &config.security,
+    )));
+    tracing::info!(
+        "Injection detector initialized with {:?} detection mode",
+        config.security.injection_detection_mode()
+    );
+
+    // Initialize the redaction policy
+    let redaction_policy = Arc::new(build_redaction_policy(&config.security));
+    tracing::info!(
+        "Redaction policy initialized with default strategy {:?}",
+        config.security.default_redaction_strategy()
+    );
+
+    // Initialize the cache: the two-tier Redis-backed cache (sharded L1 LRU
+    // in front of Redis/L2) when Redis is reachable, or a dependency-free
+    // in-memory cache when it isn't.
+    let (cache, two_tier_cache): (Arc<dyn Cache>, Option<Arc<TwoTierCache>>) = if redis_available {
+        let mut redis_cache = if let Some(crypto) = config.performance.cache_crypto() {
+            tracing::info!("Cache at-rest encryption enabled");
+            RedisCache::with_crypto(Arc::new(redis_set.client("reflex").clone()), crypto)
+        } else {
+            RedisCache::new(Arc::new(redis_set.client("reflex").clone()))
+        };
+        telemetry::register_cache_metrics(redis_cache.stats_ref());
+
+        let invalidator = if config.redis.enable_invalidation_pubsub {
+            let invalidator = Arc::new(CacheInvalidator::new(Arc::new(
+                redis_set.client("reflex").clone(),
+            )));
+            invalidator.spawn_listener();
+            redis_cache = redis_cache.with_invalidator(Arc::clone(&invalidator));
+            tracing::info!("Cross-instance cache invalidation Pub/Sub enabled");
+            Some(invalidator)
+        } else {
+            None
+        };
+
+        let mut two_tier_cache = TwoTierCache::new(
+            redis_cache,
+            config.l1_cache.shard_count,
+            config.l1_cache.shard_capacity,
+        );
+        if let Some(invalidator) = &invalidator {
+            two_tier_cache = two_tier_cache.with_invalidator(invalidator);
+        }
+        if let Some(snapshot_path) = config.l1_cache.snapshot_path() {
+            match two_tier_cache.load_l1_snapshot(snapshot_path, config.l1_cache.shard_capacity) {
+                Ok(_) => tracing::info!("L1 cache warmed from snapshot at {}", snapshot_path),
+                Err(e) => tracing::warn!("Failed to warm L1 cache from {}: {}", snapshot_path, e),
+            }
+        }
+        let two_tier_cache = Arc::new(two_tier_cache);
+        tracing::info!(
+            "Two-tier cache initialized ({} L1 shards x {} entries in front of Redis)",
+            config.l1_cache.shard_count,
+            config.l1_cache.shard_capacity
+        );
+        (two_tier_cache.clone(), Some(two_tier_cache))
+    } else {
+        tracing::warn!("Redis unavailable; using in-memory cache (no cross-instance sharing)");
+        (Arc::new(InMemoryCache::new()), None)
     };
-    let injection_detector = Arc::new(InjectionDetector::new(injection_config));
-    tracing::info!("Injection detector initialized with Standard detection mode");
 
-    // Initialize Redis cache
-    let cache = Arc::new(RedisCache::new(redis_client.clone()));
-    tracing::info!("Redis cache initialized");
+    // Initialize the rate limiter: distributed Redis-backed (behind a circuit
+    // breaker that falls back to a local in-memory bucket on a Redis outage)
+    // when Redis is reachable at startup, or the local bucket directly when
+    // it isn't.
+    let rate_limiter: Arc<dyn RateLimitBackend> = if redis_available {
+        tracing::info!("Redis rate limiter initialized (circuit breaker armed)");
+        let redis_limiter = Arc::new(RedisRateLimiter::new(Arc::new(
+            redis_set.client("misc").clone(),
+        )));
+        let breaker = Arc::new(CircuitBreakerRateLimiter::new(redis_limiter));
+        // Adversarial key churn (e.g. IPv6 address rotation) would otherwise
+        // grow the breaker's local fallback map unbounded; sweep out idle,
+        // fully-refilled buckets.
+        breaker
+            .fallback()
+            .clone()
+            .spawn_cleanup_task(Duration::from_secs(60));
+        breaker
+    } else {
+        tracing::warn!("Redis unavailable; using in-memory rate limiter (not distributed)");
+        let in_memory = Arc::new(InMemoryRateLimiter::new());
+        // Adversarial key churn (e.g. IPv6 address rotation) would otherwise
+        // grow this map unbounded; sweep out idle, fully-refilled buckets.
+        in_memory.clone().spawn_cleanup_task(Duration::from_secs(60));
+        in_memory
+    };
 
-    // Initialize Redis rate limiter
-    let rate_limiter = Arc::new(RedisRateLimiter::new(redis_client.clone()));
-    tracing::info!("Redis rate limiter initialized");
+    // Initialize the verdict cache sitting in front of InjectionDetector::detect
+    let verdict_cache = {
+        let shard_count = config.performance.verdict_cache_shard_count;
+        let shard_capacity = config.performance.verdict_cache_shard_capacity;
+        let default_ttl = CacheTTL::Custom(config.performance.verdict_cache_ttl_secs);
+
+        let verdict_cache = match config.performance.verdict_cache_snapshot_path() {
+            Some(snapshot_path) => {
+                match VerdictCache::load_snapshot(snapshot_path, shard_count, shard_capacity, default_ttl) {
+                    Ok(cache) => {
+                        tracing::info!("Verdict cache warmed from snapshot at {}", snapshot_path);
+                        cache
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to warm verdict cache from {}: {}",
+                            snapshot_path,
+                            e
+                        );
+                        VerdictCache::new(shard_count, shard_capacity, default_ttl)
+                    }
+                }
+            }
+            None => VerdictCache::new(shard_count, shard_capacity, default_ttl),
+        };
+        Arc::new(verdict_cache)
+    };
+    tracing::info!(
+        "Verdict cache initialized ({} shards x {} entries)",
+        config.performance.verdict_cache_shard_count,
+        config.performance.verdict_cache_shard_capacity
+    );
+
+    // Initialize API-key-driven rate-limit tiers
+    let tier_config_table = Arc::new(build_tier_config_table(&config.rate_limit));
+    let api_key_tiers = Arc::new(build_api_key_tiers(&config.rate_limit));
+    tracing::info!(
+        "Rate-limit tiers initialized ({} API key(s) mapped to a non-Free tier)",
+        config.rate_limit.api_keys.split(';').filter(|s| !s.trim().is_empty()).count()
+    );
 
     // Create application state
     let state = Arc::new(AppState {
-        config: Arc::new(config.clone()),
-        redis: (*redis_client).clone(), // Clone the inner RedisClient
-        pii_detector,
-        injection_detector,
+        config: ArcSwap::new(Arc::new(config.clone())),
+        redis: (*redis_set).clone(), // Clone the inner RedisClientSet
+        pii_detector: ArcSwap::new(pii_detector),
+        injection_detector: ArcSwap::new(injection_detector),
+        redaction_policy: ArcSwap::new(redaction_policy),
         cache,
+        two_tier_cache,
+        verdict_cache,
         rate_limiter,
+        tier_config_table,
+        api_key_tiers,
         start_time: std::time::Instant::now(),
     });
 
+    // Reload detectors and config on SIGHUP, reusing the same signal-handling
+    // idiom as `shutdown_signal`, so operators can change pattern sets and
+    // detection modes without restarting the process
+    #[cfg(unix)]
+    {
+        let reload_state = state.clone();
+        tokio::spawn(async move {
+            let mut sighup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+                Ok(sighup) => sighup,
+                Err(e) => {
+                    tracing::error!("Failed to install SIGHUP handler: {}", e);
+                    return;
+                }
+            };
+
+            loop {
+                sighup.recv().await;
+                tracing::info!("Received SIGHUP, reloading configuration");
+                match reload_config(&reload_state).await {
+                    Ok(report) if report.reloaded => {
+                        tracing::info!("Configuration reloaded: {:?}", report.changed_fields)
+                    }
+                    Ok(_) => tracing::info!("Configuration reload: no changes detected"),
+                    Err(e) => tracing::error!("Configuration reload failed: {}", e),
+                }
+            }
+        });
+    }
+
     // Build router with middleware
     let app = Router::new()
-        // Main processing endpoint
-        .route("/process", post(handlers::process_text))
+        // Main processing endpoint (POST with a JSON body, or GET with a
+        // query string for callers that can't issue a JSON POST body)
+        .route(
+            "/process",
+            post(handlers::process_text).get(handlers::process_text_query),
+        )
         // Health and readiness endpoints
         .route("/health", get(health_handler))
         .route("/ready", get(readiness_handler))
         // Metrics endpoint
         .route("/metrics", get(metrics_handler))
+        // Admin endpoint to hot-reload detectors and security config
+        .route("/reload", post(reload_handler))
         .with_state(state.clone())
         // Middleware stack (applied in reverse order)
         .layer(axum_middleware::from_fn(middleware::metrics_middleware))
@@ -192,6 +538,26 @@ async fn main() -> ReflexResult<()> {
         .await
         .map_err(|e| ReflexError::Internal(format!("Server error: {}", e)))?;
 
+    if let Some(two_tier_cache) = &state.two_tier_cache {
+        if let Some(snapshot_path) = state.config.load().l1_cache.snapshot_path() {
+            match two_tier_cache.save_l1_snapshot(snapshot_path) {
+                Ok(_) => tracing::info!("L1 cache snapshotted to {}", snapshot_path),
+                Err(e) => tracing::warn!("Failed to snapshot L1 cache to {}: {}", snapshot_path, e),
+            }
+        }
+    }
+
+    if let Some(snapshot_path) = state.config.load().performance.verdict_cache_snapshot_path() {
+        match state.verdict_cache.save_snapshot(snapshot_path) {
+            Ok(_) => tracing::info!("Verdict cache snapshotted to {}", snapshot_path),
+            Err(e) => tracing::warn!(
+                "Failed to snapshot verdict cache to {}: {}",
+                snapshot_path,
+                e
+            ),
+        }
+    }
+
     tracing::info!("Reflex layer shutdown complete");
     Ok(())
 }
@@ -215,8 +581,9 @@ async fn health_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse
 ///
 /// Returns service readiness status including dependency checks.
 async fn readiness_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    // Check Redis connectivity
-    let redis_ready = match state.redis.health_check().await {
+    // Check Redis connectivity (general reflex cache pool, representative of
+    // overall Redis reachability)
+    let redis_ready = match state.redis.health_check("reflex").await {
         Ok(_) => true,
         Err(e) => {
             tracing::warn!("Redis readiness check failed: {}", e);
@@ -249,7 +616,12 @@ async fn readiness_handler(State(state): State<Arc<AppState>>) -> impl IntoRespo
 /// Metrics endpoint
 ///
 /// Returns Prometheus-compatible metrics.
-async fn metrics_handler(State(_state): State<Arc<AppState>>) -> impl IntoResponse {
+async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    if let Some(two_tier_cache) = &state.two_tier_cache {
+        metrics::record_l1_cache_stats(&two_tier_cache.l1_shard_stats());
+    }
+    metrics::publish_rate_limited_unique_clients();
+
     let encoder = TextEncoder::new();
     let metric_families = prometheus::gather();
 
@@ -265,6 +637,30 @@ async fn metrics_handler(State(_state): State<Arc<AppState>>) -> impl IntoRespon
     }
 }
 
+/// Admin endpoint to hot-reload detectors and security configuration
+///
+/// Re-reads configuration from the environment, rebuilds the PII and
+/// injection detectors if the security section changed, and atomically
+/// swaps them in without dropping in-flight requests. Returns a JSON diff
+/// of which fields changed; a failed reload leaves the running config
+/// untouched.
+async fn reload_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    match reload_config(&state).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => {
+            tracing::error!("Configuration reload failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ReloadReport {
+                    reloaded: false,
+                    changed_fields: Vec::new(),
+                }),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Parse log level string to tracing Level
 fn parse_log_level(level: &str) -> Level {
     match level.to_lowercase().as_str() {
@@ -321,4 +717,91 @@ mod tests {
         assert!(matches!(parse_log_level("error"), Level::ERROR));
         assert!(matches!(parse_log_level("invalid"), Level::INFO));
     }
+
+    fn test_security_config() -> SecurityConfig {
+        SecurityConfig {
+            enable_pii_detection: true,
+            enable_injection_detection: true,
+            block_on_high_risk: true,
+            alert_on_critical: true,
+            max_query_length: 10000,
+            pattern_set: "standard".to_string(),
+            enable_validation: true,
+            detection_mode: "standard".to_string(),
+            severity_threshold: "low".to_string(),
+            redaction_policy_rules: "".to_string(),
+            default_redaction_strategy: "mask".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_pii_config_reflects_security_settings() {
+        let mut security = test_security_config();
+        security.pattern_set = "strict".to_string();
+        security.enable_validation = false;
+
+        let pii_config = build_pii_config(&security);
+        assert_eq!(pii_config.pattern_set, reflex_layer::PatternSet::Strict);
+        assert!(!pii_config.enable_validation);
+    }
+
+    #[test]
+    fn test_build_injection_config_reflects_security_settings() {
+        let mut security = test_security_config();
+        security.detection_mode = "relaxed".to_string();
+        security.severity_threshold = "high".to_string();
+
+        let injection_config = build_injection_config(&security);
+        assert_eq!(
+            injection_config.detection_mode,
+            reflex_layer::DetectionMode::Relaxed
+        );
+        assert_eq!(
+            injection_config.severity_threshold,
+            reflex_layer::Severity::High
+        );
+    }
+
+    #[test]
+    fn test_diff_security_config_detects_changed_fields() {
+        let old = test_security_config();
+        let mut new = test_security_config();
+        new.detection_mode = "strict".to_string();
+        new.max_query_length = 20000;
+
+        let changed = diff_security_config(&old, &new);
+        assert_eq!(changed, vec!["max_query_length", "detection_mode"]);
+    }
+
+    #[test]
+    fn test_diff_security_config_no_changes_is_empty() {
+        let old = test_security_config();
+        let new = test_security_config();
+
+        assert!(diff_security_config(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_security_config_detects_redaction_policy_changes() {
+        let old = test_security_config();
+        let mut new = test_security_config();
+        new.redaction_policy_rules = r#"pii.type == "SSN" => hash"#.to_string();
+        new.default_redaction_strategy = "token".to_string();
+
+        let changed = diff_security_config(&old, &new);
+        assert_eq!(
+            changed,
+            vec!["redaction_policy_rules", "default_redaction_strategy"]
+        );
+    }
+
+    #[test]
+    fn test_build_redaction_policy_falls_back_on_parse_failure() {
+        let mut security = test_security_config();
+        security.redaction_policy_rules = "not a valid rule".to_string();
+
+        // Malformed rules shouldn't panic; they degrade to the configured
+        // default strategy for every match.
+        let _policy = build_redaction_policy(&security);
+    }
 }
@@ -5,9 +5,10 @@
 
 use lazy_static::lazy_static;
 use prometheus::{
-    register_histogram_vec, register_int_counter, register_int_counter_vec, HistogramVec,
-    IntCounter, IntCounterVec,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
+use reflex_layer::cache::{CacheStatsSnapshot, HyperLogLog};
 
 lazy_static! {
     /// Total HTTP requests by method and path
@@ -114,6 +115,35 @@ lazy_static! {
         "Total number of requests blocked due to critical injection"
     )
     .unwrap();
+
+    /// L1 (in-process sharded LRU) cache hits by shard, refreshed at scrape time
+    pub static ref L1_CACHE_HITS: IntGaugeVec = register_int_gauge_vec!(
+        "reflex_l1_cache_hits_total",
+        "Total number of L1 in-memory cache hits by shard",
+        &["shard"]
+    )
+    .unwrap();
+
+    /// L1 (in-process sharded LRU) cache misses by shard, refreshed at scrape time
+    pub static ref L1_CACHE_MISSES: IntGaugeVec = register_int_gauge_vec!(
+        "reflex_l1_cache_misses_total",
+        "Total number of L1 in-memory cache misses by shard",
+        &["shard"]
+    )
+    .unwrap();
+
+    /// Approximate number of distinct clients that hit a rate limit during
+    /// the current scrape window, refreshed (and reset) at scrape time
+    pub static ref RATE_LIMITED_UNIQUE_CLIENTS: IntGauge = register_int_gauge!(
+        "reflex_rate_limited_unique_clients",
+        "Approximate cardinality of distinct clients rate-limited since the last scrape"
+    )
+    .unwrap();
+
+    /// Accumulates rate-limited client keys between scrapes; not a
+    /// Prometheus metric itself, just the estimator behind
+    /// [`RATE_LIMITED_UNIQUE_CLIENTS`]
+    static ref RATE_LIMITED_CLIENTS_HLL: HyperLogLog = HyperLogLog::new();
 }
 
 /// Register custom metrics for specific operations
@@ -181,6 +211,38 @@ pub fn record_request_blocked() {
     REQUESTS_BLOCKED.inc();
 }
 
+pub fn record_rate_limited_client(key: &str) {
+    RATE_LIMITED_CLIENTS_HLL.record(key);
+}
+
+/// Publish the current scrape window's unique-rate-limited-clients
+/// estimate into [`RATE_LIMITED_UNIQUE_CLIENTS`], then reset the estimator
+/// so the next window starts from zero
+///
+/// High-cardinality labels (one per client) aren't viable in Prometheus, so
+/// this folds the whole window down to a single approximate count instead.
+pub fn publish_rate_limited_unique_clients() {
+    RATE_LIMITED_UNIQUE_CLIENTS.set(RATE_LIMITED_CLIENTS_HLL.estimate() as i64);
+    RATE_LIMITED_CLIENTS_HLL.reset();
+}
+
+/// Refresh the L1 cache hit/miss gauges from a live shard stats snapshot
+///
+/// Counters are cumulative, but since the L1 cache itself only exposes a
+/// point-in-time snapshot, gauges (set, not incremented) are the correct
+/// Prometheus type here.
+pub fn record_l1_cache_stats(shard_stats: &[CacheStatsSnapshot]) {
+    for (shard, stats) in shard_stats.iter().enumerate() {
+        let shard_label = shard.to_string();
+        L1_CACHE_HITS
+            .with_label_values(&[&shard_label])
+            .set(stats.hits as i64);
+        L1_CACHE_MISSES
+            .with_label_values(&[&shard_label])
+            .set(stats.misses as i64);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +275,36 @@ mod tests {
         assert!(RATE_LIMIT_ALLOWED.get() > 0);
         assert!(REQUESTS_BLOCKED.get() > 0);
     }
+
+    #[test]
+    fn test_record_l1_cache_stats() {
+        let shard_stats = vec![CacheStatsSnapshot {
+            hits: 5,
+            misses: 2,
+            sets: 3,
+            deletes: 0,
+            errors: 0,
+            hit_rate: 5.0 / 7.0,
+            miss_rate: 2.0 / 7.0,
+        }];
+
+        record_l1_cache_stats(&shard_stats);
+
+        assert_eq!(L1_CACHE_HITS.with_label_values(&["0"]).get(), 5);
+        assert_eq!(L1_CACHE_MISSES.with_label_values(&["0"]).get(), 2);
+    }
+
+    #[test]
+    fn test_publish_rate_limited_unique_clients_estimates_and_resets() {
+        record_rate_limited_client("user:1");
+        record_rate_limited_client("user:2");
+        record_rate_limited_client("user:1"); // repeat: shouldn't inflate the estimate
+
+        publish_rate_limited_unique_clients();
+        assert_eq!(RATE_LIMITED_UNIQUE_CLIENTS.get(), 2);
+
+        // The estimator was reset, so the next window starts from zero.
+        publish_rate_limited_unique_clients();
+        assert_eq!(RATE_LIMITED_UNIQUE_CLIENTS.get(), 0);
+    }
 }
@@ -3,7 +3,9 @@
 //! Provides request ID generation, logging, and metrics collection middleware.
 
 use axum::{extract::Request, http::header::HeaderValue, middleware::Next, response::Response};
+use reflex_layer::error::REQUEST_ID;
 use std::time::Instant;
+use tracing::Instrument;
 use uuid::Uuid;
 
 /// Request ID header name
@@ -12,7 +14,12 @@ pub const REQUEST_ID_HEADER: &str = "X-Request-ID";
 /// Middleware that adds a unique request ID to each request and response
 ///
 /// If the client provides an X-Request-ID header, it will be preserved.
-/// Otherwise, a new UUID will be generated.
+/// Otherwise, a new UUID will be generated. The id is stashed in request
+/// extensions (for handlers), in the `reflex_layer::error::REQUEST_ID`
+/// task-local (so `IntoResponse` impls can attach it to `ErrorResponse`
+/// without it being threaded through every function signature), and as a
+/// field on the span wrapping the rest of the request so every `tracing`
+/// event emitted while handling it carries the same correlation id.
 pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
     // Get or generate request ID
     let request_id = request
@@ -32,8 +39,11 @@ pub async fn request_id_middleware(mut request: Request, next: Next) -> Response
             .insert(REQUEST_ID_HEADER, header_value.clone());
     }
 
-    // Process request
-    let mut response = next.run(request).await;
+    // Process request, with the id available as a task-local and as a span field
+    let span = tracing::info_span!("request", request_id = %request_id);
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request).instrument(span))
+        .await;
 
     // Add to response headers
     if let Ok(header_value) = HeaderValue::from_str(&request_id) {
@@ -0,0 +1,286 @@
+// Unicode Evasion Normalization
+//
+// Attackers evade literal-text pattern matching by substituting visually similar
+// characters: Cyrillic/Greek homoglyphs for Latin letters, zero-width joiners spliced
+// between letters, and leetspeak digit-for-letter substitutions. This module builds
+// normalized views of a string that fold those tricks back to plain ASCII, while
+// keeping a byte-offset mapping back to the original text so callers can still report
+// (and redact) the real, unmodified bytes.
+//
+// `PIIDetector` and `InjectionDetector` both run their pattern sets against the raw
+// text first, then against the homoglyph-normalized view (and, if enabled, the
+// further leet-folded view), translating any hits found in a normalized view back to
+// original offsets via `NormalizedText::original_range`.
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+lazy_static! {
+    /// Curated table of Cyrillic and Greek characters that are visually
+    /// indistinguishable (or nearly so) from a Latin letter, mapped to that letter.
+    /// Not an exhaustive Unicode confusables table — just the letters attackers
+    /// actually reach for to spoof common English words.
+    static ref HOMOGLYPH_MAP: HashMap<char, char> = {
+        let mut m = HashMap::new();
+
+        // Cyrillic lookalikes
+        for (cyrillic, latin) in [
+            ('а', 'a'), ('А', 'A'),
+            ('е', 'e'), ('Е', 'E'),
+            ('о', 'o'), ('О', 'O'),
+            ('р', 'p'), ('Р', 'P'),
+            ('с', 'c'), ('С', 'C'),
+            ('х', 'x'), ('Х', 'X'),
+            ('у', 'y'), ('У', 'Y'),
+            ('і', 'i'), ('І', 'I'),
+            ('ј', 'j'), ('Ј', 'J'),
+            ('ѕ', 's'), ('Ѕ', 'S'),
+            ('ԁ', 'd'),
+            ('һ', 'h'),
+            ('ⅰ', 'i'),
+        ] {
+            m.insert(cyrillic, latin);
+        }
+
+        // Greek lookalikes
+        for (greek, latin) in [
+            ('α', 'a'), ('Α', 'A'),
+            ('ο', 'o'), ('Ο', 'O'),
+            ('ρ', 'p'), ('Ρ', 'P'),
+            ('υ', 'u'), ('Υ', 'Y'),
+            ('ν', 'v'), ('Ν', 'N'),
+            ('κ', 'k'), ('Κ', 'K'),
+            ('β', 'b'), ('Β', 'B'),
+            ('τ', 't'), ('Τ', 'T'),
+        ] {
+            m.insert(greek, latin);
+        }
+
+        m
+    };
+
+    /// Curated leetspeak fold table. Ambiguous digits (`1` could stand in for
+    /// either `i` or `l`) are folded to one canonical letter so the mapping stays
+    /// deterministic; picking the wrong one just means the leet-folded view misses
+    /// that particular word; the homoglyph-normalized and raw passes are unaffected.
+    static ref LEET_MAP: HashMap<char, char> = {
+        let mut m = HashMap::new();
+        m.insert('1', 'i');
+        m.insert('3', 'e');
+        m.insert('0', 'o');
+        m.insert('@', 'a');
+        m.insert('$', 's');
+        m
+    };
+}
+
+/// Zero-width and other invisible formatting characters attackers splice between
+/// letters to break up a literal match (e.g. `i⁠g⁠n⁠o⁠r⁠e`)
+fn is_zero_width(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00AD}' // soft hyphen
+            | '\u{200B}' // zero width space
+            | '\u{200C}' // zero width non-joiner
+            | '\u{200D}' // zero width joiner
+            | '\u{2060}' // word joiner
+            | '\u{FEFF}' // zero width no-break space / BOM
+    )
+}
+
+/// Combining diacritical marks, which can be stacked onto an otherwise-plain ASCII
+/// letter to visually disguise it (e.g. `i` + combining tilde)
+fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+/// Map a fullwidth Latin letter or digit (U+FF01-U+FF5E) to its standard-width
+/// ASCII equivalent, the codepoints differ by a constant offset from `!`..`~`
+fn fold_fullwidth(ch: char) -> Option<char> {
+    let code = ch as u32;
+    if (0xFF01..=0xFF5E).contains(&code) {
+        char::from_u32(code - 0xFEE0)
+    } else {
+        None
+    }
+}
+
+fn map_homoglyph(ch: char) -> char {
+    if let Some(&mapped) = HOMOGLYPH_MAP.get(&ch) {
+        mapped
+    } else if let Some(folded) = fold_fullwidth(ch) {
+        folded
+    } else {
+        ch
+    }
+}
+
+/// A normalized view of some text, plus a mapping from each of its byte offsets
+/// back to the byte offset in the true original text it was derived from
+///
+/// `offsets` has `normalized.len() + 1` entries so that both a match's `start()`
+/// and its exclusive `end()` can be looked up, the final entry is always the
+/// original text's length.
+pub struct NormalizedText {
+    normalized: String,
+    offsets: Vec<usize>,
+}
+
+impl NormalizedText {
+    /// The normalized text, to run patterns against
+    pub fn as_str(&self) -> &str {
+        &self.normalized
+    }
+
+    /// Translate a `[start, end)` byte range in `as_str()` back to the
+    /// corresponding `[start, end)` byte range in the original text
+    pub fn original_range(&self, start: usize, end: usize) -> (usize, usize) {
+        (self.offsets[start], self.offsets[end])
+    }
+}
+
+/// Build a normalized view of `text` with zero-width characters and combining
+/// marks stripped out, and Cyrillic/Greek/fullwidth homoglyphs folded back to
+/// their ASCII equivalents
+///
+/// This is the view `PatternSet` and injection patterns should be run against
+/// alongside the raw text, to catch lookalike evasion like `с1а1is` spoofing
+/// "claim" or `V1agr@` spoofing "Viagra".
+pub fn normalize_homoglyphs(text: &str) -> NormalizedText {
+    build_normalized(text, None, |ch| {
+        if is_zero_width(ch) || is_combining_mark(ch) {
+            None
+        } else {
+            Some(map_homoglyph(ch))
+        }
+    })
+}
+
+/// Build a secondary, further-folded view on top of an already
+/// homoglyph-normalized view, substituting common leetspeak digits/symbols for
+/// the letters they're standing in for (`1`->`i`, `3`->`e`, `0`->`o`, `@`->`a`,
+/// `$`->`s`)
+///
+/// This catches instructions spelled like `1gn0re` or `byp@$$`. It's kept as a
+/// separate, opt-in pass rather than folded into `normalize_homoglyphs` because
+/// folding digits into letters is far more prone to false positives (legitimate
+/// product codes, part numbers, etc. contain digits that aren't leetspeak).
+pub fn fold_leet(homoglyph_normalized: &NormalizedText) -> NormalizedText {
+    build_normalized(
+        homoglyph_normalized.as_str(),
+        Some(&homoglyph_normalized.offsets),
+        |ch| Some(*LEET_MAP.get(&ch).unwrap_or(&ch)),
+    )
+}
+
+/// Shared builder for both normalization passes: walks `text` char by char,
+/// calling `transform` to either drop the character (`None`) or replace it with
+/// another character (`Some`), while tracking which original byte offset each
+/// emitted byte came from
+///
+/// `base_offsets`, if given, is `text`'s own offset table back to some earlier
+/// "true" original (as produced by a prior call to this function); omitting it
+/// treats `text` itself as the original.
+fn build_normalized(
+    text: &str,
+    base_offsets: Option<&[usize]>,
+    mut transform: impl FnMut(char) -> Option<char>,
+) -> NormalizedText {
+    let mut normalized = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+
+    for (byte_idx, ch) in text.char_indices() {
+        let Some(mapped) = transform(ch) else {
+            continue;
+        };
+        let original_idx = base_offsets.map_or(byte_idx, |o| o[byte_idx]);
+        for _ in 0..mapped.len_utf8() {
+            offsets.push(original_idx);
+        }
+        normalized.push(mapped);
+    }
+
+    let original_len = base_offsets.map_or(text.len(), |o| o[text.len()]);
+    offsets.push(original_len);
+
+    NormalizedText {
+        normalized,
+        offsets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_homoglyph_normalization_maps_cyrillic_lookalikes() {
+        // Cyrillic "с" (U+0441) and "а" (U+0430) standing in for Latin "c"/"a"
+        let normalized = normalize_homoglyphs("с1а1is");
+        assert_eq!(normalized.as_str(), "c1a1is");
+    }
+
+    #[test]
+    fn test_homoglyph_normalization_strips_zero_width_joiners() {
+        let normalized = normalize_homoglyphs("i\u{200D}g\u{200B}n\u{200C}o\u{2060}re");
+        assert_eq!(normalized.as_str(), "ignore");
+    }
+
+    #[test]
+    fn test_homoglyph_normalization_strips_combining_marks() {
+        let normalized = normalize_homoglyphs("i\u{0303}gnore"); // i + combining tilde
+        assert_eq!(normalized.as_str(), "ignore");
+    }
+
+    #[test]
+    fn test_homoglyph_normalization_folds_fullwidth_latin() {
+        // Fullwidth "ＶＩＡＧＲＡ"
+        let normalized = normalize_homoglyphs("\u{FF36}\u{FF29}\u{FF21}\u{FF27}\u{FF32}\u{FF21}");
+        assert_eq!(normalized.as_str(), "VIAGRA");
+    }
+
+    #[test]
+    fn test_homoglyph_normalization_leaves_plain_ascii_untouched() {
+        let normalized = normalize_homoglyphs("hello world 123");
+        assert_eq!(normalized.as_str(), "hello world 123");
+    }
+
+    #[test]
+    fn test_original_range_maps_back_through_dropped_characters() {
+        // "i<ZWJ>gnore" -- normalized "ignore" starts at byte 0, but original
+        // "gnore" (after the dropped ZWJ) starts 3 bytes in (1-byte 'i' + 3-byte ZWJ)
+        let text = "i\u{200D}gnore";
+        let normalized = normalize_homoglyphs(text);
+        assert_eq!(normalized.as_str(), "ignore");
+        let (start, end) = normalized.original_range(1, 6); // "gnore" in the normalized view
+        assert_eq!(&text[start..end], "gnore");
+    }
+
+    #[test]
+    fn test_fold_leet_substitutes_digits_and_symbols() {
+        let homoglyph = normalize_homoglyphs("1gn0re byp@$$");
+        let leet = fold_leet(&homoglyph);
+        assert_eq!(leet.as_str(), "ignore bypass");
+    }
+
+    #[test]
+    fn test_fold_leet_original_range_maps_through_both_passes() {
+        let text = "с1а1is"; // homoglyph 'с'/'а' + leet '1'
+        let homoglyph = normalize_homoglyphs(text);
+        let leet = fold_leet(&homoglyph);
+        assert_eq!(leet.as_str(), "caiis");
+
+        let (start, end) = leet.original_range(0, 5);
+        assert_eq!(&text[start..end], text);
+    }
+
+    #[test]
+    fn test_normalized_text_handles_empty_input() {
+        let normalized = normalize_homoglyphs("");
+        assert_eq!(normalized.as_str(), "");
+        assert_eq!(normalized.original_range(0, 0), (0, 0));
+    }
+}
@@ -3,10 +3,19 @@
 // This module implements the main PII detection algorithm.
 
 use std::collections::HashMap;
-
-use crate::pii::patterns::{get_pattern_metadata, get_patterns};
-use crate::pii::types::{PIIConfig, PIIMatch, PIIType};
-use crate::pii::validator::{validate_email, validate_luhn, validate_phone, validate_ssn};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::normalize::{fold_leet, normalize_homoglyphs};
+use crate::pii::patterns::{extract_cidr_mask, get_pattern_metadata, get_patterns_indexed};
+use crate::pii::secret::{mask_keep_trailing, RedactedPIIMatch, SecretPIIMatch};
+use crate::pii::types::{CryptoConfig, PIIConfig, PIIMatch, PIIType, PatternSet};
+use crate::pii::validator::{
+    crypto_network_tag, ethereum_checksum_status, validate_bitcoin_address, CryptoNetworkTag,
+    EthereumChecksumStatus, Validator, ValidatorRegistry,
+};
+use crate::update::{verify_bundle, SignedBundle, TrustRoot, UpdateError};
+use zeroize::Zeroizing;
 
 /// Main PII detector that finds PII in text
 pub struct PIIDetector {
@@ -14,6 +23,19 @@ pub struct PIIDetector {
     config: PIIConfig,
     /// Pattern metadata for severity and validation requirements
     metadata: HashMap<PIIType, crate::pii::patterns::PatternMetadata>,
+    /// Cheap "could this possibly match" prefilter over every pattern in
+    /// `config.pattern_set`, built once at construction time so the per-call
+    /// cost of scanning N patterns collapses to one O(text) pass instead of
+    /// O(patterns * text)
+    pattern_set: regex::RegexSet,
+    /// Patterns in the same order as `pattern_set`, for span extraction
+    patterns: Vec<(PIIType, &'static regex::Regex)>,
+    /// Patterns loaded from a signed update bundle, keyed by pattern file name
+    custom_patterns: RwLock<Vec<(String, regex::Regex)>>,
+    /// Version of the last signed bundle accepted by `load_signed_patterns`
+    last_update_version: AtomicU64,
+    /// Pluggable structural validators, keyed by PII type
+    validators: ValidatorRegistry,
 }
 
 impl PIIDetector {
@@ -32,12 +54,79 @@ impl PIIDetector {
     ///     pattern_set: PatternSet::Standard,
     ///     enable_validation: true,
     ///     enable_context: false,
+    ///     ..Default::default()
     /// };
     /// let detector = PIIDetector::new(config);
     /// ```
     pub fn new(config: PIIConfig) -> Self {
         let metadata = get_pattern_metadata();
-        Self { config, metadata }
+        let (pattern_set, patterns) = get_patterns_indexed(&config.pattern_set);
+        Self {
+            config,
+            metadata,
+            pattern_set,
+            patterns,
+            custom_patterns: RwLock::new(Vec::new()),
+            last_update_version: AtomicU64::new(0),
+            validators: ValidatorRegistry::with_defaults(),
+        }
+    }
+
+    /// Register a custom structural validator for a PII type, replacing any
+    /// existing one (including a built-in default)
+    ///
+    /// Useful for national ID formats and other categories beyond the
+    /// built-ins, especially alongside `PIIType::Custom` patterns loaded via
+    /// `load_signed_patterns`.
+    pub fn register_validator(&self, pii_type: PIIType, validator: Box<dyn Validator>) {
+        self.validators.register(pii_type, validator);
+    }
+
+    /// Cheaply check whether `text` could contain any PII
+    ///
+    /// Runs only the `RegexSet` prefilter scan, with no span extraction, no
+    /// validation, and no `PIIMatch` allocation — use this when the caller
+    /// only needs a yes/no signal and `detect` would be wasted work.
+    pub fn is_suspicious(&self, text: &str) -> bool {
+        self.pattern_set.is_match(text)
+            || self
+                .custom_patterns
+                .read()
+                .unwrap()
+                .iter()
+                .any(|(_, pattern)| pattern.is_match(text))
+    }
+
+    /// Load a signed, versioned pattern-set update
+    ///
+    /// Verifies the bundle's signature threshold, anti-rollback version
+    /// check, expiry, and per-file hashes before compiling any regex. The
+    /// active custom pattern set is only swapped once every check has
+    /// passed and every file has compiled successfully, so a malformed or
+    /// tampered bundle never partially applies.
+    pub fn load_signed_patterns(
+        &self,
+        bundle: &SignedBundle,
+        trust: &TrustRoot,
+    ) -> Result<(), UpdateError> {
+        let last_version = self.last_update_version.load(Ordering::SeqCst);
+        let verified_files = verify_bundle(bundle, trust, last_version)?;
+
+        let mut compiled = Vec::with_capacity(verified_files.len());
+        for file in &verified_files {
+            let regex = regex::Regex::new(&file.content).map_err(|e| UpdateError::InvalidPattern {
+                file: file.path.clone(),
+                source: e.to_string(),
+            })?;
+            compiled.push((file.path.clone(), regex));
+        }
+
+        let metadata = bundle.metadata()?;
+
+        *self.custom_patterns.write().unwrap() = compiled;
+        self.last_update_version.store(metadata.version, Ordering::SeqCst);
+
+        Ok(())
     }
 
     /// Detect all PII in the given text
@@ -62,20 +151,98 @@ impl PIIDetector {
     /// assert!(matches.len() > 0);
     /// ```
     pub fn detect(&self, text: &str) -> Vec<PIIMatch> {
+        self.detect_raw(text)
+    }
+
+    /// Detect PII and return zeroize-on-drop matches
+    ///
+    /// Identical to `detect`, except each match's raw text is wrapped in a
+    /// `Zeroizing<String>` so the backing buffer is scrubbed as soon as the
+    /// match is dropped, instead of being left in a freed allocation for the
+    /// next user of that memory to potentially read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reflex_layer::pii::{PIIDetector, PIIConfig};
+    ///
+    /// let detector = PIIDetector::new(PIIConfig::default());
+    /// let matches = detector.detect_secret("SSN: 123-45-6789");
+    /// assert_eq!(&*matches[0].matched_text, "123-45-6789");
+    /// ```
+    pub fn detect_secret(&self, text: &str) -> Vec<SecretPIIMatch> {
+        self.detect_raw(text)
+            .into_iter()
+            .map(|m| SecretPIIMatch::new(m.pii_type, m.start, m.end, m.matched_text, m.confidence))
+            .collect()
+    }
+
+    /// Detect PII without ever exposing the raw matched text to the caller
+    ///
+    /// Each match is reduced to its offsets, type, and a masked rendering
+    /// (keeping the last `keep_trailing` characters, e.g. `***-**-6789`)
+    /// before the raw text is dropped, so downstream logging/telemetry can
+    /// record that PII was found and where without the plaintext ever being
+    /// cloned into a caller-owned `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reflex_layer::pii::{PIIDetector, PIIConfig};
+    ///
+    /// let detector = PIIDetector::new(PIIConfig::default());
+    /// let matches = detector.detect_redacted("SSN: 123-45-6789", 4);
+    /// assert_eq!(matches[0].masked, "*******6789");
+    /// ```
+    pub fn detect_redacted(&self, text: &str, keep_trailing: usize) -> Vec<RedactedPIIMatch> {
+        self.detect_raw(text)
+            .into_iter()
+            .map(|m| {
+                let raw = Zeroizing::new(m.matched_text);
+                let masked = mask_keep_trailing(&raw, keep_trailing);
+                RedactedPIIMatch {
+                    pii_type: m.pii_type,
+                    start: m.start,
+                    end: m.end,
+                    masked,
+                    confidence: m.confidence,
+                }
+            })
+            .collect()
+    }
+
+    /// Run the indexed pattern prefilter and every `get_patterns` regex against
+    /// `text`, returning matches with offsets in `text`'s own coordinate space
+    ///
+    /// Shared by `detect_raw`'s raw-text pass and its homoglyph/leet-normalized
+    /// passes; the caller is responsible for translating offsets back to the
+    /// original text when `text` isn't the original.
+    fn scan_indexed_patterns(&self, text: &str) -> Vec<PIIMatch> {
         let mut matches = Vec::new();
-        let patterns = get_patterns(&self.config.pattern_set);
 
-        // Iterate through all enabled patterns
-        for (pii_type, pattern) in &patterns {
+        // Prefilter: one unified RegexSet scan to learn which patterns can
+        // possibly match, then only run `find_iter` (span extraction) on
+        // that subset instead of every pattern in the active `PatternSet`.
+        for idx in self.pattern_set.matches(text).iter() {
+            let (pii_type, pattern) = &self.patterns[idx];
+            if !Self::chain_enabled(&self.config.crypto, pii_type) {
+                continue;
+            }
             // Find all matches for this pattern
             for capture in pattern.find_iter(text) {
                 let matched_text = capture.as_str().to_string();
 
-                // Validate if enabled and required
+                // Validate if enabled and required. A failed validation only
+                // suppresses the match outright under `PatternSet::Strict`;
+                // otherwise it's kept with a lower confidence score (see
+                // `calculate_confidence`), so `enable_validation` is a
+                // precision lever rather than a binary accept/reject switch.
                 if self.config.enable_validation {
                     if let Some(meta) = self.metadata.get(pii_type) {
-                        if meta.requires_validation && !self.validate(pii_type, &matched_text) {
-                            // Skip this match if validation fails
+                        if meta.requires_validation
+                            && !self.validate(pii_type, &matched_text)
+                            && self.config.pattern_set == PatternSet::Strict
+                        {
                             continue;
                         }
                     }
@@ -83,13 +250,75 @@ impl PIIDetector {
 
                 // Calculate confidence score
                 let confidence = self.calculate_confidence(pii_type, &matched_text);
+                let cidr_mask = extract_cidr_mask(pii_type, &matched_text);
+                let network = crypto_network_tag(pii_type, &matched_text);
 
-                matches.push(PIIMatch::new(
+                let mut pii_match = PIIMatch::new(
                     pii_type.clone(),
                     capture.start(),
                     capture.end(),
                     matched_text,
                     confidence,
+                );
+                if let Some(mask) = cidr_mask {
+                    pii_match = pii_match.with_cidr_mask(mask);
+                }
+                if let Some(network) = network {
+                    pii_match = pii_match.with_crypto_network(network);
+                }
+                matches.push(pii_match);
+            }
+        }
+
+        matches
+    }
+
+    /// Whether `pii_type`'s chain is enabled under `crypto`
+    ///
+    /// Non-crypto types are always enabled; `crypto` only gates the three
+    /// crypto-address `PIIType`s.
+    fn chain_enabled(crypto: &CryptoConfig, pii_type: &PIIType) -> bool {
+        match pii_type {
+            PIIType::BitcoinAddress => crypto.enable_bitcoin_base58,
+            PIIType::CryptoWalletAddress => crypto.enable_bitcoin_bech32,
+            PIIType::EthereumAddress => crypto.enable_ethereum,
+            _ => true,
+        }
+    }
+
+    /// Core detection pass shared by `detect`, `detect_secret`, and `detect_redacted`
+    ///
+    /// Runs the pattern set against the raw text, then (unless disabled)
+    /// against a homoglyph-normalized view and, if enabled, a further
+    /// leet-folded view, so Cyrillic/Greek/fullwidth lookalikes and leetspeak
+    /// substitutions (`с1а1is`, `1gn0re`) don't slip past a literal regex.
+    /// Hits found in a normalized view are translated back to original byte
+    /// offsets before being reported, so redaction still targets the real
+    /// bytes; a normalized-view hit that overlaps one already found in the raw
+    /// text is dropped as a duplicate.
+    fn detect_raw(&self, text: &str) -> Vec<PIIMatch> {
+        let mut matches = self.scan_indexed_patterns(text);
+
+        if self.config.enable_homoglyph_normalization {
+            let homoglyph = normalize_homoglyphs(text);
+            self.append_normalized_matches(&mut matches, text, &homoglyph);
+
+            if self.config.enable_leet_folding {
+                let leet = fold_leet(&homoglyph);
+                self.append_normalized_matches(&mut matches, text, &leet);
+            }
+        }
+
+        // Run patterns loaded from a signed update bundle, if any
+        for (name, pattern) in self.custom_patterns.read().unwrap().iter() {
+            for capture in pattern.find_iter(text) {
+                let matched_text = capture.as_str().to_string();
+                matches.push(PIIMatch::new(
+                    PIIType::Custom(name.clone()),
+                    capture.start(),
+                    capture.end(),
+                    matched_text,
+                    0.8,
                 ));
             }
         }
@@ -99,6 +328,43 @@ impl PIIDetector {
         matches
     }
 
+    /// Scan `normalized` and append every hit not already covered by an
+    /// existing match at the same (translated) original offsets, to `matches`
+    ///
+    /// `original_text` is re-sliced at the translated offsets so the reported
+    /// `matched_text` is always the real original bytes, never the normalized
+    /// stand-in text.
+    fn append_normalized_matches(
+        &self,
+        matches: &mut Vec<PIIMatch>,
+        original_text: &str,
+        normalized: &crate::normalize::NormalizedText,
+    ) {
+        for m in self.scan_indexed_patterns(normalized.as_str()) {
+            let (start, end) = normalized.original_range(m.start, m.end);
+            if matches
+                .iter()
+                .any(|existing| existing.start == start && existing.end == end)
+            {
+                continue;
+            }
+            let mut pii_match = PIIMatch::new(
+                m.pii_type,
+                start,
+                end,
+                original_text[start..end].to_string(),
+                m.confidence,
+            );
+            if let Some(mask) = m.cidr_mask {
+                pii_match = pii_match.with_cidr_mask(mask);
+            }
+            if let Some(network) = m.crypto_network {
+                pii_match = pii_match.with_crypto_network(network);
+            }
+            matches.push(pii_match);
+        }
+    }
+
     /// Detect PII with context awareness
     ///
     /// This method looks for context clues near PII (e.g., "SSN:", "Email:")
@@ -180,20 +446,65 @@ impl PIIDetector {
         counts
     }
 
-    /// Validate a PII value based on its type
-    fn validate(&self, pii_type: &PIIType, text: &str) -> bool {
-        match pii_type {
-            PIIType::CreditCard => validate_luhn(text),
-            PIIType::SSN => validate_ssn(text),
-            PIIType::Email => validate_email(text),
-            PIIType::Phone => validate_phone(text),
-            // Other types don't require validation
-            _ => true,
+    /// Count crypto wallet address matches by network/address-family tag
+    ///
+    /// Complements `count_pii`'s per-`PIIType` breakdown with a per-network
+    /// one, so a compliance caller can answer "how many mainnet Bitcoin
+    /// addresses vs testnet" from one scan. Matches whose network couldn't
+    /// be determined (e.g. a Lightning invoice) aren't counted here.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to scan for PII
+    ///
+    /// # Returns
+    ///
+    /// A HashMap mapping `CryptoNetworkTag` to count of occurrences
+    pub fn count_crypto_by_network(&self, text: &str) -> HashMap<CryptoNetworkTag, usize> {
+        let matches = self.detect(text);
+        let mut counts: HashMap<CryptoNetworkTag, usize> = HashMap::new();
+
+        for match_ in matches {
+            if let Some(network) = match_.crypto_network {
+                *counts.entry(network).or_insert(0) += 1;
+            }
         }
+
+        counts
+    }
+
+    /// Validate a PII value using the registered `Validator` for its type
+    ///
+    /// A type with no registered validator is treated as passing, since
+    /// there's nothing to confirm beyond the regex match itself.
+    fn validate(&self, pii_type: &PIIType, text: &str) -> bool {
+        self.validators.validate(pii_type, text).unwrap_or(true)
     }
 
     /// Calculate confidence score for a PII match
     fn calculate_confidence(&self, pii_type: &PIIType, text: &str) -> f64 {
+        // `EthereumAddress` gets its own three-tier scheme instead of the
+        // generic pass/fail one below: EIP-55 checksums are optional, so an
+        // all-same-case address isn't wrong, just unconfirmed, which the
+        // validated/unvalidated binary can't express.
+        if *pii_type == PIIType::EthereumAddress && self.config.enable_validation {
+            return match ethereum_checksum_status(text) {
+                EthereumChecksumStatus::Valid => 1.0,
+                EthereumChecksumStatus::NoChecksum => 0.8,
+                EthereumChecksumStatus::Invalid | EthereumChecksumStatus::Malformed => 0.05,
+            };
+        }
+
+        // `BitcoinAddress` also gets a sharper down-weight than the generic
+        // 0.7 "pattern match without validation" fallback below: a base58/
+        // bech32-shaped string that fails its checksum is almost always a
+        // false positive (a random identifier, not a malformed real address),
+        // so it should score closer to "not actually a match" than to
+        // "plausible but unconfirmed".
+        if *pii_type == PIIType::BitcoinAddress && self.config.enable_validation {
+            return if validate_bitcoin_address(text) { 1.0 } else { 0.05 };
+        }
+
         let base_confidence = if self.config.enable_validation {
             // If validation is enabled and this type was validated, high confidence
             if let Some(meta) = self.metadata.get(pii_type) {
@@ -283,16 +594,17 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_with_validation_rejects_invalid() {
+    fn test_detect_with_validation_rejects_invalid_in_strict_mode() {
         let detector = PIIDetector::new(PIIConfig {
             enable_validation: true,
+            pattern_set: PatternSet::Strict,
             ..Default::default()
         });
         // Invalid Luhn checksum
         let text = "Card: 4532-0151-1283-0367";
         let matches = detector.detect(text);
 
-        // Should not detect invalid credit card
+        // Strict mode suppresses matches that fail validation entirely
         let cc_matches: Vec<_> = matches
             .iter()
             .filter(|m| m.pii_type == PIIType::CreditCard)
@@ -300,6 +612,82 @@ mod tests {
         assert_eq!(cc_matches.len(), 0);
     }
 
+    #[test]
+    fn test_detect_with_validation_lowers_confidence_outside_strict() {
+        let detector = PIIDetector::new(PIIConfig {
+            enable_validation: true,
+            ..Default::default()
+        });
+        // Invalid Luhn checksum
+        let text = "Card: 4532-0151-1283-0367";
+        let matches = detector.detect(text);
+
+        // Standard mode keeps the match but with lowered confidence, rather
+        // than dropping it outright
+        let cc_match = matches
+            .iter()
+            .find(|m| m.pii_type == PIIType::CreditCard)
+            .expect("invalid card should still be reported outside strict mode");
+        assert!(cc_match.confidence < 1.0);
+    }
+
+    #[test]
+    fn test_ethereum_address_confidence_tiers_by_checksum_status() {
+        let detector = PIIDetector::new(PIIConfig {
+            enable_validation: true,
+            ..Default::default()
+        });
+
+        // Valid EIP-55 mixed-case checksum: high confidence
+        let valid = detector.detect("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        let valid_match = valid
+            .iter()
+            .find(|m| m.pii_type == PIIType::EthereumAddress)
+            .expect("valid checksummed address should be detected");
+        assert_eq!(valid_match.confidence, 1.0);
+
+        // All-lowercase: no checksum asserted, moderate confidence
+        let unchecked = detector.detect("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        let unchecked_match = unchecked
+            .iter()
+            .find(|m| m.pii_type == PIIType::EthereumAddress)
+            .expect("all-lowercase address should be detected");
+        assert_eq!(unchecked_match.confidence, 0.8);
+
+        // Same address as the valid one with one letter's case flipped:
+        // checksum violation, kept (outside strict mode) but very low confidence
+        let invalid = detector.detect("0x5aAeb6053f3E94C9b9A09f33669435E7Ef1BeAed");
+        let invalid_match = invalid
+            .iter()
+            .find(|m| m.pii_type == PIIType::EthereumAddress)
+            .expect("checksum violation should still be reported outside strict mode");
+        assert_eq!(invalid_match.confidence, 0.05);
+    }
+
+    #[test]
+    fn test_bitcoin_address_confidence_down_weighted_on_checksum_failure() {
+        let detector = PIIDetector::new(PIIConfig {
+            pattern_set: PatternSet::Relaxed,
+            enable_validation: true,
+            ..Default::default()
+        });
+
+        let valid = detector.detect("Bitcoin: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+        let valid_match = valid
+            .iter()
+            .find(|m| m.pii_type == PIIType::BitcoinAddress)
+            .expect("valid checksummed address should be detected");
+        assert_eq!(valid_match.confidence, 1.0);
+
+        // Last character flipped: same shape, fails the base58check checksum
+        let invalid = detector.detect("Bitcoin: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb");
+        let invalid_match = invalid
+            .iter()
+            .find(|m| m.pii_type == PIIType::BitcoinAddress)
+            .expect("checksum violation should still be reported outside strict mode");
+        assert_eq!(invalid_match.confidence, 0.05);
+    }
+
     #[test]
     fn test_detect_by_type() {
         let detector = PIIDetector::new(PIIConfig::default());
@@ -310,6 +698,24 @@ mod tests {
         assert_eq!(matches[0].pii_type, PIIType::Email);
     }
 
+    #[test]
+    fn test_detect_reports_cidr_mask_for_ip_subnets() {
+        let detector = PIIDetector::new(PIIConfig {
+            pattern_set: PatternSet::Relaxed,
+            ..Default::default()
+        });
+        let matches = detector.detect("Subnet: 192.168.0.0/24, host: 10.0.0.1");
+
+        let subnet = matches
+            .iter()
+            .find(|m| m.matched_text == "192.168.0.0/24")
+            .unwrap();
+        assert_eq!(subnet.cidr_mask, Some(24));
+
+        let host = matches.iter().find(|m| m.matched_text == "10.0.0.1").unwrap();
+        assert_eq!(host.cidr_mask, None);
+    }
+
     #[test]
     fn test_count_pii() {
         let detector = PIIDetector::new(PIIConfig::default());
@@ -361,4 +767,255 @@ mod tests {
         let matches = detector.detect(text);
         assert_eq!(matches.len(), 0);
     }
+
+    #[test]
+    fn test_detect_secret_preserves_value_and_offsets() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "My SSN is 123-45-6789";
+        let matches = detector.detect_secret(text);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].pii_type, PIIType::SSN);
+        assert_eq!(&*matches[0].matched_text, "123-45-6789");
+        assert_eq!(matches[0].start, 10);
+        assert_eq!(matches[0].end, 21);
+    }
+
+    #[test]
+    fn test_detect_redacted_masks_and_keeps_trailing() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "My SSN is 123-45-6789";
+        let matches = detector.detect_redacted(text, 4);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].pii_type, PIIType::SSN);
+        assert_eq!(matches[0].masked, "*******6789");
+        assert_eq!(matches[0].start, 10);
+        assert_eq!(matches[0].end, 21);
+    }
+
+    #[test]
+    fn test_detect_redacted_keeps_fewer_trailing_chars() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "Contact john.doe@example.com for more info";
+        let matches = detector.detect_redacted(text, 4);
+
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].masked.ends_with(".com"));
+        assert!(!matches[0].masked.contains("john"));
+    }
+
+    #[test]
+    fn test_detect_redacted_matches_detect_count() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "Email: test@example.com, Phone: 555-123-4567, SSN: 123-45-6789";
+
+        assert_eq!(detector.detect(text).len(), detector.detect_redacted(text, 4).len());
+    }
+
+    #[test]
+    fn test_is_suspicious_true_when_pii_present() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        assert!(detector.is_suspicious("Contact me at test@example.com"));
+    }
+
+    #[test]
+    fn test_is_suspicious_false_on_clean_text() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        assert!(!detector.is_suspicious("This text contains no PII information at all"));
+    }
+
+    #[test]
+    fn test_detect_skips_prefilter_misses_in_relaxed_mode() {
+        // Relaxed enables many more patterns than Standard; confirm the
+        // prefilter still only reports the types that are actually present
+        // rather than every enabled pattern.
+        let detector = PIIDetector::new(PIIConfig {
+            pattern_set: PatternSet::Relaxed,
+            ..Default::default()
+        });
+        let text = "My SSN is 123-45-6789";
+        let matches = detector.detect(text);
+
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::SSN));
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::MacAddress));
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::BitcoinAddress));
+    }
+
+    #[test]
+    fn test_detect_catches_email_with_cyrillic_homoglyphs() {
+        // Cyrillic "е" (U+0435) standing in for Latin "e" in "test"
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "Contact t\u{0435}st@example.com for more info";
+        let matches = detector.detect(text);
+
+        let email_match = matches
+            .iter()
+            .find(|m| m.pii_type == PIIType::Email)
+            .expect("expected to find an email despite the homoglyph");
+        // Offsets and matched text point at the real (Cyrillic-containing) bytes
+        assert_eq!(&text[email_match.start..email_match.end], email_match.matched_text);
+    }
+
+    #[test]
+    fn test_disabling_homoglyph_normalization_misses_lookalike_email() {
+        let detector = PIIDetector::new(PIIConfig {
+            enable_homoglyph_normalization: false,
+            ..Default::default()
+        });
+        let text = "Contact t\u{0435}st@example.com for more info";
+        let matches = detector.detect(text);
+
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::Email));
+    }
+
+    #[test]
+    fn test_homoglyph_normalization_does_not_duplicate_plain_ascii_matches() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "My SSN is 123-45-6789";
+        let matches = detector.detect(text);
+
+        assert_eq!(matches.iter().filter(|m| m.pii_type == PIIType::SSN).count(), 1);
+    }
+
+    #[test]
+    fn test_leet_folding_is_disabled_by_default() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        assert!(!detector.config.enable_leet_folding);
+    }
+
+    #[test]
+    fn test_enabling_leet_folding_does_not_disrupt_ordinary_detection() {
+        let detector = PIIDetector::new(PIIConfig {
+            pattern_set: PatternSet::Relaxed,
+            enable_leet_folding: true,
+            ..Default::default()
+        });
+        // The Bitcoin address itself is base58 (case-sensitive, not leet-folded);
+        // confirm leet folding doesn't break ordinary detection when enabled.
+        let text = "Bitcoin: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        let matches = detector.detect(text);
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::BitcoinAddress));
+    }
+
+    #[test]
+    fn test_detect_flags_bech32_wallet_address_and_rejects_bad_checksum() {
+        let detector = PIIDetector::new(PIIConfig::default());
+
+        let valid = "Send to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 please";
+        let matches = detector.detect(valid);
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::CryptoWalletAddress));
+
+        // Last character flipped relative to the valid address above
+        let invalid = "Send to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5 please";
+        let matches = detector.detect(invalid);
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::CryptoWalletAddress));
+    }
+
+    #[test]
+    fn test_detect_flags_lightning_invoice() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "Invoice: lnbc101qpzry9x8gfl694pr for the coffee";
+        let matches = detector.detect(text);
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::CryptoWalletAddress));
+    }
+
+    #[test]
+    fn test_detect_flags_valid_iban_and_rejects_bad_checksum() {
+        let detector = PIIDetector::new(PIIConfig::default());
+
+        let valid = "Wire to GB29 NWBK 6016 1331 9268 19 today";
+        let matches = detector.detect(valid);
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::Iban));
+
+        // Last check digit flipped relative to the valid IBAN above
+        let invalid = "Wire to GB28 NWBK 6016 1331 9268 19 today";
+        let matches = detector.detect(invalid);
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::Iban));
+    }
+
+    #[test]
+    fn test_detect_flags_valid_routing_number_and_rejects_bad_checksum() {
+        let detector = PIIDetector::new(PIIConfig {
+            pattern_set: PatternSet::Relaxed,
+            ..Default::default()
+        });
+
+        let valid = "Routing: 021000021 for the wire";
+        let matches = detector.detect(valid);
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::RoutingNumber));
+
+        let invalid = "Routing: 021000022 for the wire";
+        let matches = detector.detect(invalid);
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::RoutingNumber));
+    }
+
+    #[test]
+    fn test_detect_tags_bitcoin_matches_with_their_network() {
+        let detector = PIIDetector::new(PIIConfig::default());
+
+        let text = "Mainnet: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        let matches = detector.detect(text);
+        let m = matches
+            .iter()
+            .find(|m| m.pii_type == PIIType::BitcoinAddress)
+            .expect("expected a Bitcoin address match");
+        assert_eq!(m.crypto_network, Some(CryptoNetworkTag::BitcoinMainnetP2pkh));
+    }
+
+    #[test]
+    fn test_detect_tags_segwit_matches_with_their_network() {
+        let detector = PIIDetector::new(PIIConfig::default());
+
+        let text = "Segwit: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        let matches = detector.detect(text);
+        let m = matches
+            .iter()
+            .find(|m| m.pii_type == PIIType::CryptoWalletAddress)
+            .expect("expected a crypto wallet address match");
+        assert_eq!(m.crypto_network, Some(CryptoNetworkTag::BitcoinMainnetSegwit));
+    }
+
+    #[test]
+    fn test_disabling_a_chain_in_crypto_config_suppresses_its_matches() {
+        let detector = PIIDetector::new(PIIConfig {
+            crypto: CryptoConfig {
+                enable_bitcoin_base58: false,
+                ..CryptoConfig::default()
+            },
+            ..Default::default()
+        });
+
+        let text = "Bitcoin: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        let matches = detector.detect(text);
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::BitcoinAddress));
+    }
+
+    #[test]
+    fn test_disabling_ethereum_chain_leaves_other_chains_enabled() {
+        let detector = PIIDetector::new(PIIConfig {
+            crypto: CryptoConfig {
+                enable_ethereum: false,
+                ..CryptoConfig::default()
+            },
+            ..Default::default()
+        });
+
+        let text = "Eth: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed, \
+                    BTC: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        let matches = detector.detect(text);
+        assert!(!matches.iter().any(|m| m.pii_type == PIIType::EthereumAddress));
+        assert!(matches.iter().any(|m| m.pii_type == PIIType::BitcoinAddress));
+    }
+
+    #[test]
+    fn test_count_crypto_by_network_reports_mainnet_and_testnet_breakdown() {
+        let detector = PIIDetector::new(PIIConfig::default());
+        let text = "Mainnet: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa, \
+                    Testnet: mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn";
+
+        let counts = detector.count_crypto_by_network(text);
+        assert_eq!(counts.get(&CryptoNetworkTag::BitcoinMainnetP2pkh), Some(&1));
+        assert_eq!(counts.get(&CryptoNetworkTag::BitcoinTestnet), Some(&1));
+    }
 }
@@ -0,0 +1,918 @@
+// Expression-based redaction policy engine
+//
+// A `RedactionPolicy` is an ordered list of `<condition> => <strategy>` rules
+// evaluated per PII match. The condition language is a small expression
+// grammar (literals, dotted field references, comparison/boolean/`in`
+// operators, and a handful of string helpers) so operators can express
+// policies like "hash SSNs, token-replace emails, mask everything else" or
+// "if more than 5 matches in this field use Remove" as data rather than code.
+//
+// Pipeline: `lex` tokenizes a rule's condition text, `Parser` builds an
+// `Expr` tree from the tokens, and `eval` walks that tree against an
+// `EvalContext` to produce a `Value`, which `RedactionPolicy::resolve`
+// expects to be a `Value::Bool`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::pii::redactor::{redact_per_match, RedactionStrategy};
+use crate::pii::types::{PIIMatch, PIIType};
+
+/// Errors raised while parsing or evaluating a policy condition
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PolicyError {
+    /// The condition text ended before a complete expression was parsed
+    #[error("unexpected end of expression")]
+    UnexpectedEof,
+
+    /// A character or token sequence didn't fit the grammar
+    #[error("unexpected token: {0}")]
+    UnexpectedToken(String),
+
+    /// A dotted field reference isn't one of the known context fields
+    #[error("unknown field reference: {0}")]
+    UnknownField(String),
+
+    /// A function call name isn't one of the known string helpers
+    #[error("unknown function: {0}")]
+    UnknownFunction(String),
+
+    /// A function was called with the wrong number of arguments
+    #[error("function {0} expects {1} argument(s)")]
+    ArityError(String, usize),
+
+    /// An operator was applied to operands of incompatible types
+    #[error("type error evaluating expression: {0}")]
+    TypeError(String),
+
+    /// A rule's `=> <strategy>` suffix didn't name a known `RedactionStrategy`
+    #[error("unknown redaction strategy: {0}")]
+    UnknownStrategy(String),
+
+    /// A rule line didn't contain the `<condition> => <strategy>` separator
+    #[error("rule is missing a `=>` strategy separator: {0}")]
+    MissingStrategySeparator(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Not,
+    And,
+    Or,
+    In,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, PolicyError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '[' => {
+                tokens.push(Token::LBracket);
+                i += 1;
+            }
+            ']' => {
+                tokens.push(Token::RBracket);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '"' => {
+                let start = i + 1;
+                let mut j = start;
+                while j < chars.len() && chars[j] != '"' {
+                    j += 1;
+                }
+                if j >= chars.len() {
+                    return Err(PolicyError::UnexpectedToken(
+                        "unterminated string literal".to_string(),
+                    ));
+                }
+                tokens.push(Token::Str(chars[start..j].iter().collect()));
+                i = j + 1;
+            }
+            '&' if chars.get(i + 1) == Some(&'&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Le);
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Lt);
+                i += 1;
+            }
+            '>' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Ge);
+                i += 2;
+            }
+            '>' => {
+                tokens.push(Token::Gt);
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                let mut j = i;
+                while j < chars.len() && (chars[j].is_ascii_digit() || chars[j] == '.') {
+                    j += 1;
+                }
+                let text: String = chars[start..j].iter().collect();
+                let num = text
+                    .parse::<f64>()
+                    .map_err(|_| PolicyError::UnexpectedToken(text.clone()))?;
+                tokens.push(Token::Num(num));
+                i = j;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                let mut j = i;
+                while j < chars.len()
+                    && (chars[j].is_alphanumeric() || chars[j] == '_' || chars[j] == '.')
+                {
+                    j += 1;
+                }
+                let text: String = chars[start..j].iter().collect();
+                tokens.push(match text.as_str() {
+                    "true" => Token::Bool(true),
+                    "false" => Token::Bool(false),
+                    "in" => Token::In,
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Ident(text),
+                });
+                i = j;
+            }
+            other => {
+                return Err(PolicyError::UnexpectedToken(other.to_string()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Comparison operators usable between two expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A parsed policy condition
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    /// A dotted field reference, e.g. `pii.type` or `match.count`
+    Field(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Cmp(Box<Expr>, CmpOp, Box<Expr>),
+    In(Box<Expr>, Vec<Expr>),
+    /// A string helper call, e.g. `contains(match.text, "@")`
+    Call(String, Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &Token) -> Result<(), PolicyError> {
+        match self.advance() {
+            Some(ref t) if t == expected => Ok(()),
+            Some(t) => Err(PolicyError::UnexpectedToken(format!("{:?}", t))),
+            None => Err(PolicyError::UnexpectedEof),
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, PolicyError> {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, PolicyError> {
+        let mut left = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.advance();
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, PolicyError> {
+        let mut left = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token::And)) {
+            self.advance();
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, PolicyError> {
+        if matches!(self.peek(), Some(Token::Not)) {
+            self.advance();
+            let inner = self.parse_unary()?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, PolicyError> {
+        let left = self.parse_primary()?;
+
+        let op = match self.peek() {
+            Some(Token::Eq) => Some(CmpOp::Eq),
+            Some(Token::Ne) => Some(CmpOp::Ne),
+            Some(Token::Lt) => Some(CmpOp::Lt),
+            Some(Token::Le) => Some(CmpOp::Le),
+            Some(Token::Gt) => Some(CmpOp::Gt),
+            Some(Token::Ge) => Some(CmpOp::Ge),
+            _ => None,
+        };
+
+        if let Some(op) = op {
+            self.advance();
+            let right = self.parse_primary()?;
+            return Ok(Expr::Cmp(Box::new(left), op, Box::new(right)));
+        }
+
+        if matches!(self.peek(), Some(Token::In)) {
+            self.advance();
+            self.expect(&Token::LBracket)?;
+            let list = self.parse_list(&Token::RBracket)?;
+            self.expect(&Token::RBracket)?;
+            return Ok(Expr::In(Box::new(left), list));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_list(&mut self, terminator: &Token) -> Result<Vec<Expr>, PolicyError> {
+        let mut items = Vec::new();
+        if self.peek() == Some(terminator) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.parse_expr()?);
+            match self.peek() {
+                Some(Token::Comma) => {
+                    self.advance();
+                }
+                _ => break,
+            }
+        }
+        Ok(items)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, PolicyError> {
+        match self.advance() {
+            Some(Token::Str(s)) => Ok(Expr::Str(s)),
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Bool(b)) => Ok(Expr::Bool(b)),
+            Some(Token::LParen) => {
+                let inner = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) => {
+                if matches!(self.peek(), Some(Token::LParen)) {
+                    self.advance();
+                    let args = self.parse_list(&Token::RParen)?;
+                    self.expect(&Token::RParen)?;
+                    Ok(Expr::Call(name, args))
+                } else {
+                    Ok(Expr::Field(name))
+                }
+            }
+            Some(t) => Err(PolicyError::UnexpectedToken(format!("{:?}", t))),
+            None => Err(PolicyError::UnexpectedEof),
+        }
+    }
+}
+
+/// Parse a condition string into an `Expr`
+pub fn parse(input: &str) -> Result<Expr, PolicyError> {
+    let tokens = lex(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+    if parser.pos != parser.tokens.len() {
+        return Err(PolicyError::UnexpectedToken(format!(
+            "{:?}",
+            parser.tokens[parser.pos]
+        )));
+    }
+    Ok(expr)
+}
+
+/// Result of evaluating an `Expr`
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+impl Value {
+    fn as_str(&self) -> Result<&str, PolicyError> {
+        match self {
+            Value::Str(s) => Ok(s),
+            other => Err(PolicyError::TypeError(format!(
+                "expected a string, got {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// The per-match context a policy condition is evaluated against
+pub struct EvalContext<'a> {
+    /// Type of the PII that matched
+    pub pii_type: &'a PIIType,
+    /// Total number of matches found in the text being redacted
+    pub match_count: usize,
+    /// The literal matched text
+    pub matched_text: &'a str,
+    /// The route or endpoint the request came in on
+    pub route: &'a str,
+}
+
+fn resolve_field(name: &str, ctx: &EvalContext) -> Result<Value, PolicyError> {
+    match name {
+        "pii.type" => Ok(Value::Str(ctx.pii_type.to_string())),
+        "match.count" => Ok(Value::Num(ctx.match_count as f64)),
+        "match.text" => Ok(Value::Str(ctx.matched_text.to_string())),
+        "ctx.route" => Ok(Value::Str(ctx.route.to_string())),
+        other => Err(PolicyError::UnknownField(other.to_string())),
+    }
+}
+
+fn eval_call(name: &str, args: &[Expr], ctx: &EvalContext) -> Result<Value, PolicyError> {
+    let values = args
+        .iter()
+        .map(|a| eval(a, ctx))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match (name, values.as_slice()) {
+        ("lower", [v]) => Ok(Value::Str(v.as_str()?.to_lowercase())),
+        ("upper", [v]) => Ok(Value::Str(v.as_str()?.to_uppercase())),
+        ("len", [v]) => Ok(Value::Num(v.as_str()?.chars().count() as f64)),
+        ("contains", [a, b]) => Ok(Value::Bool(a.as_str()?.contains(b.as_str()?))),
+        ("starts_with", [a, b]) => Ok(Value::Bool(a.as_str()?.starts_with(b.as_str()?))),
+        ("ends_with", [a, b]) => Ok(Value::Bool(a.as_str()?.ends_with(b.as_str()?))),
+        ("lower", _) | ("upper", _) | ("len", _) => Err(PolicyError::ArityError(name.to_string(), 1)),
+        ("contains", _) | ("starts_with", _) | ("ends_with", _) => {
+            Err(PolicyError::ArityError(name.to_string(), 2))
+        }
+        _ => Err(PolicyError::UnknownFunction(name.to_string())),
+    }
+}
+
+fn compare(op: CmpOp, left: &Value, right: &Value) -> Result<bool, PolicyError> {
+    match (left, right) {
+        (Value::Num(a), Value::Num(b)) => Ok(match op {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Gt => a > b,
+            CmpOp::Ge => a >= b,
+        }),
+        (Value::Str(a), Value::Str(b)) => Ok(match op {
+            CmpOp::Eq => a == b,
+            CmpOp::Ne => a != b,
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Gt => a > b,
+            CmpOp::Ge => a >= b,
+        }),
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            CmpOp::Eq => Ok(a == b),
+            CmpOp::Ne => Ok(a != b),
+            _ => Err(PolicyError::TypeError(
+                "booleans only support == and !=".to_string(),
+            )),
+        },
+        (a, b) => Err(PolicyError::TypeError(format!(
+            "cannot compare {:?} with {:?}",
+            a, b
+        ))),
+    }
+}
+
+/// Evaluate a parsed condition against a match context
+fn eval(expr: &Expr, ctx: &EvalContext) -> Result<Value, PolicyError> {
+    match expr {
+        Expr::Str(s) => Ok(Value::Str(s.clone())),
+        Expr::Num(n) => Ok(Value::Num(*n)),
+        Expr::Bool(b) => Ok(Value::Bool(*b)),
+        Expr::Field(name) => resolve_field(name, ctx),
+        Expr::Not(inner) => match eval(inner, ctx)? {
+            Value::Bool(b) => Ok(Value::Bool(!b)),
+            other => Err(PolicyError::TypeError(format!(
+                "expected a boolean, got {:?}",
+                other
+            ))),
+        },
+        Expr::And(l, r) => {
+            let left = match eval(l, ctx)? {
+                Value::Bool(b) => b,
+                other => return Err(PolicyError::TypeError(format!("expected bool, got {:?}", other))),
+            };
+            if !left {
+                return Ok(Value::Bool(false));
+            }
+            eval(r, ctx)
+        }
+        Expr::Or(l, r) => {
+            let left = match eval(l, ctx)? {
+                Value::Bool(b) => b,
+                other => return Err(PolicyError::TypeError(format!("expected bool, got {:?}", other))),
+            };
+            if left {
+                return Ok(Value::Bool(true));
+            }
+            eval(r, ctx)
+        }
+        Expr::Cmp(l, op, r) => {
+            let left = eval(l, ctx)?;
+            let right = eval(r, ctx)?;
+            Ok(Value::Bool(compare(*op, &left, &right)?))
+        }
+        Expr::In(target, list) => {
+            let target = eval(target, ctx)?;
+            for item in list {
+                if eval(item, ctx)? == target {
+                    return Ok(Value::Bool(true));
+                }
+            }
+            Ok(Value::Bool(false))
+        }
+        Expr::Call(name, args) => eval_call(name, args, ctx),
+    }
+}
+
+/// A single `<condition> => <strategy>` rule
+#[derive(Debug, Clone)]
+struct PolicyRule {
+    condition: Expr,
+    strategy: RedactionStrategy,
+}
+
+/// Maps matched PII to a `RedactionStrategy`, chosen by evaluating an
+/// ordered list of rules against each match; the first rule whose condition
+/// evaluates to `true` wins, falling back to a default strategy.
+#[derive(Debug, Clone)]
+pub struct RedactionPolicy {
+    rules: Vec<PolicyRule>,
+    default_strategy: RedactionStrategy,
+}
+
+impl RedactionPolicy {
+    /// A policy with no rules, always resolving to `default_strategy`
+    pub fn new(default_strategy: RedactionStrategy) -> Self {
+        Self {
+            rules: Vec::new(),
+            default_strategy,
+        }
+    }
+
+    /// Append a rule, parsing `condition` into an `Expr`
+    pub fn with_rule(mut self, condition: &str, strategy: RedactionStrategy) -> Result<Self, PolicyError> {
+        self.rules.push(PolicyRule {
+            condition: parse(condition)?,
+            strategy,
+        });
+        Ok(self)
+    }
+
+    /// Parse a policy from semicolon-separated `<condition> => <strategy>`
+    /// rules (operator config format), e.g.
+    /// `pii.type in ["SSN", "BankAccount"] => Hash; pii.type == "Email" => Token`
+    pub fn from_rules_str(rules: &str, default_strategy: RedactionStrategy) -> Result<Self, PolicyError> {
+        let mut policy = Self::new(default_strategy);
+
+        for rule in rules.split(';') {
+            let rule = rule.trim();
+            if rule.is_empty() {
+                continue;
+            }
+
+            let (condition, strategy) = rule
+                .rsplit_once("=>")
+                .ok_or_else(|| PolicyError::MissingStrategySeparator(rule.to_string()))?;
+
+            let strategy = RedactionStrategy::from_str(strategy.trim())
+                .map_err(|_| PolicyError::UnknownStrategy(strategy.trim().to_string()))?;
+
+            policy = policy.with_rule(condition.trim(), strategy)?;
+        }
+
+        Ok(policy)
+    }
+
+    /// Resolve the strategy for a single match, evaluating rules in order
+    /// and falling back to `default_strategy` if none match (or a rule
+    /// fails to evaluate, which is logged and treated as a non-match)
+    pub fn resolve(&self, ctx: &EvalContext) -> RedactionStrategy {
+        for rule in &self.rules {
+            match eval(&rule.condition, ctx) {
+                Ok(Value::Bool(true)) => return rule.strategy,
+                Ok(Value::Bool(false)) => continue,
+                Ok(other) => {
+                    tracing::warn!(
+                        "redaction policy rule did not evaluate to a boolean: {:?}",
+                        other
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("redaction policy rule evaluation failed: {}", e);
+                }
+            }
+        }
+        self.default_strategy
+    }
+
+    /// Redact `text`, choosing a strategy per match via `resolve`
+    pub fn redact(&self, text: &str, matches: &[PIIMatch], route: &str) -> String {
+        redact_per_match(text, matches, |m| {
+            self.resolve(&EvalContext {
+                pii_type: &m.pii_type,
+                match_count: matches.len(),
+                matched_text: &m.matched_text,
+                route,
+            })
+        })
+    }
+
+    /// Build a policy from a flat `PIIType -> RedactionStrategy` map plus a
+    /// default fallback, for deployments that just want "hash SSNs, token
+    /// person names, mask credit cards, remove API keys" without writing
+    /// rule condition strings.
+    ///
+    /// This is a convenience layer over the rule engine: each entry becomes
+    /// a `pii.type == "<Type>"` rule (iterated in unspecified map order,
+    /// since the types are mutually exclusive and can't both match the same
+    /// `PIIMatch`), falling back to `default_strategy` for any type not
+    /// present in `type_strategies`.
+    pub fn from_type_map(
+        type_strategies: HashMap<PIIType, RedactionStrategy>,
+        default_strategy: RedactionStrategy,
+    ) -> Self {
+        let mut policy = Self::new(default_strategy);
+        for (pii_type, strategy) in type_strategies {
+            let condition = format!(r#"pii.type == "{}""#, pii_type);
+            policy = policy
+                .with_rule(&condition, strategy)
+                .expect(r#"pii.type == "<Type>" is always a valid condition"#);
+        }
+        policy
+    }
+}
+
+/// Redact `text` using `policy` to choose each match's strategy by its
+/// `PIIType`, independent of which route the request came in on
+///
+/// Equivalent to `policy.redact(text, matches, "")`; intended for policies
+/// built from [`RedactionPolicy::from_type_map`], whose rules only ever
+/// reference `pii.type` and never `ctx.route`. Policies with route-dependent
+/// rules should call [`RedactionPolicy::redact`] directly instead.
+pub fn redact_with_policy(text: &str, matches: &[PIIMatch], policy: &RedactionPolicy) -> String {
+    policy.redact(text, matches, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pii::types::PIIType;
+
+    fn ssn_match() -> PIIMatch {
+        PIIMatch::new(PIIType::SSN, 0, 11, "123-45-6789".to_string(), 0.95)
+    }
+
+    fn email_match() -> PIIMatch {
+        PIIMatch::new(PIIType::Email, 0, 16, "test@example.com".to_string(), 0.95)
+    }
+
+    #[test]
+    fn test_parse_simple_comparison() {
+        let expr = parse(r#"pii.type == "SSN""#).unwrap();
+        assert_eq!(
+            expr,
+            Expr::Cmp(
+                Box::new(Expr::Field("pii.type".to_string())),
+                CmpOp::Eq,
+                Box::new(Expr::Str("SSN".to_string())),
+            )
+        );
+    }
+
+    #[test]
+    fn test_eval_in_operator() {
+        let expr = parse(r#"pii.type in ["SSN", "BankAccount"]"#).unwrap();
+        let m = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &m.pii_type,
+            match_count: 1,
+            matched_text: &m.matched_text,
+            route: "/process",
+        };
+        assert_eq!(eval(&expr, &ctx).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_eval_match_count_threshold() {
+        let expr = parse("match.count > 5").unwrap();
+        let m = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &m.pii_type,
+            match_count: 6,
+            matched_text: &m.matched_text,
+            route: "/process",
+        };
+        assert_eq!(eval(&expr, &ctx).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_eval_string_helper_function() {
+        let expr = parse(r#"contains(match.text, "@")"#).unwrap();
+        let m = email_match();
+        let ctx = EvalContext {
+            pii_type: &m.pii_type,
+            match_count: 1,
+            matched_text: &m.matched_text,
+            route: "/process",
+        };
+        assert_eq!(eval(&expr, &ctx).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_eval_and_or_not() {
+        let expr = parse(r#"!(pii.type == "Email") && ctx.route == "/process""#).unwrap();
+        let m = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &m.pii_type,
+            match_count: 1,
+            matched_text: &m.matched_text,
+            route: "/process",
+        };
+        assert_eq!(eval(&expr, &ctx).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_unknown_field_errors() {
+        let expr = parse("pii.bogus == 1").unwrap();
+        let m = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &m.pii_type,
+            match_count: 1,
+            matched_text: &m.matched_text,
+            route: "/process",
+        };
+        assert_eq!(
+            eval(&expr, &ctx),
+            Err(PolicyError::UnknownField("pii.bogus".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_policy_resolve_first_match_wins() {
+        let policy = RedactionPolicy::new(RedactionStrategy::Mask)
+            .with_rule(r#"pii.type == "SSN""#, RedactionStrategy::Hash)
+            .unwrap()
+            .with_rule(r#"pii.type == "Email""#, RedactionStrategy::Token)
+            .unwrap();
+
+        let ssn = ssn_match();
+        let ssn_ctx = EvalContext {
+            pii_type: &ssn.pii_type,
+            match_count: 1,
+            matched_text: &ssn.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&ssn_ctx), RedactionStrategy::Hash);
+
+        let email = email_match();
+        let email_ctx = EvalContext {
+            pii_type: &email.pii_type,
+            match_count: 1,
+            matched_text: &email.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&email_ctx), RedactionStrategy::Token);
+    }
+
+    #[test]
+    fn test_policy_resolve_falls_back_to_default() {
+        let policy = RedactionPolicy::new(RedactionStrategy::Mask)
+            .with_rule(r#"pii.type == "SSN""#, RedactionStrategy::Hash)
+            .unwrap();
+
+        let email = email_match();
+        let ctx = EvalContext {
+            pii_type: &email.pii_type,
+            match_count: 1,
+            matched_text: &email.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&ctx), RedactionStrategy::Mask);
+    }
+
+    #[test]
+    fn test_policy_redact_applies_per_match_strategy() {
+        let policy = RedactionPolicy::new(RedactionStrategy::Mask)
+            .with_rule(r#"pii.type == "SSN""#, RedactionStrategy::Hash)
+            .unwrap();
+
+        let text = "SSN: 123-45-6789, Email: test@example.com";
+        let matches = vec![
+            PIIMatch::new(PIIType::SSN, 5, 16, "123-45-6789".to_string(), 0.95),
+            PIIMatch::new(
+                PIIType::Email,
+                26,
+                42,
+                "test@example.com".to_string(),
+                0.95,
+            ),
+        ];
+
+        let redacted = policy.redact(text, &matches, "/process");
+        assert_eq!(redacted.len(), 16, "hash is 16 hex chars");
+        assert!(redacted.contains("****************"), "email falls back to mask");
+    }
+
+    #[test]
+    fn test_from_type_map_routes_each_type_to_its_strategy() {
+        let mut type_strategies = HashMap::new();
+        type_strategies.insert(PIIType::SSN, RedactionStrategy::Hash);
+        type_strategies.insert(PIIType::ApiKey, RedactionStrategy::Remove);
+        let policy = RedactionPolicy::from_type_map(type_strategies, RedactionStrategy::Mask);
+
+        let ssn = ssn_match();
+        let ssn_ctx = EvalContext {
+            pii_type: &ssn.pii_type,
+            match_count: 1,
+            matched_text: &ssn.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&ssn_ctx), RedactionStrategy::Hash);
+
+        let email = email_match();
+        let email_ctx = EvalContext {
+            pii_type: &email.pii_type,
+            match_count: 1,
+            matched_text: &email.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&email_ctx), RedactionStrategy::Mask);
+    }
+
+    #[test]
+    fn test_redact_with_policy_applies_per_match_strategy() {
+        let mut type_strategies = HashMap::new();
+        type_strategies.insert(PIIType::SSN, RedactionStrategy::Hash);
+        let policy = RedactionPolicy::from_type_map(type_strategies, RedactionStrategy::Mask);
+
+        let text = "SSN: 123-45-6789, Email: test@example.com";
+        let matches = vec![
+            PIIMatch::new(PIIType::SSN, 5, 16, "123-45-6789".to_string(), 0.95),
+            PIIMatch::new(
+                PIIType::Email,
+                26,
+                42,
+                "test@example.com".to_string(),
+                0.95,
+            ),
+        ];
+
+        let redacted = redact_with_policy(text, &matches, &policy);
+        assert!(redacted.contains("****************"), "email falls back to mask");
+        assert!(!redacted.contains("123-45-6789"), "ssn is hashed, not left in place");
+    }
+
+    #[test]
+    fn test_from_rules_str_parses_multiple_rules() {
+        let policy = RedactionPolicy::from_rules_str(
+            r#"pii.type == "SSN" => hash; pii.type == "Email" => token"#,
+            RedactionStrategy::Mask,
+        )
+        .unwrap();
+
+        let ssn = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &ssn.pii_type,
+            match_count: 1,
+            matched_text: &ssn.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&ctx), RedactionStrategy::Hash);
+    }
+
+    #[test]
+    fn test_from_rules_str_empty_is_default_only() {
+        let policy = RedactionPolicy::from_rules_str("", RedactionStrategy::Token).unwrap();
+        let ssn = ssn_match();
+        let ctx = EvalContext {
+            pii_type: &ssn.pii_type,
+            match_count: 1,
+            matched_text: &ssn.matched_text,
+            route: "/process",
+        };
+        assert_eq!(policy.resolve(&ctx), RedactionStrategy::Token);
+    }
+
+    #[test]
+    fn test_from_rules_str_rejects_unknown_strategy() {
+        let err = RedactionPolicy::from_rules_str(
+            r#"pii.type == "SSN" => obliterate"#,
+            RedactionStrategy::Mask,
+        )
+        .unwrap_err();
+        assert_eq!(err, PolicyError::UnknownStrategy("obliterate".to_string()));
+    }
+
+    #[test]
+    fn test_from_rules_str_rejects_missing_separator() {
+        let err = RedactionPolicy::from_rules_str(r#"pii.type == "SSN""#, RedactionStrategy::Mask)
+            .unwrap_err();
+        assert!(matches!(err, PolicyError::MissingStrategySeparator(_)));
+    }
+
+    #[test]
+    fn test_parse_rejects_trailing_garbage() {
+        let err = parse(r#"pii.type == "SSN" )"#).unwrap_err();
+        assert!(matches!(err, PolicyError::UnexpectedToken(_)));
+    }
+}
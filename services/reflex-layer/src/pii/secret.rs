@@ -0,0 +1,115 @@
+//! Zeroizing containers for raw PII matches
+//!
+//! `PIIDetector::detect` returns `PIIMatch`, which holds the raw PII value in
+//! a plain `String` that can linger in freed heap memory after it's dropped.
+//! `SecretPIIMatch` wraps that value in a `Zeroizing<String>` so the backing
+//! bytes are scrubbed the moment the match is dropped, and `RedactedPIIMatch`
+//! goes further: it never exposes the raw value at all, only a masked
+//! rendering built from it.
+
+use zeroize::Zeroizing;
+
+use crate::pii::types::PIIType;
+
+/// A PII match whose raw text is scrubbed from memory when dropped
+///
+/// Behaves like `PIIMatch`, except `matched_text` is a `Zeroizing<String>`:
+/// its backing buffer is overwritten with zeroes as soon as the match goes
+/// out of scope, rather than left for the allocator to hand out unchanged.
+#[derive(Debug)]
+pub struct SecretPIIMatch {
+    /// Type of PII detected
+    pub pii_type: PIIType,
+    /// Start byte offset in the original text
+    pub start: usize,
+    /// End byte offset in the original text
+    pub end: usize,
+    /// The matched text, zeroized on drop
+    pub matched_text: Zeroizing<String>,
+    /// Confidence score (0.0-1.0)
+    pub confidence: f64,
+}
+
+impl SecretPIIMatch {
+    /// Wrap a raw matched value in a zeroize-on-drop container
+    pub fn new(
+        pii_type: PIIType,
+        start: usize,
+        end: usize,
+        matched_text: String,
+        confidence: f64,
+    ) -> Self {
+        Self {
+            pii_type,
+            start,
+            end,
+            matched_text: Zeroizing::new(matched_text),
+            confidence,
+        }
+    }
+}
+
+/// A PII match reduced to its location, type, and a masked rendering
+///
+/// Never holds the raw matched text: `PIIDetector::detect_redacted` builds
+/// `masked` directly from the match span and drops the plaintext before
+/// returning, so downstream logging/telemetry can record that PII was found
+/// and where without the plaintext ever reaching a caller-owned allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactedPIIMatch {
+    /// Type of PII detected
+    pub pii_type: PIIType,
+    /// Start byte offset in the original text
+    pub start: usize,
+    /// End byte offset in the original text
+    pub end: usize,
+    /// Masked rendering, e.g. `*******6789` keeping the last `keep_trailing` chars
+    pub masked: String,
+    /// Confidence score (0.0-1.0)
+    pub confidence: f64,
+}
+
+/// Build a masked rendering that keeps the last `keep_trailing` characters
+/// and replaces the rest with `*`
+///
+/// Mirrors the masking convention in `pii::redactor`'s partial replacement,
+/// but with a configurable number of trailing characters and operating on a
+/// borrowed value, so the caller never has to keep an owned copy of the
+/// secret around just to mask it.
+pub(crate) fn mask_keep_trailing(text: &str, keep_trailing: usize) -> String {
+    let len = text.len();
+    if len <= keep_trailing {
+        "*".repeat(len)
+    } else {
+        let prefix_len = len - keep_trailing;
+        format!("{}{}", "*".repeat(prefix_len), &text[prefix_len..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_keep_trailing() {
+        assert_eq!(mask_keep_trailing("123-45-6789", 4), "*******6789");
+    }
+
+    #[test]
+    fn test_mask_keep_trailing_short_text() {
+        assert_eq!(mask_keep_trailing("abc", 4), "***");
+    }
+
+    #[test]
+    fn test_mask_keep_trailing_zero_trailing() {
+        assert_eq!(mask_keep_trailing("abc", 0), "***");
+    }
+
+    #[test]
+    fn test_secret_pii_match_wraps_value() {
+        let m = SecretPIIMatch::new(PIIType::SSN, 0, 11, "123-45-6789".to_string(), 1.0);
+        assert_eq!(&*m.matched_text, "123-45-6789");
+        assert_eq!(m.start, 0);
+        assert_eq!(m.end, 11);
+    }
+}
@@ -33,6 +33,7 @@ mod integration_tests {
             pattern_set: PatternSet::Relaxed,
             enable_validation: false,
             enable_context: false,
+            ..Default::default()
         });
 
         // Test text with various PII types
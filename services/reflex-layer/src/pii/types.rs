@@ -26,6 +26,9 @@ pub enum PIIType {
     BitcoinAddress,
     /// Ethereum address (0x + 40 hex chars)
     EthereumAddress,
+    /// Bech32/bech32m crypto wallet identifier: native SegWit/Taproot
+    /// Bitcoin addresses (`bc1...`) and BOLT11 Lightning invoices (`lnbc...`)
+    CryptoWalletAddress,
     /// MAC address
     MacAddress,
     /// US Driver's License number
@@ -38,10 +41,18 @@ pub enum PIIType {
     BankAccount,
     /// US routing number (9 digits)
     RoutingNumber,
+    /// International Bank Account Number (ISO 13616)
+    Iban,
     /// Individual Taxpayer Identification Number (9XX-XX-XXXX)
     ITIN,
     /// Date of birth
     DateOfBirth,
+    /// PEM-encoded private key (RSA, EC, OpenSSH, DSA, or encrypted)
+    PrivateKey,
+    /// PEM-encoded X.509 certificate
+    Certificate,
+    /// JSON Web Token (header.payload.signature)
+    Jwt,
     /// Custom user-defined PII pattern
     Custom(String),
 }
@@ -58,14 +69,19 @@ impl fmt::Display for PIIType {
             PIIType::ApiKey => write!(f, "ApiKey"),
             PIIType::BitcoinAddress => write!(f, "BitcoinAddress"),
             PIIType::EthereumAddress => write!(f, "EthereumAddress"),
+            PIIType::CryptoWalletAddress => write!(f, "CryptoWalletAddress"),
             PIIType::MacAddress => write!(f, "MacAddress"),
             PIIType::DriversLicense => write!(f, "DriversLicense"),
             PIIType::Passport => write!(f, "Passport"),
             PIIType::MedicalRecordNumber => write!(f, "MedicalRecordNumber"),
             PIIType::BankAccount => write!(f, "BankAccount"),
             PIIType::RoutingNumber => write!(f, "RoutingNumber"),
+            PIIType::Iban => write!(f, "Iban"),
             PIIType::ITIN => write!(f, "ITIN"),
             PIIType::DateOfBirth => write!(f, "DateOfBirth"),
+            PIIType::PrivateKey => write!(f, "PrivateKey"),
+            PIIType::Certificate => write!(f, "Certificate"),
+            PIIType::Jwt => write!(f, "Jwt"),
             PIIType::Custom(name) => write!(f, "Custom({})", name),
         }
     }
@@ -84,6 +100,17 @@ pub struct PIIMatch {
     pub matched_text: String,
     /// Confidence score (0.0-1.0)
     pub confidence: f64,
+    /// CIDR prefix length, for `IPv4`/`IPv6` matches written in CIDR notation
+    /// (e.g. the `24` in `192.168.0.0/24`). `None` for a single host address
+    /// or any non-IP match.
+    #[serde(default)]
+    pub cidr_mask: Option<u8>,
+    /// Blockchain network/address-family tag, for a `BitcoinAddress`,
+    /// `CryptoWalletAddress`, or `EthereumAddress` match whose checksum
+    /// identified one. `None` for any non-crypto match, or a crypto match
+    /// whose network couldn't be determined (e.g. a Lightning invoice).
+    #[serde(default)]
+    pub crypto_network: Option<crate::pii::validator::CryptoNetworkTag>,
 }
 
 impl PIIMatch {
@@ -101,9 +128,25 @@ impl PIIMatch {
             end,
             matched_text,
             confidence,
+            cidr_mask: None,
+            crypto_network: None,
         }
     }
 
+    /// Attach a CIDR prefix length, for an `IPv4`/`IPv6` match that turned
+    /// out to be a subnet rather than a single host
+    pub fn with_cidr_mask(mut self, mask: u8) -> Self {
+        self.cidr_mask = Some(mask);
+        self
+    }
+
+    /// Attach a blockchain network/address-family tag, for a crypto wallet
+    /// match whose checksum identified one
+    pub fn with_crypto_network(mut self, network: crate::pii::validator::CryptoNetworkTag) -> Self {
+        self.crypto_network = Some(network);
+        self
+    }
+
     /// Get the length of the matched text
     pub fn len(&self) -> usize {
         self.end - self.start
@@ -136,6 +179,15 @@ pub struct PIIConfig {
     pub enable_validation: bool,
     /// Enable context-aware detection
     pub enable_context: bool,
+    /// Also run patterns against a homoglyph-normalized view of the text, to
+    /// catch Cyrillic/Greek/fullwidth lookalike evasion (e.g. `с1а1is`)
+    pub enable_homoglyph_normalization: bool,
+    /// Also run patterns against a further leet-folded view (`1`->`i`, `0`->`o`,
+    /// etc.), on top of homoglyph normalization. Off by default: folding digits
+    /// into letters is much more prone to false positives than homoglyph mapping.
+    pub enable_leet_folding: bool,
+    /// Which crypto address families are active
+    pub crypto: CryptoConfig,
 }
 
 impl Default for PIIConfig {
@@ -144,6 +196,34 @@ impl Default for PIIConfig {
             pattern_set: PatternSet::Standard,
             enable_validation: true,
             enable_context: false,
+            enable_homoglyph_normalization: true,
+            enable_leet_folding: false,
+            crypto: CryptoConfig::default(),
+        }
+    }
+}
+
+/// Which blockchain address families the crypto-related patterns should
+/// match, letting a deployment disable chains it doesn't care about (cutting
+/// down on false positives from the others) without reaching for a whole
+/// different `PatternSet`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CryptoConfig {
+    /// Legacy base58check `1.../3...` (and testnet `m.../n.../2...`) Bitcoin addresses
+    pub enable_bitcoin_base58: bool,
+    /// Bech32/bech32m `bc1.../tb1...` SegWit/Taproot Bitcoin addresses, and
+    /// BOLT11 Lightning invoices (`lnbc...`), which share the same `PIIType`
+    pub enable_bitcoin_bech32: bool,
+    /// EIP-55 `0x...` Ethereum addresses
+    pub enable_ethereum: bool,
+}
+
+impl Default for CryptoConfig {
+    fn default() -> Self {
+        Self {
+            enable_bitcoin_base58: true,
+            enable_bitcoin_bech32: true,
+            enable_ethereum: true,
         }
     }
 }
@@ -170,6 +250,14 @@ mod tests {
         assert_eq!(m.end, 20);
         assert_eq!(m.len(), 20);
         assert!(!m.is_empty());
+        assert_eq!(m.cidr_mask, None);
+    }
+
+    #[test]
+    fn test_pii_match_with_cidr_mask() {
+        let m = PIIMatch::new(PIIType::IPv4, 0, 14, "192.168.0.0/24".to_string(), 0.9)
+            .with_cidr_mask(24);
+        assert_eq!(m.cidr_mask, Some(24));
     }
 
     #[test]
@@ -184,5 +272,7 @@ mod tests {
         assert_eq!(config.pattern_set, PatternSet::Standard);
         assert!(config.enable_validation);
         assert!(!config.enable_context);
+        assert!(config.enable_homoglyph_normalization);
+        assert!(!config.enable_leet_folding);
     }
 }
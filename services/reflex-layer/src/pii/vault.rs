@@ -0,0 +1,280 @@
+// Reversible tokenization vault
+//
+// `redact_with_context`'s `Token` strategy is one-way: the redacted text
+// carries a stable ID per value, but the original value itself is gone.
+// `redact_reversible` additionally records each emitted token's original
+// value in a `RedactionVault`, encrypted at rest under a caller-supplied
+// key, so an authorized caller holding that key can later recover the exact
+// original document via `restore`. Everyone else only ever sees tokens.
+
+use std::collections::HashMap;
+
+use crate::cache::{CacheCrypto, CacheError};
+use crate::pii::redactor::{resolve_overlaps, safe_replace_range};
+use crate::pii::types::{PIIMatch, PIIType};
+use crate::pii::RedactionContext;
+
+/// A single recoverable token's vault record
+#[derive(Debug, Clone)]
+struct VaultEntry {
+    pii_type: PIIType,
+    start: usize,
+    end: usize,
+    /// Base64-encoded `nonce || ciphertext`, as produced by `CacheCrypto::encrypt`
+    encrypted_value: String,
+}
+
+/// Holds the original value behind each reversible token emitted by
+/// [`redact_reversible`], encrypted under the caller's [`CacheCrypto`] key so
+/// the vault is safe to store or transmit at rest
+///
+/// The token string (e.g. `<EMAIL-1>`) is the lookup handle into the vault,
+/// matching the placeholder left in the redacted text.
+#[derive(Debug, Clone, Default)]
+pub struct RedactionVault {
+    entries: HashMap<String, VaultEntry>,
+}
+
+impl RedactionVault {
+    /// Number of recoverable tokens recorded in this vault
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether this vault has no recorded tokens
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over recorded tokens with their `PIIType` and original span,
+    /// without decrypting any values
+    ///
+    /// Useful for auditing what was redacted (counts, types, positions)
+    /// without holding the decryption key.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &PIIType, usize, usize)> {
+        self.entries
+            .iter()
+            .map(|(token, entry)| (token.as_str(), &entry.pii_type, entry.start, entry.end))
+    }
+
+    /// Reconstruct the original document from `text` produced by
+    /// [`redact_reversible`], replacing each reversible token this vault
+    /// knows about with its decrypted original value
+    ///
+    /// Equivalent to calling [`restore`] with `self` as the vault; provided
+    /// as a method for callers that already hold a `&RedactionVault` and
+    /// find that more natural than the free function.
+    pub fn detokenize(&self, text: &str, crypto: &CacheCrypto) -> String {
+        restore(text, self, crypto)
+    }
+}
+
+/// Redact `text`, replacing each match with a stable `Token`-strategy
+/// placeholder (via `ctx`, the same [`RedactionContext`] used by
+/// `redact_with_context`) and recording its original value in a
+/// `RedactionVault`, encrypted under `crypto`
+///
+/// Overlapping/nested matches are resolved exactly as in [`redact`](crate::pii::redact),
+/// keeping only the longest-preferred, non-overlapping regions.
+pub fn redact_reversible(
+    text: &str,
+    matches: &[PIIMatch],
+    ctx: &mut RedactionContext,
+    crypto: &CacheCrypto,
+) -> Result<(String, RedactionVault), CacheError> {
+    if matches.is_empty() {
+        return Ok((text.to_string(), RedactionVault::default()));
+    }
+
+    let mut resolved = resolve_overlaps(matches);
+    resolved.sort_by_key(|m| std::cmp::Reverse(m.start));
+
+    let mut result = text.to_string();
+    let mut vault = RedactionVault::default();
+
+    for pii_match in resolved {
+        let id = ctx.id_for(&pii_match.pii_type, &pii_match.matched_text);
+        let token = format!("<{}-{}>", pii_match.pii_type.to_string().to_uppercase(), id);
+
+        // The token itself is bound as AEAD associated data, so a captured
+        // vault entry can't be replayed under a different token.
+        let encrypted_value = crypto.encrypt(&token, &pii_match.matched_text)?;
+
+        vault.entries.insert(
+            token.clone(),
+            VaultEntry {
+                pii_type: pii_match.pii_type.clone(),
+                start: pii_match.start,
+                end: pii_match.end,
+                encrypted_value,
+            },
+        );
+
+        safe_replace_range(&mut result, pii_match.start, pii_match.end, &token);
+    }
+
+    Ok((result, vault))
+}
+
+/// Reconstruct the original document from `redacted` text produced by
+/// [`redact_reversible`], replacing each reversible token with its decrypted
+/// original value from `vault`
+///
+/// A token whose vault entry fails to decrypt under `crypto` (wrong key, or
+/// a vault that doesn't match this text) is left in place rather than
+/// silently dropped, so a partial-key mismatch is visible in the output.
+pub fn restore(redacted: &str, vault: &RedactionVault, crypto: &CacheCrypto) -> String {
+    let mut result = redacted.to_string();
+
+    for (token, entry) in &vault.entries {
+        if let Some(plaintext) = crypto.decrypt(token, &entry.encrypted_value) {
+            result = result.replace(token.as_str(), &plaintext);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CACHE_CRYPTO_KEY_LEN;
+    use crate::pii::types::PIIMatch;
+
+    fn test_crypto() -> CacheCrypto {
+        CacheCrypto::new(&[3u8; CACHE_CRYPTO_KEY_LEN])
+    }
+
+    #[test]
+    fn test_redact_reversible_then_restore_round_trips() {
+        let text = "Contact: test@example.com";
+        let matches = vec![PIIMatch::new(
+            PIIType::Email,
+            9,
+            25,
+            "test@example.com".to_string(),
+            0.95,
+        )];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        assert_eq!(redacted, "Contact: <EMAIL-1>");
+        assert_eq!(vault.len(), 1);
+
+        let restored = restore(&redacted, &vault, &crypto);
+        assert_eq!(restored, text);
+    }
+
+    #[test]
+    fn test_vault_detokenize_matches_restore() {
+        let text = "Contact: test@example.com";
+        let matches = vec![PIIMatch::new(
+            PIIType::Email,
+            9,
+            25,
+            "test@example.com".to_string(),
+            0.95,
+        )];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        assert_eq!(vault.detokenize(&redacted, &crypto), text);
+    }
+
+    #[test]
+    fn test_redact_reversible_reuses_stable_token_for_repeated_value() {
+        let text = "From test@example.com to test@example.com";
+        let matches = vec![
+            PIIMatch::new(PIIType::Email, 5, 21, "test@example.com".to_string(), 0.95),
+            PIIMatch::new(
+                PIIType::Email,
+                25,
+                41,
+                "test@example.com".to_string(),
+                0.95,
+            ),
+        ];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        assert_eq!(redacted, "From <EMAIL-1> to <EMAIL-1>");
+        assert_eq!(vault.len(), 1, "both mentions share one vault entry");
+
+        let restored = restore(&redacted, &vault, &crypto);
+        assert_eq!(restored, text);
+    }
+
+    #[test]
+    fn test_vault_is_not_plaintext() {
+        let text = "SSN: 123-45-6789";
+        let matches = vec![PIIMatch::new(
+            PIIType::SSN,
+            5,
+            16,
+            "123-45-6789".to_string(),
+            0.95,
+        )];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (_, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        let (_, _, _, _) = vault.entries().next().unwrap();
+        // The only way to recover "123-45-6789" is via `restore` with the
+        // matching key; nothing in the vault's public surface exposes it.
+        assert!(!format!("{:?}", vault).contains("123-45-6789"));
+    }
+
+    #[test]
+    fn test_restore_wrong_key_leaves_token_in_place() {
+        let text = "SSN: 123-45-6789";
+        let matches = vec![PIIMatch::new(
+            PIIType::SSN,
+            5,
+            16,
+            "123-45-6789".to_string(),
+            0.95,
+        )];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        let wrong_crypto = CacheCrypto::new(&[9u8; CACHE_CRYPTO_KEY_LEN]);
+        let restored = restore(&redacted, &vault, &wrong_crypto);
+        assert_eq!(restored, redacted, "decrypt failure leaves the token untouched");
+    }
+
+    #[test]
+    fn test_redact_reversible_resolves_overlaps() {
+        let text = "test@example.com";
+        let matches = vec![
+            PIIMatch::new(PIIType::Email, 0, 16, "test@example.com".to_string(), 0.95),
+            PIIMatch::new(PIIType::Email, 5, 12, "example".to_string(), 0.9),
+        ];
+
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &matches, &mut ctx, &crypto).unwrap();
+
+        assert_eq!(redacted, "<EMAIL-1>");
+        assert_eq!(vault.len(), 1);
+    }
+
+    #[test]
+    fn test_redact_reversible_empty_matches_returns_text_unchanged() {
+        let text = "nothing to redact here";
+        let crypto = test_crypto();
+        let mut ctx = RedactionContext::new();
+        let (redacted, vault) = redact_reversible(text, &[], &mut ctx, &crypto).unwrap();
+
+        assert_eq!(redacted, text);
+        assert!(vault.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+//! API-key-driven rate-limit tier resolution
+//!
+//! Maps a caller-supplied `x-api-key` header to the [`RateLimitTier`] an
+//! operator configured for that key, so a paying customer can be granted a
+//! higher limit without a code change. A missing or unrecognized key falls
+//! back to [`RateLimitTier::Free`], the same tier unauthenticated callers
+//! get today.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::ratelimit::types::{RateLimitError, RateLimitTier};
+
+/// Resolves an `x-api-key` header value to its configured [`RateLimitTier`]
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyTierTable {
+    tiers: HashMap<String, RateLimitTier>,
+}
+
+impl ApiKeyTierTable {
+    /// Build a table from already-parsed `(api_key, tier)` pairs
+    pub fn new(tiers: HashMap<String, RateLimitTier>) -> Self {
+        Self { tiers }
+    }
+
+    /// Parse a semicolon-separated `<api_key>=<tier>` rule string (e.g.
+    /// `"sk-live-abc=pro;sk-live-xyz=enterprise"`) into a table, mirroring
+    /// the `<condition> => <strategy>` rule-string convention used by
+    /// [`RedactionPolicy::from_rules_str`](crate::pii::RedactionPolicy::from_rules_str).
+    /// Blank entries are skipped; an empty `rules` string produces an empty
+    /// table.
+    pub fn from_rules_str(rules: &str) -> Result<Self, RateLimitError> {
+        let mut tiers = HashMap::new();
+
+        for entry in rules.split(';') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            let (key, tier_name) = entry.trim().split_once('=').ok_or_else(|| {
+                RateLimitError::Config(format!(
+                    "invalid API key tier rule '{}' (expected '<api_key>=<tier>')",
+                    entry
+                ))
+            })?;
+
+            let tier = RateLimitTier::from_str(tier_name.trim())?;
+            tiers.insert(key.trim().to_string(), tier);
+        }
+
+        Ok(Self { tiers })
+    }
+
+    /// Resolve an API key to its configured tier, falling back to `Free`
+    /// when the key is absent or unrecognized
+    pub fn resolve(&self, api_key: Option<&str>) -> RateLimitTier {
+        api_key
+            .and_then(|key| self.tiers.get(key))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_known_key_returns_configured_tier() {
+        let mut tiers = HashMap::new();
+        tiers.insert("sk-live-pro".to_string(), RateLimitTier::Pro);
+        let table = ApiKeyTierTable::new(tiers);
+
+        assert_eq!(table.resolve(Some("sk-live-pro")), RateLimitTier::Pro);
+    }
+
+    #[test]
+    fn test_resolve_missing_or_absent_key_falls_back_to_free() {
+        let table = ApiKeyTierTable::new(HashMap::new());
+
+        assert_eq!(table.resolve(Some("unknown-key")), RateLimitTier::Free);
+        assert_eq!(table.resolve(None), RateLimitTier::Free);
+    }
+
+    #[test]
+    fn test_from_rules_str_parses_multiple_entries() {
+        let table =
+            ApiKeyTierTable::from_rules_str("sk-live-pro=pro;sk-live-ent=enterprise").unwrap();
+
+        assert_eq!(table.resolve(Some("sk-live-pro")), RateLimitTier::Pro);
+        assert_eq!(
+            table.resolve(Some("sk-live-ent")),
+            RateLimitTier::Enterprise
+        );
+    }
+
+    #[test]
+    fn test_from_rules_str_empty_string_is_empty_table() {
+        let table = ApiKeyTierTable::from_rules_str("").unwrap();
+        assert_eq!(table.resolve(Some("anything")), RateLimitTier::Free);
+    }
+
+    #[test]
+    fn test_from_rules_str_skips_blank_entries() {
+        let table = ApiKeyTierTable::from_rules_str("sk-live-pro=pro;;").unwrap();
+        assert_eq!(table.resolve(Some("sk-live-pro")), RateLimitTier::Pro);
+    }
+
+    #[test]
+    fn test_from_rules_str_rejects_missing_equals() {
+        assert!(ApiKeyTierTable::from_rules_str("sk-live-pro").is_err());
+    }
+
+    #[test]
+    fn test_from_rules_str_rejects_unknown_tier_name() {
+        assert!(ApiKeyTierTable::from_rules_str("sk-live-pro=gold").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+//! Low-level rate-limit storage backend
+//!
+//! Mirrors [`cache::CacheBackend`](crate::cache::CacheBackend)'s
+//! get/set/incr/expire shape, scoped to rate limiting: `incr` is the atomic
+//! refill-and-consume hot path (what `RedisRateLimiter`'s Lua script
+//! already does), `get`/`set` inspect or overwrite a bucket's raw token
+//! count (used by `reset`), and `expire` lets an idle bucket be
+//! garbage-collected instead of living forever. `RedisRateLimiter`
+//! implements it against Redis; [`InMemoryRateLimiter`](crate::ratelimit::InMemoryRateLimiter)
+//! implements it against a local token bucket per key, so a failed Redis
+//! health check at startup can degrade rate limiting to a real
+//! (non-distributed) limiter instead of the service half-working.
+
+use async_trait::async_trait;
+
+use crate::ratelimit::types::{RateLimitConfig, RateLimitError, RateLimitKey, RateLimitResult};
+
+/// Raw rate-limit bucket storage primitives, implemented by a live Redis
+/// connection or an in-memory fallback
+#[async_trait]
+pub trait RateLimitBackend: Send + Sync {
+    /// Current token count for `key`'s bucket, or `None` if it hasn't been
+    /// touched yet
+    async fn get(&self, key: &RateLimitKey) -> Result<Option<f64>, RateLimitError>;
+
+    /// Overwrite `key`'s bucket to hold exactly `tokens` (used by `reset`)
+    async fn set(&self, key: &RateLimitKey, tokens: f64) -> Result<(), RateLimitError>;
+
+    /// Atomically refill `key`'s bucket per `config` and attempt to consume
+    /// `tokens`
+    async fn incr(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> Result<RateLimitResult, RateLimitError>;
+
+    /// Set an idle-eviction TTL on `key`'s bucket
+    ///
+    /// Returns `false` if the bucket doesn't exist.
+    async fn expire(&self, key: &RateLimitKey, ttl_secs: u64) -> Result<bool, RateLimitError>;
+}
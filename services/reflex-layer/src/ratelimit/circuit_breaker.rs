@@ -0,0 +1,328 @@
+//! Circuit-breaker fallback from the distributed Redis rate limiter to a
+//! local in-memory one
+//!
+//! [`backend::RateLimitBackend`](crate::ratelimit::backend)'s doc comment
+//! already describes falling back to [`InMemoryRateLimiter`] when a
+//! startup Redis health check fails; this module handles the harder case
+//! of Redis going unreachable *while already running*, where the choice
+//! up to now has been between fail-open (every request gets through
+//! unchecked) and fail-closed (a Redis blip becomes a full outage).
+//! [`CircuitBreakerRateLimiter`] instead degrades: after
+//! [`CircuitBreakerRateLimiter::with_failure_threshold`] consecutive Redis
+//! failures it trips open and serves every check from a local
+//! [`InMemoryRateLimiter`] (so limits are enforced per-node instead of
+//! cluster-wide), then periodically probes Redis again and closes the
+//! circuit the moment a probe succeeds.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::ratelimit::backend::RateLimitBackend;
+use crate::ratelimit::clock::{Clock, SystemClock};
+use crate::ratelimit::in_memory::InMemoryRateLimiter;
+use crate::ratelimit::redis_limiter::RedisRateLimiter;
+use crate::ratelimit::types::{RateLimitConfig, RateLimitError, RateLimitKey, RateLimitResult};
+
+/// Consecutive Redis failures before the circuit trips open
+const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long to stay open before probing Redis again
+const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Whether a [`CircuitBreakerRateLimiter`] is currently enforcing limits
+/// cluster-wide against Redis, or has degraded to a per-node local bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimiterMode {
+    /// Checking against Redis -- limits are enforced cluster-wide
+    Distributed,
+    /// Redis is unreachable; checking against a local in-memory bucket --
+    /// limits are only enforced per-node until Redis recovers
+    LocalFallback,
+}
+
+/// Wraps a [`RedisRateLimiter`] with a local [`InMemoryRateLimiter`]
+/// fallback, switching between them based on Redis's recent health
+///
+/// While closed, every check is attempted against Redis first; a failure
+/// both serves that single request from the local fallback (so the caller
+/// never sees a hard error just because of a transient blip) and counts
+/// toward tripping the circuit. Once `failure_threshold` consecutive
+/// failures accumulate, the circuit opens and every subsequent check is
+/// served locally without even attempting Redis, until `probe_interval`
+/// has elapsed, at which point the next check is allowed through as a
+/// probe -- a probe success closes the circuit immediately, a probe
+/// failure extends the open window by another `probe_interval`.
+pub struct CircuitBreakerRateLimiter {
+    redis: Arc<RedisRateLimiter>,
+    fallback: Arc<InMemoryRateLimiter>,
+    failure_threshold: u32,
+    probe_interval: Duration,
+    clock: Arc<dyn Clock>,
+    consecutive_failures: AtomicU32,
+    /// `None` while the circuit is closed (serving from Redis); `Some(t)`
+    /// once it's open, where `t` is the instant of the most recent trip or
+    /// failed probe, used to schedule the next probe.
+    opened_at: Mutex<Option<Instant>>,
+}
+
+impl CircuitBreakerRateLimiter {
+    /// Wrap `redis` with a fresh local fallback, using the default
+    /// 5-consecutive-failure threshold and 10-second probe interval
+    pub fn new(redis: Arc<RedisRateLimiter>) -> Self {
+        Self {
+            redis,
+            fallback: Arc::new(InMemoryRateLimiter::new()),
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            probe_interval: DEFAULT_PROBE_INTERVAL,
+            clock: Arc::new(SystemClock),
+            consecutive_failures: AtomicU32::new(0),
+            opened_at: Mutex::new(None),
+        }
+    }
+
+    /// Trip open after `threshold` consecutive failures instead of the
+    /// default
+    pub fn with_failure_threshold(self, threshold: u32) -> Self {
+        Self {
+            failure_threshold: threshold,
+            ..self
+        }
+    }
+
+    /// Probe Redis every `interval` while open instead of the default
+    pub fn with_probe_interval(self, interval: Duration) -> Self {
+        Self {
+            probe_interval: interval,
+            ..self
+        }
+    }
+
+    /// Use a custom time source for scheduling probes instead of the real
+    /// wall clock, so tests can advance time deterministically
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        Self { clock, ..self }
+    }
+
+    /// The local fallback backend, so a caller can wire up its own
+    /// idle-bucket cleanup (see [`InMemoryRateLimiter::spawn_cleanup_task`])
+    pub fn fallback(&self) -> &Arc<InMemoryRateLimiter> {
+        &self.fallback
+    }
+
+    /// Whether checks are currently served from Redis (`Distributed`) or
+    /// the local fallback (`LocalFallback`)
+    pub fn mode(&self) -> RateLimiterMode {
+        match *self.opened_at.lock().unwrap() {
+            Some(_) => RateLimiterMode::LocalFallback,
+            None => RateLimiterMode::Distributed,
+        }
+    }
+
+    /// Number of consecutive Redis failures observed so far (resets to
+    /// zero on any success)
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures.load(Ordering::Relaxed)
+    }
+
+    /// Whether Redis should be attempted this call: always when the
+    /// circuit is closed, and on an open circuit only once `probe_interval`
+    /// has elapsed since it was last opened (or last failed a probe)
+    fn should_try_redis(&self) -> bool {
+        match *self.opened_at.lock().unwrap() {
+            None => true,
+            Some(opened_at) => self.clock.now().duration_since(opened_at) >= self.probe_interval,
+        }
+    }
+
+    /// Record a successful Redis call: reset the failure count and close
+    /// the circuit, whether this was a normal call or a recovery probe
+    fn on_success(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.opened_at.lock().unwrap() = None;
+    }
+
+    /// Record a failed Redis call: bump the failure count, and either trip
+    /// the circuit open (if the threshold is now reached) or, if it was
+    /// already open, push the next probe another `probe_interval` out
+    fn on_failure(&self) {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut opened_at = self.opened_at.lock().unwrap();
+        if opened_at.is_some() || failures >= self.failure_threshold {
+            *opened_at = Some(self.clock.now());
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for CircuitBreakerRateLimiter {
+    async fn get(&self, key: &RateLimitKey) -> Result<Option<f64>, RateLimitError> {
+        if self.should_try_redis() {
+            match self.redis.get(key).await {
+                Ok(value) => {
+                    self.on_success();
+                    return Ok(value);
+                }
+                Err(_) => self.on_failure(),
+            }
+        }
+        self.fallback.get(key).await
+    }
+
+    async fn set(&self, key: &RateLimitKey, tokens: f64) -> Result<(), RateLimitError> {
+        if self.should_try_redis() {
+            match self.redis.set(key, tokens).await {
+                Ok(()) => {
+                    self.on_success();
+                    return Ok(());
+                }
+                Err(_) => self.on_failure(),
+            }
+        }
+        self.fallback.set(key, tokens).await
+    }
+
+    async fn incr(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> Result<RateLimitResult, RateLimitError> {
+        if self.should_try_redis() {
+            match self.redis.incr(key, config, tokens).await {
+                Ok(result) => {
+                    self.on_success();
+                    return Ok(result);
+                }
+                Err(_) => self.on_failure(),
+            }
+        }
+        self.fallback.incr(key, config, tokens).await
+    }
+
+    async fn expire(&self, key: &RateLimitKey, ttl_secs: u64) -> Result<bool, RateLimitError> {
+        if self.should_try_redis() {
+            match self.redis.expire(key, ttl_secs).await {
+                Ok(value) => {
+                    self.on_success();
+                    return Ok(value);
+                }
+                Err(_) => self.on_failure(),
+            }
+        }
+        self.fallback.expire(key, ttl_secs).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedisConfig;
+    use crate::ratelimit::clock::FakeClock;
+    use crate::redis_client::RedisClient;
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig {
+            capacity: 5,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        }
+    }
+
+    /// A `RedisRateLimiter` pointed at a connection that can never succeed,
+    /// standing in for an unreachable Redis without needing a live server.
+    /// A short timeout keeps a failed attempt from hanging the test.
+    fn unreachable_redis() -> Arc<RedisRateLimiter> {
+        let config = RedisConfig {
+            url: "redis://127.0.0.1:1".to_string(),
+            pool_size: 1,
+            connection_timeout_ms: 200,
+            command_timeout_ms: 200,
+            cache_ttl_secs: 300,
+            ..Default::default()
+        };
+        let redis = RedisClient::new(config).expect("pool construction doesn't itself connect");
+        Arc::new(RedisRateLimiter::new(Arc::new(redis)))
+    }
+
+    // These attempt (and expect to fail) a real TCP connection, so they're
+    // excluded from the default run like the other Redis-requiring tests in
+    // this module (see `setup_limiter` in `redis_limiter.rs`).
+
+    #[tokio::test]
+    #[ignore] // Attempts a real (failing) Redis connection
+    async fn test_starts_closed_in_distributed_mode() {
+        let breaker = CircuitBreakerRateLimiter::new(unreachable_redis());
+        assert_eq!(breaker.mode(), RateLimiterMode::Distributed);
+    }
+
+    #[tokio::test]
+    #[ignore] // Attempts a real (failing) Redis connection
+    async fn test_falls_back_on_a_single_redis_failure_without_erroring() {
+        let breaker = CircuitBreakerRateLimiter::new(unreachable_redis());
+        let key = RateLimitKey::User("alice".to_string());
+
+        // One failed attempt still returns a usable result, served locally.
+        let result = breaker.incr(&key, &config(), 1.0).await.unwrap();
+        assert!(result.is_allowed());
+        assert_eq!(breaker.consecutive_failures(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore] // Attempts a real (failing) Redis connection
+    async fn test_trips_open_after_consecutive_failure_threshold() {
+        let breaker =
+            CircuitBreakerRateLimiter::new(unreachable_redis()).with_failure_threshold(3);
+        let key = RateLimitKey::User("bob".to_string());
+
+        for _ in 0..2 {
+            breaker.incr(&key, &config(), 1.0).await.unwrap();
+            assert_eq!(breaker.mode(), RateLimiterMode::Distributed);
+        }
+
+        breaker.incr(&key, &config(), 1.0).await.unwrap();
+        assert_eq!(breaker.mode(), RateLimiterMode::LocalFallback);
+    }
+
+    #[tokio::test]
+    #[ignore] // Attempts a real (failing) Redis connection
+    async fn test_open_circuit_skips_redis_until_probe_interval_elapses() {
+        let clock = FakeClock::new();
+        let breaker = CircuitBreakerRateLimiter::new(unreachable_redis())
+            .with_failure_threshold(1)
+            .with_probe_interval(Duration::from_secs(30))
+            .with_clock(Arc::new(clock.clone()));
+        let key = RateLimitKey::User("carol".to_string());
+
+        breaker.incr(&key, &config(), 1.0).await.unwrap();
+        assert_eq!(breaker.mode(), RateLimiterMode::LocalFallback);
+        assert!(!breaker.should_try_redis());
+
+        clock.advance(Duration::from_secs(10));
+        assert!(!breaker.should_try_redis());
+
+        clock.advance(Duration::from_secs(20));
+        assert!(breaker.should_try_redis());
+    }
+
+    #[tokio::test]
+    #[ignore] // Attempts a real (failing) Redis connection
+    async fn test_fallback_buckets_stay_independent_per_key() {
+        let breaker = CircuitBreakerRateLimiter::new(unreachable_redis());
+        let alice = RateLimitKey::User("alice2".to_string());
+        let bob = RateLimitKey::User("bob2".to_string());
+        let small = RateLimitConfig {
+            capacity: 1,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        assert!(breaker.incr(&alice, &small, 1.0).await.unwrap().is_allowed());
+        assert!(breaker.incr(&alice, &small, 1.0).await.unwrap().is_limited());
+        assert!(breaker.incr(&bob, &small, 1.0).await.unwrap().is_allowed());
+    }
+}
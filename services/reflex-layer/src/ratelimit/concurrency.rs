@@ -0,0 +1,184 @@
+//! Distributed in-flight concurrency limiting
+//!
+//! A token bucket caps request *rate* but says nothing about how many
+//! requests for a key are executing *at the same time*, which is what
+//! actually protects an upstream LLM provider from overload. Reservations
+//! are tracked in Redis as a simple counter with a self-healing TTL: every
+//! [`ConcurrencyLimiter::acquire`] atomically increments the counter and
+//! rejects once it exceeds `max_concurrent`, and the returned
+//! [`ConcurrencyPermit`] decrements it again on drop so a permit is always
+//! released, even if the caller returns early via `?` or panics.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use redis::Script;
+use tracing::{debug, warn};
+
+use crate::ratelimit::types::{RateLimitError, RateLimitKey};
+use crate::redis_client::RedisClient;
+
+/// How long an unreleased permit lingers before Redis expires it on its
+/// own, bounding the damage from a caller that crashes mid-request instead
+/// of dropping its guard.
+const DEFAULT_PERMIT_TTL: Duration = Duration::from_secs(300);
+
+/// Distributed in-flight concurrency limiter
+///
+/// Counts requests currently executing per [`RateLimitKey`] in Redis and
+/// rejects once the count exceeds a configured maximum, independent of
+/// the token-bucket rate limiters.
+pub struct ConcurrencyLimiter {
+    redis: Arc<RedisClient>,
+    acquire_script: Script,
+    release_script: Script,
+    permit_ttl: Duration,
+}
+
+impl ConcurrencyLimiter {
+    /// Create a new concurrency limiter with the default permit TTL
+    pub fn new(redis: Arc<RedisClient>) -> Self {
+        Self::with_permit_ttl(redis, DEFAULT_PERMIT_TTL)
+    }
+
+    /// Create a new concurrency limiter whose permits self-heal after
+    /// `permit_ttl` if never explicitly released
+    pub fn with_permit_ttl(redis: Arc<RedisClient>, permit_ttl: Duration) -> Self {
+        Self {
+            redis,
+            acquire_script: Script::new(include_str!("concurrency_acquire.lua")),
+            release_script: Script::new(include_str!("concurrency_release.lua")),
+            permit_ttl,
+        }
+    }
+
+    /// Attempt to acquire one in-flight permit for `key`
+    ///
+    /// On success, returns a [`ConcurrencyPermit`] that must be held for
+    /// the lifetime of the request; dropping it (including via an early
+    /// `?` return or a panic) releases the permit.
+    pub async fn acquire(
+        &self,
+        key: &RateLimitKey,
+        max_concurrent: u64,
+    ) -> Result<ConcurrencyPermit, RateLimitError> {
+        let redis_key = format!("ratelimit:concurrency:{}", key.to_redis_key());
+        let mut conn = self.redis.get_connection(&redis_key).await?;
+
+        let result: Vec<i64> = self
+            .acquire_script
+            .key(&redis_key)
+            .arg(max_concurrent)
+            .arg(self.permit_ttl.as_secs())
+            .invoke_async(&mut *conn)
+            .await
+            .map_err(|e| {
+                RateLimitError::ScriptError(format!("Concurrency acquire script error: {}", e))
+            })?;
+
+        let allowed = result[0] == 1;
+        let in_flight = result[1].max(0) as u64;
+
+        if allowed {
+            debug!("Concurrency permit ACQUIRED: key={redis_key}, in_flight={in_flight}");
+            Ok(ConcurrencyPermit {
+                redis: self.redis.clone(),
+                release_script: self.release_script.clone(),
+                redis_key,
+            })
+        } else {
+            debug!(
+                "Concurrency permit DENIED: key={redis_key}, in_flight={in_flight}, max={max_concurrent}"
+            );
+            Err(RateLimitError::ConcurrencyLimited {
+                in_flight,
+                max: max_concurrent,
+            })
+        }
+    }
+}
+
+/// A held in-flight concurrency permit
+///
+/// Releases itself on drop via a detached background task (Redis calls are
+/// async, but `Drop::drop` isn't), so the permit is always returned whether
+/// the caller finishes normally, returns early, or panics.
+pub struct ConcurrencyPermit {
+    redis: Arc<RedisClient>,
+    release_script: Script,
+    redis_key: String,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let redis = self.redis.clone();
+        let script = self.release_script.clone();
+        let redis_key = self.redis_key.clone();
+
+        tokio::spawn(async move {
+            let Ok(mut conn) = redis.get_connection(&redis_key).await else {
+                warn!("Failed to get a connection to release concurrency permit: {redis_key}");
+                return;
+            };
+            let result: Result<i64, _> = script.key(&redis_key).invoke_async(&mut *conn).await;
+            if let Err(e) = result {
+                warn!("Failed to release concurrency permit {redis_key}: {e}");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedisConfig;
+
+    fn test_redis_config() -> RedisConfig {
+        RedisConfig {
+            url: "redis://localhost:6379".to_string(),
+            pool_size: 10,
+            connection_timeout_ms: 5000,
+            command_timeout_ms: 3000,
+            cache_ttl_secs: 300,
+            ..Default::default()
+        }
+    }
+
+    fn make_limiter() -> ConcurrencyLimiter {
+        let redis = RedisClient::new(test_redis_config()).unwrap();
+        ConcurrencyLimiter::new(Arc::new(redis))
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_acquire_denies_beyond_max_concurrent() {
+        let limiter = make_limiter();
+        let key = RateLimitKey::Endpoint("/api/v1/completions".to_string());
+
+        let first = limiter.acquire(&key, 1).await;
+        assert!(first.is_ok());
+
+        let second = limiter.acquire(&key, 1).await;
+        assert!(matches!(
+            second,
+            Err(RateLimitError::ConcurrencyLimited { in_flight: 1, max: 1 })
+        ));
+
+        drop(first);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_dropping_permit_frees_the_slot() {
+        let limiter = make_limiter();
+        let key = RateLimitKey::Endpoint("/api/v1/drop_test".to_string());
+
+        let permit = limiter.acquire(&key, 1).await.unwrap();
+        drop(permit);
+
+        // Give the background release task a moment to run
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(limiter.acquire(&key, 1).await.is_ok());
+    }
+}
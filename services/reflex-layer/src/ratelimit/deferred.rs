@@ -0,0 +1,384 @@
+//! Local-cache-fronted Redis rate limiter
+//!
+//! [`RedisRateLimiter`] is authoritative but costs a round-trip per check.
+//! [`DeferredRateLimiter`] wraps it with a bounded, TTL'd local cache of
+//! per-key remaining-token estimates so a request that's obviously allowed
+//! never touches Redis: it decrements the local estimate and returns
+//! immediately, only falling back to Redis once the estimate nears
+//! exhaustion, is missing, or has expired. Concurrent checks for the same
+//! key that all miss the local cache collapse into a single Redis call via
+//! a per-key async lock (the "single-flight" part), and a failed Redis
+//! call reuses whatever local estimate exists for the rest of its TTL
+//! rather than erroring out.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use tokio::sync::Mutex as AsyncMutex;
+use tracing::{debug, warn};
+
+use crate::ratelimit::redis_limiter::RedisRateLimiter;
+use crate::ratelimit::types::{RateLimitConfig, RateLimitKey, RateLimitResult};
+
+/// Fixed-point scale applied to token counts so they fit in an `AtomicI64`
+/// and can be decremented with a lock-free compare-and-swap loop.
+const SCALE: i64 = 1_000_000;
+
+/// Tokens kept in reserve below which a check stops trusting the local
+/// estimate and goes to Redis for an authoritative refill.
+const LOCAL_HEADROOM_TOKENS: f64 = 1.0;
+
+/// Outcome of a [`DeferredRateLimiter`] check
+///
+/// Distinguishes how an allow decision was reached, not just whether it was
+/// allowed: a caller measuring how effectively the local cache is shielding
+/// Redis needs to tell "served from the in-process estimate" apart from
+/// "had to pay the round-trip".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeferredResult {
+    /// Allowed purely from the local estimate; no Redis round-trip
+    AllowedLocally,
+    /// The local estimate was missing, expired, or out of headroom, so an
+    /// authoritative Redis check ran (and allowed the request)
+    AllowedAfterSync,
+    /// Denied for now; the caller should retry at or after this instant
+    RetryAt(Instant),
+    /// Denied with no usable retry hint (Redis is unreachable and there's
+    /// no local estimate to fall back on)
+    RetryNever,
+}
+
+impl DeferredResult {
+    /// Whether the request was allowed, regardless of which tier served it
+    pub fn is_allowed(&self) -> bool {
+        matches!(
+            self,
+            DeferredResult::AllowedLocally | DeferredResult::AllowedAfterSync
+        )
+    }
+}
+
+/// A key's locally cached remaining-token estimate
+struct LocalEstimate {
+    /// Remaining tokens, scaled by [`SCALE`] so it fits an atomic integer
+    scaled_remaining: AtomicI64,
+    /// When this estimate stops being trusted and must be refreshed
+    expires_at: Instant,
+}
+
+impl LocalEstimate {
+    fn new(remaining: f64, ttl: Duration) -> Self {
+        Self {
+            scaled_remaining: AtomicI64::new((remaining * SCALE as f64) as i64),
+            expires_at: Instant::now() + ttl,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    fn refresh_ttl(&mut self, remaining: f64, ttl: Duration) {
+        self.scaled_remaining
+            .store((remaining * SCALE as f64) as i64, Ordering::Relaxed);
+        self.expires_at = Instant::now() + ttl;
+    }
+
+    /// Attempt to consume `tokens` locally without dropping below the
+    /// headroom, returning the resulting token count on success.
+    fn try_consume(&self, tokens: f64) -> Option<f64> {
+        let scaled_tokens = (tokens * SCALE as f64) as i64;
+        let headroom = (LOCAL_HEADROOM_TOKENS * SCALE as f64) as i64;
+
+        loop {
+            let current = self.scaled_remaining.load(Ordering::Relaxed);
+            let remaining_after = current - scaled_tokens;
+            if remaining_after < headroom {
+                return None;
+            }
+
+            if self
+                .scaled_remaining
+                .compare_exchange_weak(
+                    current,
+                    remaining_after,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+            {
+                return Some(remaining_after as f64 / SCALE as f64);
+            }
+        }
+    }
+}
+
+/// Two-tier rate limiter: a local, TTL'd estimate in front of the
+/// authoritative [`RedisRateLimiter`]
+pub struct DeferredRateLimiter {
+    redis: Arc<RedisRateLimiter>,
+    local: DashMap<String, LocalEstimate>,
+    inflight: DashMap<String, Arc<AsyncMutex<()>>>,
+}
+
+impl DeferredRateLimiter {
+    /// Wrap a [`RedisRateLimiter`] with a local deferred-check cache
+    pub fn new(redis: Arc<RedisRateLimiter>) -> Self {
+        Self {
+            redis,
+            local: DashMap::new(),
+            inflight: DashMap::new(),
+        }
+    }
+
+    /// Number of keys with a live local estimate (for tests/metrics)
+    pub fn local_cache_len(&self) -> usize {
+        self.local.len()
+    }
+
+    /// Check and, if allowed, consume `tokens` from `key`'s bucket
+    ///
+    /// Serves the check from the local estimate when it's fresh and has
+    /// comfortable headroom; otherwise performs a single-flighted
+    /// authoritative check against Redis and refreshes the estimate.
+    pub async fn check(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> DeferredResult {
+        let redis_key = key.to_redis_key();
+
+        if let Some(result) = self.try_local(&redis_key, tokens) {
+            return result;
+        }
+
+        self.refresh_via_redis(&redis_key, key, config, tokens)
+            .await
+    }
+
+    /// Serve `tokens` from the local estimate if it's fresh and not about
+    /// to run dry; returns `None` when Redis must be consulted.
+    fn try_local(&self, redis_key: &str, tokens: f64) -> Option<DeferredResult> {
+        let entry = self.local.get(redis_key)?;
+        if entry.is_expired() {
+            return None;
+        }
+
+        let remaining = entry.try_consume(tokens)?;
+        debug!("Local rate limit estimate ALLOWED: key={redis_key}, remaining={remaining}");
+        Some(DeferredResult::AllowedLocally)
+    }
+
+    /// Refresh `redis_key`'s local estimate with an authoritative Redis
+    /// check, collapsing concurrent callers for the same key into one
+    /// round-trip.
+    async fn refresh_via_redis(
+        &self,
+        redis_key: &str,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> DeferredResult {
+        let lock = self
+            .inflight
+            .entry(redis_key.to_string())
+            .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+            .clone();
+        let _guard = lock.lock().await;
+
+        // Another caller may have already refreshed the estimate while we
+        // were waiting for the lock.
+        if let Some(result) = self.try_local(redis_key, tokens) {
+            return result;
+        }
+
+        let ttl = refill_period(config);
+        match self.redis.check_rate_limit(key, config, tokens).await {
+            Ok(RateLimitResult::Allowed { remaining, .. }) => {
+                self.store_local(redis_key, remaining, ttl);
+                DeferredResult::AllowedAfterSync
+            }
+            Ok(RateLimitResult::Limited {
+                retry_after_ms,
+                current_tokens,
+                ..
+            }) => {
+                self.store_local(redis_key, current_tokens, ttl);
+                DeferredResult::RetryAt(Instant::now() + Duration::from_millis(retry_after_ms))
+            }
+            Err(e) => {
+                warn!(
+                    "Redis rate limit check failed for {redis_key}, falling back to local estimate: {e}"
+                );
+                self.fallback_on_redis_error(redis_key, ttl)
+            }
+        }
+    }
+
+    /// When Redis is unreachable, keep using whatever local estimate
+    /// exists (even if technically expired) for another TTL instead of
+    /// erroring out; with no estimate at all there's nothing safe to
+    /// allow.
+    fn fallback_on_redis_error(&self, redis_key: &str, ttl: Duration) -> DeferredResult {
+        let Some(mut entry) = self.local.get_mut(redis_key) else {
+            return DeferredResult::RetryNever;
+        };
+
+        let remaining = entry.scaled_remaining.load(Ordering::Relaxed) as f64 / SCALE as f64;
+        entry.refresh_ttl(remaining, ttl);
+
+        match entry.try_consume(1.0) {
+            Some(_) => DeferredResult::AllowedLocally,
+            None => DeferredResult::RetryAt(Instant::now() + ttl),
+        }
+    }
+
+    fn store_local(&self, redis_key: &str, remaining: f64, ttl: Duration) {
+        self.local
+            .insert(redis_key.to_string(), LocalEstimate::new(remaining, ttl));
+    }
+}
+
+/// Time for a fully-drained bucket to refill to capacity, used as the
+/// local estimate's time-to-live
+fn refill_period(config: &RateLimitConfig) -> Duration {
+    Duration::from_secs_f64(config.capacity as f64 / config.refill_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RedisConfig;
+    use crate::redis_client::RedisClient;
+
+    fn test_redis_config() -> RedisConfig {
+        RedisConfig {
+            url: "redis://localhost:6379".to_string(),
+            pool_size: 10,
+            connection_timeout_ms: 5000,
+            command_timeout_ms: 3000,
+            cache_ttl_secs: 300,
+            ..Default::default()
+        }
+    }
+
+    fn make_limiter() -> DeferredRateLimiter {
+        let redis = RedisClient::new(test_redis_config()).unwrap();
+        DeferredRateLimiter::new(Arc::new(RedisRateLimiter::new(Arc::new(redis))))
+    }
+
+    fn test_config() -> RateLimitConfig {
+        RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_local_estimate_try_consume_respects_headroom() {
+        let estimate = LocalEstimate::new(2.0, Duration::from_secs(10));
+
+        // 2.0 - 1.0 = 1.0, which is not below the 1.0 headroom, so this succeeds
+        assert_eq!(estimate.try_consume(1.0), Some(1.0));
+        // 1.0 - 1.0 = 0.0, which is below headroom, so Redis must be consulted
+        assert_eq!(estimate.try_consume(1.0), None);
+    }
+
+    #[test]
+    fn test_local_estimate_expires_after_ttl() {
+        let estimate = LocalEstimate::new(5.0, Duration::from_millis(0));
+        assert!(estimate.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_check_serves_from_fresh_local_cache() {
+        let limiter = make_limiter();
+        let key = RateLimitKey::User("local_user".to_string());
+        let redis_key = key.to_redis_key();
+
+        limiter.store_local(&redis_key, 5.0, Duration::from_secs(60));
+
+        let result = limiter.check(&key, &test_config(), 1.0).await;
+        assert_eq!(result, DeferredResult::AllowedLocally);
+        assert_eq!(limiter.local_cache_len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_falls_through_when_cache_empty() {
+        // With no cached estimate and Redis unreachable, there's nothing
+        // safe to allow.
+        let limiter = make_limiter();
+        let key = RateLimitKey::User("no_estimate_user".to_string());
+
+        let result = limiter.check(&key, &test_config(), 1.0).await;
+        assert_eq!(result, DeferredResult::RetryNever);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_reuses_stale_estimate_on_redis_error() {
+        let limiter = make_limiter();
+        let key = RateLimitKey::User("stale_user".to_string());
+        let redis_key = key.to_redis_key();
+
+        limiter.store_local(&redis_key, 5.0, Duration::from_millis(0));
+        assert!(limiter.local.get(&redis_key).unwrap().is_expired());
+
+        let result = limiter
+            .fallback_on_redis_error(&redis_key, Duration::from_secs(10));
+        assert_eq!(result, DeferredResult::AllowedLocally);
+        assert!(!limiter.local.get(&redis_key).unwrap().is_expired());
+    }
+
+    #[test]
+    fn test_refill_period_is_capacity_over_refill_rate() {
+        let config = RateLimitConfig {
+            capacity: 100,
+            refill_rate: 10.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+        assert_eq!(refill_period(&config), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_check_refreshes_from_redis_when_cache_misses() {
+        let limiter = make_limiter();
+        let key = RateLimitKey::User("redis_backed_user".to_string());
+        let config = test_config();
+
+        assert!(limiter.check(&key, &config, 1.0).await.is_allowed());
+        assert_eq!(limiter.local_cache_len(), 1);
+
+        limiter.redis.reset(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_concurrent_checks_for_same_key_single_flight() {
+        let limiter = Arc::new(make_limiter());
+        let key = RateLimitKey::User("single_flight_user".to_string());
+        let config = test_config();
+
+        let mut handles = vec![];
+        for _ in 0..5 {
+            let limiter = limiter.clone();
+            let key = key.clone();
+            let config = config;
+            handles.push(tokio::spawn(async move {
+                limiter.check(&key, &config, 1.0).await.is_allowed()
+            }));
+        }
+
+        for handle in handles {
+            assert!(handle.await.unwrap());
+        }
+
+        limiter.redis.reset(&key).await.unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+//! In-memory `RateLimitBackend` fallback
+//!
+//! Keeps one [`TokenBucket`] per rate-limit key in a `DashMap`, created
+//! lazily on first use and refilled in place on every subsequent check --
+//! the token-bucket counterpart to [`cache::InMemoryCache`](crate::cache::InMemoryCache).
+//! Used when Redis is unreachable at startup and as a dependency-free mock
+//! in tests.
+//!
+//! Under adversarial key churn (e.g. an attacker rotating through many
+//! distinct `RateLimitKey`s), the idle-cleanup task bounds memory by
+//! dropping entries that have certainly refilled back to full capacity,
+//! and each entry's cached allowance is kept as an `f32` instead of
+//! re-locking the bucket's `f64` mutex just to decide whether it's idle.
+
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use async_trait::async_trait;
+
+use crate::ratelimit::backend::RateLimitBackend;
+use crate::ratelimit::token_bucket::TokenBucket;
+use crate::ratelimit::types::{RateLimitConfig, RateLimitError, RateLimitKey, RateLimitResult};
+
+/// A local bucket plus the bookkeeping needed to evict it once idle
+struct BucketEntry {
+    bucket: TokenBucket,
+    /// Last time this key was touched by `get`/`set`/`incr`
+    last_checked: Instant,
+    /// Token count as of `last_checked`, kept as `f32` to keep the
+    /// idle-eviction sweep cheap under high key cardinality
+    last_known_tokens: f32,
+}
+
+impl BucketEntry {
+    fn new(bucket: TokenBucket) -> Self {
+        let tokens = bucket.current_tokens() as f32;
+        Self {
+            bucket,
+            last_checked: Instant::now(),
+            last_known_tokens: tokens,
+        }
+    }
+
+    fn touch(&mut self, tokens: f64) {
+        self.last_checked = Instant::now();
+        self.last_known_tokens = tokens as f32;
+    }
+
+    /// Time for this bucket to refill from empty to full, used as the
+    /// idle-eviction threshold
+    fn refill_to_full_duration(&self) -> Duration {
+        let config = self.bucket.config();
+        Duration::from_secs_f64(config.capacity as f64 / config.refill_rate)
+    }
+
+    /// Whether this entry hasn't been touched in at least as long as it
+    /// takes its bucket to refill to capacity -- i.e. it's certainly full
+    /// and idle, and safe to drop
+    fn is_idle(&self) -> bool {
+        self.last_checked.elapsed() >= self.refill_to_full_duration()
+    }
+}
+
+/// `DashMap`-backed in-memory implementation of [`RateLimitBackend`]
+#[derive(Default)]
+pub struct InMemoryRateLimiter {
+    buckets: DashMap<String, BucketEntry>,
+}
+
+impl InMemoryRateLimiter {
+    /// Create an in-memory rate limiter with no buckets yet
+    pub fn new() -> Self {
+        Self {
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Number of distinct keys with a live bucket
+    pub fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Last known token count for `key`, read from the cheap `f32` cache
+    /// instead of locking the bucket's mutex (may be stale by up to one
+    /// refill tick)
+    pub fn cached_tokens(&self, key: &RateLimitKey) -> Option<f32> {
+        self.buckets
+            .get(&key.to_redis_key())
+            .map(|entry| entry.last_known_tokens)
+    }
+
+    /// Drop every bucket that's been idle long enough to have certainly
+    /// refilled back to full capacity
+    ///
+    /// Meant to be called on an interval (see
+    /// [`InMemoryRateLimiter::spawn_cleanup_task`]) so a map that's seen
+    /// adversarial key churn doesn't grow unbounded.
+    pub fn cleanup_idle(&self) {
+        self.buckets.retain(|_, entry| !entry.is_idle());
+    }
+
+    /// Spawn a background task that calls [`Self::cleanup_idle`] on
+    /// `interval` until the returned handle is aborted or dropped
+    pub fn spawn_cleanup_task(
+        self: std::sync::Arc<Self>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.cleanup_idle();
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl RateLimitBackend for InMemoryRateLimiter {
+    async fn get(&self, key: &RateLimitKey) -> Result<Option<f64>, RateLimitError> {
+        Ok(self.buckets.get_mut(&key.to_redis_key()).map(|mut entry| {
+            let tokens = entry.bucket.current_tokens();
+            entry.touch(tokens);
+            tokens
+        }))
+    }
+
+    async fn set(&self, key: &RateLimitKey, tokens: f64) -> Result<(), RateLimitError> {
+        if let Some(mut entry) = self.buckets.get_mut(&key.to_redis_key()) {
+            entry.bucket.set_tokens(tokens);
+            entry.touch(tokens);
+        }
+        Ok(())
+    }
+
+    async fn incr(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> Result<RateLimitResult, RateLimitError> {
+        let mut entry = self
+            .buckets
+            .entry(key.to_redis_key())
+            .or_insert_with(|| BucketEntry::new(TokenBucket::new(*config)));
+        let result = entry.bucket.try_consume(tokens);
+        let current = match result {
+            RateLimitResult::Allowed { remaining, .. } => remaining,
+            RateLimitResult::Limited { current_tokens, .. } => current_tokens,
+        };
+        entry.touch(current);
+        Ok(result)
+    }
+
+    async fn expire(&self, _key: &RateLimitKey, _ttl_secs: u64) -> Result<bool, RateLimitError> {
+        // Idle buckets are reclaimed by `cleanup_idle` instead of a
+        // per-key TTL; there's nothing to evict early here.
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> RateLimitConfig {
+        RateLimitConfig {
+            capacity: 5,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_incr_creates_bucket_lazily() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::User("user1".to_string());
+
+        assert_eq!(limiter.get(&key).await.unwrap(), None);
+        assert!(limiter
+            .incr(&key, &test_config(), 1.0)
+            .await
+            .unwrap()
+            .is_allowed());
+        assert_eq!(limiter.bucket_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_incr_denies_once_exhausted() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::IP("1.2.3.4".to_string());
+        let config = test_config();
+
+        for _ in 0..5 {
+            assert!(limiter.incr(&key, &config, 1.0).await.unwrap().is_allowed());
+        }
+        assert!(limiter.incr(&key, &config, 1.0).await.unwrap().is_limited());
+    }
+
+    #[tokio::test]
+    async fn test_set_overwrites_existing_bucket() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::Global;
+        let config = test_config();
+
+        limiter.incr(&key, &config, 5.0).await.unwrap();
+        limiter.set(&key, 2.0).await.unwrap();
+
+        assert_eq!(limiter.get(&key).await.unwrap(), Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn test_set_on_missing_bucket_is_a_noop() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::Global;
+
+        limiter.set(&key, 2.0).await.unwrap();
+        assert_eq!(limiter.get(&key).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_different_keys_have_independent_buckets() {
+        let limiter = InMemoryRateLimiter::new();
+        let config = test_config();
+        let key1 = RateLimitKey::User("user1".to_string());
+        let key2 = RateLimitKey::User("user2".to_string());
+
+        for _ in 0..5 {
+            limiter.incr(&key1, &config, 1.0).await.unwrap();
+        }
+
+        assert!(limiter.incr(&key1, &config, 1.0).await.unwrap().is_limited());
+        assert!(limiter.incr(&key2, &config, 1.0).await.unwrap().is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_idle_drops_fully_refilled_buckets() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::IP("10.0.0.1".to_string());
+        // refill_rate huge enough that refill-to-full is ~0, so the entry
+        // reads as idle immediately.
+        let config = RateLimitConfig {
+            capacity: 5,
+            refill_rate: 1_000_000.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        limiter.incr(&key, &config, 1.0).await.unwrap();
+        assert_eq!(limiter.bucket_count(), 1);
+
+        limiter.cleanup_idle();
+        assert_eq!(limiter.bucket_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cached_tokens_reflects_last_check() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::User("cached_user".to_string());
+        let config = test_config();
+
+        assert_eq!(limiter.cached_tokens(&key), None);
+
+        limiter.incr(&key, &config, 2.0).await.unwrap();
+        assert_eq!(limiter.cached_tokens(&key), Some(3.0));
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_idle_keeps_buckets_that_havent_refilled_yet() {
+        let limiter = InMemoryRateLimiter::new();
+        let key = RateLimitKey::IP("10.0.0.2".to_string());
+        let config = test_config(); // capacity 5, refill_rate 1.0 => refills over 5s
+
+        limiter.incr(&key, &config, 1.0).await.unwrap();
+        limiter.cleanup_idle();
+
+        assert_eq!(limiter.bucket_count(), 1);
+    }
+}
@@ -3,13 +3,34 @@
 //! Provides distributed rate limiting using Redis with token bucket algorithm,
 //! supporting multi-dimensional rate limits (user, IP, endpoint, global).
 
+pub mod api_key;
+pub mod backend;
+pub mod circuit_breaker;
+pub mod clock;
+pub mod concurrency;
+pub mod deferred;
+pub mod in_memory;
 pub mod redis_limiter;
+pub mod registry;
+pub mod tier_resolver;
 pub mod token_bucket;
 pub mod types;
+pub mod window;
 
 // Re-export commonly used items
+pub use api_key::ApiKeyTierTable;
+pub use backend::RateLimitBackend;
+pub use circuit_breaker::{CircuitBreakerRateLimiter, RateLimiterMode};
+pub use clock::{Clock, FakeClock, SystemClock};
+pub use concurrency::{ConcurrencyLimiter, ConcurrencyPermit};
+pub use deferred::{DeferredRateLimiter, DeferredResult};
+pub use in_memory::InMemoryRateLimiter;
 pub use redis_limiter::{MultiDimensionalRateLimiter, RedisRateLimiter};
+pub use registry::{NoopMetricsSink, RateLimitMetricsSink, TokenBucketRegistry};
+pub use tier_resolver::{RedisTierResolver, StaticTierResolver, TierConfigTable, TierResolver};
 pub use token_bucket::TokenBucket;
 pub use types::{
-    RateLimitConfig, RateLimitError, RateLimitKey, RateLimitReason, RateLimitResult, RateLimitTier,
+    RateLimitConfig, RateLimitError, RateLimitKey, RateLimitReason, RateLimitResult,
+    RateLimitTier, TokenType,
 };
+pub use window::parse_rate_window;
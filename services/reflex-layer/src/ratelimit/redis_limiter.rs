@@ -3,11 +3,14 @@
 //! Provides distributed rate limiting using Redis with atomic Lua scripts.
 //! Supports multi-dimensional rate limiting (user, IP, endpoint, global).
 
+use crate::ratelimit::backend::RateLimitBackend;
+use crate::ratelimit::tier_resolver::TierResolver;
 use crate::ratelimit::types::{
-    RateLimitConfig, RateLimitError, RateLimitKey, RateLimitReason, RateLimitResult,
+    RateLimitConfig, RateLimitError, RateLimitKey, RateLimitReason, RateLimitResult, TokenType,
 };
 use crate::redis_client::RedisClient;
-use redis::Script;
+use async_trait::async_trait;
+use redis::{AsyncCommands, Script};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, error};
@@ -18,6 +21,8 @@ pub struct RedisRateLimiter {
     redis: Arc<RedisClient>,
     /// Compiled Lua script for atomic token bucket operations
     script: Script,
+    /// Compiled Lua script for atomic dual-bucket (ops + bytes) operations
+    multi_script: Script,
 }
 
 impl RedisRateLimiter {
@@ -31,11 +36,18 @@ impl RedisRateLimiter {
     pub fn new(redis: Arc<RedisClient>) -> Self {
         debug!("Creating RedisRateLimiter");
 
-        // Load the Lua script
+        // Load the Lua scripts
         let lua_script = include_str!("token_bucket.lua");
         let script = Script::new(lua_script);
 
-        Self { redis, script }
+        let multi_lua_script = include_str!("token_bucket_multi.lua");
+        let multi_script = Script::new(multi_lua_script);
+
+        Self {
+            redis,
+            script,
+            multi_script,
+        }
     }
 
     /// Check rate limit for a given key
@@ -60,7 +72,7 @@ impl RedisRateLimiter {
             redis_key, config.capacity, config.refill_rate, tokens_to_consume
         );
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(&redis_key).await?;
 
         // Get current timestamp in milliseconds
         let now = SystemTime::now()
@@ -68,7 +80,11 @@ impl RedisRateLimiter {
             .map_err(|e| RateLimitError::Internal(format!("Time error: {}", e)))?
             .as_millis() as u64;
 
-        // Execute Lua script
+        // Execute Lua script. Not wrapped in `retry_redis_command`: a timed-out
+        // command leaves us unsure whether the script already ran atomically
+        // server-side, so blindly retrying it risks double-consuming tokens --
+        // unlike a cache GET/SET, a token-bucket decrement isn't safely
+        // re-playable.
         let result: Vec<i64> = self
             .script
             .key(&redis_key)
@@ -107,6 +123,83 @@ impl RedisRateLimiter {
         }
     }
 
+    /// Check and consume request-frequency and payload-bandwidth tokens
+    /// for `key` in a single atomic round-trip
+    ///
+    /// Allowed only if both the ops and bytes buckets have enough tokens;
+    /// if either is short, neither is consumed and the result is
+    /// `Limited`, tagged with whichever bucket was exhausted.
+    ///
+    /// # Arguments
+    /// * `key` - Rate limit key (user, IP, endpoint, etc.)
+    /// * `ops` - Ops bucket config and tokens to consume (typically 1.0)
+    /// * `bytes` - Bytes bucket config and tokens to consume (payload size)
+    pub async fn check_rate_limit_multi(
+        &self,
+        key: &RateLimitKey,
+        ops: (&RateLimitConfig, f64),
+        bytes: (&RateLimitConfig, f64),
+    ) -> Result<RateLimitResult, RateLimitError> {
+        let redis_key = key.to_redis_key();
+        let (ops_config, ops_consume) = ops;
+        let (bytes_config, bytes_consume) = bytes;
+
+        let mut conn = self.redis.get_connection(&redis_key).await?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| RateLimitError::Internal(format!("Time error: {}", e)))?
+            .as_millis() as u64;
+
+        let result: Vec<i64> = self
+            .multi_script
+            .key(&redis_key)
+            .arg(ops_config.capacity)
+            .arg(ops_config.refill_rate)
+            .arg(ops_consume)
+            .arg(bytes_config.capacity)
+            .arg(bytes_config.refill_rate)
+            .arg(bytes_consume)
+            .arg(now)
+            .invoke_async(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("Multi-bucket Lua script execution failed: {}", e);
+                RateLimitError::ScriptError(format!("Script execution error: {}", e))
+            })?;
+
+        let allowed = result[0] == 1;
+        let ops_tokens = result[1] as f64;
+        let bytes_tokens = result[2] as f64;
+        let retry_after_ms = result[3] as u64;
+
+        if allowed {
+            debug!(
+                "Rate limit ALLOWED: key={}, ops_remaining={}, bytes_remaining={}",
+                redis_key, ops_tokens, bytes_tokens
+            );
+            Ok(RateLimitResult::Allowed {
+                remaining: ops_tokens,
+                reset_after_ms: 0,
+            })
+        } else {
+            let (reason, current_tokens) = if ops_tokens < ops_consume {
+                (RateLimitReason::TokenQuota(TokenType::Ops), ops_tokens)
+            } else {
+                (RateLimitReason::TokenQuota(TokenType::Bytes), bytes_tokens)
+            };
+            debug!(
+                "Rate limit DENIED: key={}, reason={:?}, retry_after={}ms",
+                redis_key, reason, retry_after_ms
+            );
+            Ok(RateLimitResult::Limited {
+                reason,
+                retry_after_ms,
+                current_tokens,
+            })
+        }
+    }
+
     /// Reset rate limit for a key (clear all tokens)
     ///
     /// # Arguments
@@ -121,7 +214,7 @@ impl RedisRateLimiter {
         let redis_key = key.to_redis_key();
         debug!("Resetting rate limit: {}", redis_key);
 
-        let mut conn = self.redis.get_connection().await?;
+        let mut conn = self.redis.get_connection(&redis_key).await?;
 
         conn.del::<_, ()>(&redis_key)
             .await
@@ -129,18 +222,72 @@ impl RedisRateLimiter {
 
         Ok(())
     }
+
+}
+
+/// Raw get/set/incr/expire primitives, implemented directly against Redis
+///
+/// `incr` is `check_rate_limit` under another name (the atomic
+/// refill-and-consume Lua script); `get`/`set` read or overwrite the
+/// `tokens` field of the same Redis hash the script maintains, and
+/// `expire` lets an idle bucket be evicted instead of sitting in Redis
+/// forever.
+#[async_trait]
+impl RateLimitBackend for RedisRateLimiter {
+    async fn get(&self, key: &RateLimitKey) -> Result<Option<f64>, RateLimitError> {
+        let redis_key = key.to_redis_key();
+        let mut conn = self.redis.get_connection(&redis_key).await?;
+
+        let tokens: Option<f64> = conn
+            .hget(&redis_key, "tokens")
+            .await
+            .map_err(RateLimitError::Redis)?;
+        Ok(tokens)
+    }
+
+    async fn set(&self, key: &RateLimitKey, tokens: f64) -> Result<(), RateLimitError> {
+        let redis_key = key.to_redis_key();
+        let mut conn = self.redis.get_connection(&redis_key).await?;
+
+        conn.hset::<_, _, _, ()>(&redis_key, "tokens", tokens)
+            .await
+            .map_err(RateLimitError::Redis)?;
+        Ok(())
+    }
+
+    async fn incr(
+        &self,
+        key: &RateLimitKey,
+        config: &RateLimitConfig,
+        tokens: f64,
+    ) -> Result<RateLimitResult, RateLimitError> {
+        self.check_rate_limit(key, config, tokens).await
+    }
+
+    async fn expire(&self, key: &RateLimitKey, ttl_secs: u64) -> Result<bool, RateLimitError> {
+        let redis_key = key.to_redis_key();
+        let mut conn = self.redis.get_connection(&redis_key).await?;
+
+        conn.expire::<_, bool>(&redis_key, ttl_secs as i64)
+            .await
+            .map_err(RateLimitError::Redis)
+    }
 }
 
 /// Multi-dimensional rate limiter
 ///
 /// Checks rate limits across multiple dimensions (user, IP, endpoint, global)
-/// and returns the first exceeded limit or allows the request.
+/// and returns the first exceeded limit or allows the request. Each
+/// dimension's config is looked up fresh from a [`TierResolver`] on every
+/// check rather than fixed at construction, so a config change or plan
+/// upgrade takes effect immediately.
 pub struct MultiDimensionalRateLimiter {
     limiter: Arc<RedisRateLimiter>,
-    user_config: RateLimitConfig,
-    ip_config: RateLimitConfig,
-    endpoint_config: RateLimitConfig,
-    global_config: RateLimitConfig,
+    user_resolver: Arc<dyn TierResolver>,
+    ip_resolver: Arc<dyn TierResolver>,
+    endpoint_resolver: Arc<dyn TierResolver>,
+    endpoint_ip_resolver: Arc<dyn TierResolver>,
+    global_resolver: Arc<dyn TierResolver>,
 }
 
 impl MultiDimensionalRateLimiter {
@@ -148,53 +295,82 @@ impl MultiDimensionalRateLimiter {
     ///
     /// # Arguments
     /// * `limiter` - Arc to RedisRateLimiter
-    /// * `user_config` - Rate limit config for per-user limits
-    /// * `ip_config` - Rate limit config for per-IP limits
-    /// * `endpoint_config` - Rate limit config for per-endpoint limits
-    /// * `global_config` - Rate limit config for global limits
+    /// * `user_resolver` - Resolves the config for per-user limits
+    /// * `ip_resolver` - Resolves the config for per-IP limits
+    /// * `endpoint_resolver` - Resolves the config for per-endpoint limits
+    /// * `endpoint_ip_resolver` - Resolves the config for the joint
+    ///   (endpoint, IP) dimension
+    /// * `global_resolver` - Resolves the config for global limits
     pub fn new(
+        limiter: Arc<RedisRateLimiter>,
+        user_resolver: Arc<dyn TierResolver>,
+        ip_resolver: Arc<dyn TierResolver>,
+        endpoint_resolver: Arc<dyn TierResolver>,
+        endpoint_ip_resolver: Arc<dyn TierResolver>,
+        global_resolver: Arc<dyn TierResolver>,
+    ) -> Self {
+        Self {
+            limiter,
+            user_resolver,
+            ip_resolver,
+            endpoint_resolver,
+            endpoint_ip_resolver,
+            global_resolver,
+        }
+    }
+
+    /// Create a multi-dimensional rate limiter with fixed, non-reloadable
+    /// configs for each dimension -- a thin convenience wrapper over
+    /// [`StaticTierResolver`]-style resolvers for callers that don't need
+    /// runtime reconfiguration
+    pub fn with_fixed_configs(
         limiter: Arc<RedisRateLimiter>,
         user_config: RateLimitConfig,
         ip_config: RateLimitConfig,
         endpoint_config: RateLimitConfig,
+        endpoint_ip_config: RateLimitConfig,
         global_config: RateLimitConfig,
     ) -> Self {
-        Self {
+        Self::new(
             limiter,
-            user_config,
-            ip_config,
-            endpoint_config,
-            global_config,
-        }
+            Arc::new(FixedConfigResolver(user_config)),
+            Arc::new(FixedConfigResolver(ip_config)),
+            Arc::new(FixedConfigResolver(endpoint_config)),
+            Arc::new(FixedConfigResolver(endpoint_ip_config)),
+            Arc::new(FixedConfigResolver(global_config)),
+        )
     }
 
-    /// Check all rate limits
+    /// Check all rate limits, weighting every dimension's token cost by
+    /// how expensive `endpoint` is to serve
     ///
-    /// Checks in order: user -> IP -> endpoint -> global
+    /// Checks in order: user -> IP -> endpoint -> (endpoint, IP) -> global
     /// Returns immediately on first limit exceeded.
     ///
     /// # Arguments
     /// * `user_id` - Optional user identifier
     /// * `ip` - IP address
     /// * `endpoint` - Endpoint being accessed
+    /// * `cost` - Tokens this request consumes in every dimension (e.g. an
+    ///   expensive completion endpoint should pass a cost `> 1.0`, a cheap
+    ///   health check `< 1.0`)
     ///
     /// # Returns
     /// * `Ok(RateLimitResult::Allowed)` - All checks passed
     /// * `Ok(RateLimitResult::Limited)` - One check failed
     /// * `Err(RateLimitError)` - Error during checks
-    pub async fn check_all(
+    pub async fn check_all_weighted(
         &self,
         user_id: Option<&str>,
         ip: &str,
         endpoint: &str,
+        cost: f64,
     ) -> Result<RateLimitResult, RateLimitError> {
         // Check user limit if user_id provided
         if let Some(uid) = user_id {
             let key = RateLimitKey::User(uid.to_string());
-            let result = self
-                .limiter
-                .check_rate_limit(&key, &self.user_config, 1.0)
-                .await?;
+            let config = self.user_resolver.resolve(&key).await;
+            let result = self.limiter.check_rate_limit(&key, &config, cost).await?;
 
             if result.is_limited() {
                 if let RateLimitResult::Limited {
@@ -214,9 +390,10 @@ impl MultiDimensionalRateLimiter {
 
         // Check IP limit
         let ip_key = RateLimitKey::IP(ip.to_string());
+        let ip_config = self.ip_resolver.resolve(&ip_key).await;
         let result = self
             .limiter
-            .check_rate_limit(&ip_key, &self.ip_config, 1.0)
+            .check_rate_limit(&ip_key, &ip_config, cost)
             .await?;
 
         if result.is_limited() {
@@ -236,9 +413,10 @@ impl MultiDimensionalRateLimiter {
 
         // Check endpoint limit
         let endpoint_key = RateLimitKey::Endpoint(endpoint.to_string());
+        let endpoint_config = self.endpoint_resolver.resolve(&endpoint_key).await;
         let result = self
             .limiter
-            .check_rate_limit(&endpoint_key, &self.endpoint_config, 1.0)
+            .check_rate_limit(&endpoint_key, &endpoint_config, cost)
             .await?;
 
         if result.is_limited() {
@@ -256,11 +434,37 @@ impl MultiDimensionalRateLimiter {
             }
         }
 
+        // Check the joint (endpoint, IP) limit, so one client can't
+        // monopolize a single expensive endpoint while staying under its
+        // overall per-IP limit
+        let endpoint_ip_key = RateLimitKey::EndpointIp(endpoint.to_string(), ip.to_string());
+        let endpoint_ip_config = self.endpoint_ip_resolver.resolve(&endpoint_ip_key).await;
+        let result = self
+            .limiter
+            .check_rate_limit(&endpoint_ip_key, &endpoint_ip_config, cost)
+            .await?;
+
+        if result.is_limited() {
+            if let RateLimitResult::Limited {
+                retry_after_ms,
+                current_tokens,
+                ..
+            } = result
+            {
+                return Ok(RateLimitResult::Limited {
+                    reason: RateLimitReason::EndpointIpQuota,
+                    retry_after_ms,
+                    current_tokens,
+                });
+            }
+        }
+
         // Check global limit
         let global_key = RateLimitKey::Global;
+        let global_config = self.global_resolver.resolve(&global_key).await;
         let result = self
             .limiter
-            .check_rate_limit(&global_key, &self.global_config, 1.0)
+            .check_rate_limit(&global_key, &global_config, cost)
             .await?;
 
         if result.is_limited() {
@@ -284,6 +488,30 @@ impl MultiDimensionalRateLimiter {
             reset_after_ms: 0,
         })
     }
+
+    /// [`Self::check_all_weighted`] with the default cost of `1.0` token
+    /// per dimension
+    pub async fn check_all(
+        &self,
+        user_id: Option<&str>,
+        ip: &str,
+        endpoint: &str,
+    ) -> Result<RateLimitResult, RateLimitError> {
+        self.check_all_weighted(user_id, ip, endpoint, 1.0).await
+    }
+}
+
+/// Resolves every key to the same config, fixed at construction
+///
+/// Backs [`MultiDimensionalRateLimiter::with_fixed_configs`] for callers
+/// that don't need hot-reloadable tiers.
+struct FixedConfigResolver(RateLimitConfig);
+
+#[async_trait]
+impl TierResolver for FixedConfigResolver {
+    async fn resolve(&self, _key: &RateLimitKey) -> RateLimitConfig {
+        self.0
+    }
 }
 
 #[cfg(test)]
@@ -298,6 +526,7 @@ mod tests {
             connection_timeout_ms: 5000,
             command_timeout_ms: 3000,
             cache_ttl_secs: 300,
+            ..Default::default()
         }
     }
 
@@ -316,6 +545,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
 
         // First request should succeed
@@ -334,6 +565,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 5,
             refill_rate: 0.1, // Very slow refill
+            one_time_burst: 0,
+            ..Default::default()
         };
 
         // Consume all tokens
@@ -358,6 +591,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
 
         // Consume all tokens
@@ -383,6 +618,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 5,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
 
         let key1 = RateLimitKey::User("user1".to_string());
@@ -417,23 +654,37 @@ mod tests {
     async fn test_multi_dimensional_limiter() {
         let limiter = Arc::new(setup_limiter().await.unwrap());
 
-        let multi = MultiDimensionalRateLimiter::new(
+        let multi = MultiDimensionalRateLimiter::with_fixed_configs(
             limiter,
             RateLimitConfig {
                 capacity: 10,
                 refill_rate: 1.0,
+                one_time_burst: 0,
+                ..Default::default()
             }, // User
             RateLimitConfig {
                 capacity: 50,
                 refill_rate: 5.0,
+                one_time_burst: 0,
+                ..Default::default()
             }, // IP
             RateLimitConfig {
                 capacity: 100,
                 refill_rate: 10.0,
+                one_time_burst: 0,
+                ..Default::default()
             }, // Endpoint
+            RateLimitConfig {
+                capacity: 20,
+                refill_rate: 2.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // Endpoint+IP
             RateLimitConfig {
                 capacity: 1000,
                 refill_rate: 100.0,
+                one_time_burst: 0,
+                ..Default::default()
             }, // Global
         );
 
@@ -443,4 +694,186 @@ mod tests {
             .unwrap();
         assert!(result.is_allowed());
     }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_multi_dimensional_limiter_weighted_cost_drains_faster() {
+        let limiter = Arc::new(setup_limiter().await.unwrap());
+
+        let multi = MultiDimensionalRateLimiter::with_fixed_configs(
+            limiter,
+            RateLimitConfig {
+                capacity: 5,
+                refill_rate: 1.0 / 3600.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // User
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // IP
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // Endpoint
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // Endpoint+IP
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+                ..Default::default()
+            }, // Global
+        );
+
+        // A cost-5 request (e.g. an expensive completion call) should
+        // exhaust a 5-token user bucket in a single call.
+        let first = multi
+            .check_all_weighted(Some("weighted_user"), "192.168.1.2", "/api/expensive", 5.0)
+            .await
+            .unwrap();
+        assert!(first.is_allowed());
+
+        let second = multi
+            .check_all_weighted(Some("weighted_user"), "192.168.1.2", "/api/expensive", 5.0)
+            .await
+            .unwrap();
+        assert!(second.is_limited());
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_multi_dimensional_limiter_reflects_live_tier_updates() {
+        use crate::ratelimit::tier_resolver::{StaticTierResolver, TierConfigTable};
+        use crate::ratelimit::types::RateLimitTier;
+
+        let limiter = Arc::new(setup_limiter().await.unwrap());
+        let table = Arc::new(TierConfigTable::new());
+
+        // Shrink the Free tier down to a single request so the second call
+        // is denied -- proving the limiter consulted the table at check
+        // time, not at construction time.
+        table
+            .update(
+                RateLimitTier::Free,
+                RateLimitConfig {
+                    capacity: 1,
+                    refill_rate: 1.0 / 3600.0,
+                    one_time_burst: 0,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let multi = MultiDimensionalRateLimiter::new(
+            limiter,
+            Arc::new(StaticTierResolver::new(RateLimitTier::Free, table.clone())),
+            Arc::new(StaticTierResolver::new(RateLimitTier::Unlimited, table.clone())),
+            Arc::new(StaticTierResolver::new(RateLimitTier::Unlimited, table.clone())),
+            Arc::new(StaticTierResolver::new(RateLimitTier::Unlimited, table.clone())),
+            Arc::new(StaticTierResolver::new(RateLimitTier::Unlimited, table)),
+        );
+
+        assert!(multi
+            .check_all(Some("reload_user"), "10.0.0.5", "/api/test")
+            .await
+            .unwrap()
+            .is_allowed());
+        assert!(multi
+            .check_all(Some("reload_user"), "10.0.0.5", "/api/test")
+            .await
+            .unwrap()
+            .is_limited());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_backend_get_set_expire() {
+        let limiter = setup_limiter().await.unwrap();
+        let key = RateLimitKey::User("test_user_backend".to_string());
+        let config = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        RateLimitBackend::incr(&limiter, &key, &config, 1.0)
+            .await
+            .unwrap();
+        assert!(RateLimitBackend::get(&limiter, &key).await.unwrap().is_some());
+
+        RateLimitBackend::set(&limiter, &key, 4.0).await.unwrap();
+        assert_eq!(RateLimitBackend::get(&limiter, &key).await.unwrap(), Some(4.0));
+
+        assert!(RateLimitBackend::expire(&limiter, &key, 60).await.unwrap());
+
+        // Cleanup
+        limiter.reset(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_check_rate_limit_multi_allows_within_both_buckets() {
+        let limiter = setup_limiter().await.unwrap();
+        let key = RateLimitKey::User("test_user_multi_allow".to_string());
+        let ops_config = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+        let bytes_config = RateLimitConfig {
+            capacity: 1000,
+            refill_rate: 100.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        let result = limiter
+            .check_rate_limit_multi(&key, (&ops_config, 1.0), (&bytes_config, 200.0))
+            .await
+            .unwrap();
+        assert!(result.is_allowed());
+
+        limiter.reset(&key).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires Redis
+    async fn test_check_rate_limit_multi_denies_when_bytes_bucket_is_short() {
+        let limiter = setup_limiter().await.unwrap();
+        let key = RateLimitKey::User("test_user_multi_deny".to_string());
+        let ops_config = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+        let bytes_config = RateLimitConfig {
+            capacity: 1000,
+            refill_rate: 100.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        let result = limiter
+            .check_rate_limit_multi(&key, (&ops_config, 1.0), (&bytes_config, 5000.0))
+            .await
+            .unwrap();
+        assert!(result.is_limited());
+        if let RateLimitResult::Limited { reason, .. } = result {
+            assert_eq!(reason, RateLimitReason::TokenQuota(TokenType::Bytes));
+        }
+
+        limiter.reset(&key).await.unwrap();
+    }
 }
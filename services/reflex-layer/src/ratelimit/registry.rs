@@ -0,0 +1,244 @@
+//! Keyed multi-tenant token bucket registry
+//!
+//! A single [`TokenBucket`] only throttles one caller. Real traffic needs
+//! one bucket per user/IP/endpoint/etc, which is exactly the unbounded-map
+//! problem Lemmy's rate-limiter refactor ran into: a distinct bucket per
+//! key, with no cap on how many distinct keys show up, grows forever.
+//! [`TokenBucketRegistry`] maps each [`RateLimitKey`] to its own bucket on
+//! first use and periodically sweeps out buckets that are back at full
+//! capacity, since there's nothing left to remember about them.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+
+use crate::ratelimit::token_bucket::TokenBucket;
+use crate::ratelimit::types::{RateLimitConfig, RateLimitKey, RateLimitResult};
+
+/// Hook for recording allow/reject outcomes against an external metrics
+/// system (e.g. Prometheus) without this library crate depending on one
+/// directly -- the binary crate wires a sink that calls its own
+/// `record_rate_limit_allowed`/`record_rate_limit_rejected`
+pub trait RateLimitMetricsSink: Send + Sync {
+    /// Called when a [`TokenBucketRegistry::check`] call allows a request
+    fn record_allowed(&self);
+    /// Called when a [`TokenBucketRegistry::check`] call denies a request,
+    /// tagged with the key's dimension (`"user"`, `"ip"`, `"endpoint"`, ...)
+    fn record_rejected(&self, dimension: &str);
+}
+
+/// A [`RateLimitMetricsSink`] that records nothing; the default for a
+/// freshly constructed registry
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopMetricsSink;
+
+impl RateLimitMetricsSink for NoopMetricsSink {
+    fn record_allowed(&self) {}
+    fn record_rejected(&self, _dimension: &str) {}
+}
+
+/// Maps each distinct [`RateLimitKey`] to its own [`TokenBucket`]
+///
+/// Backed by a `DashMap` (as [`crate::ratelimit::deferred::DeferredRateLimiter`]
+/// already uses for per-key state), so unrelated keys never contend on the
+/// same lock.
+pub struct TokenBucketRegistry {
+    buckets: DashMap<String, TokenBucket>,
+    metrics: Arc<dyn RateLimitMetricsSink>,
+}
+
+impl TokenBucketRegistry {
+    /// Create an empty registry with no metrics wired up
+    pub fn new() -> Self {
+        Self {
+            buckets: DashMap::new(),
+            metrics: Arc::new(NoopMetricsSink),
+        }
+    }
+
+    /// Route allow/reject outcomes to `metrics` instead of discarding them
+    pub fn with_metrics_sink(self, metrics: Arc<dyn RateLimitMetricsSink>) -> Self {
+        Self { metrics, ..self }
+    }
+
+    /// Check and consume `tokens` from `key`'s bucket, creating it with
+    /// `config` on first use
+    pub fn check(
+        &self,
+        key: &RateLimitKey,
+        config: RateLimitConfig,
+        tokens: f64,
+    ) -> RateLimitResult {
+        let bucket = self
+            .buckets
+            .entry(key.to_redis_key())
+            .or_insert_with(|| TokenBucket::new(config));
+
+        let result = bucket.try_consume(tokens);
+        match &result {
+            RateLimitResult::Allowed { .. } => self.metrics.record_allowed(),
+            RateLimitResult::Limited { .. } => self.metrics.record_rejected(key.dimension()),
+        }
+
+        result
+    }
+
+    /// Number of distinct keys currently tracked
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Whether the registry currently tracks no keys
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Remove every bucket currently at full capacity, since there's
+    /// nothing left to remember about a key that hasn't spent any tokens.
+    ///
+    /// Clamps each remaining bucket's token count into `[0, capacity]`
+    /// first, as a defensive guard against a negative count ever leaking
+    /// out of a bucket implementation bug.
+    pub fn sweep(&self) {
+        self.buckets.retain(|_, bucket| {
+            let capacity = bucket.config().capacity as f64;
+            let tokens = bucket.current_tokens();
+            if tokens < 0.0 {
+                bucket.set_tokens(0.0);
+            }
+            tokens < capacity
+        });
+    }
+
+    /// Spawn a background task that calls [`TokenBucketRegistry::sweep`]
+    /// every `interval`
+    ///
+    /// The task holds only a [`std::sync::Weak`] reference, so it exits on
+    /// its own once every other `Arc<TokenBucketRegistry>` is dropped
+    /// instead of keeping the registry alive forever.
+    pub fn spawn_periodic_sweep(registry: &Arc<Self>, interval: Duration) {
+        let weak = Arc::downgrade(registry);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await; // first tick fires immediately; skip it
+            loop {
+                ticker.tick().await;
+                match weak.upgrade() {
+                    Some(registry) => registry.sweep(),
+                    None => break,
+                }
+            }
+        });
+    }
+}
+
+impl Default for TokenBucketRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration as StdDuration;
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig {
+            capacity: 5,
+            refill_rate: 1.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_creates_bucket_and_enforces_capacity() {
+        let registry = TokenBucketRegistry::new();
+        let key = RateLimitKey::User("alice".to_string());
+
+        for _ in 0..5 {
+            assert!(registry.check(&key, config(), 1.0).is_allowed());
+        }
+        assert!(registry.check(&key, config(), 1.0).is_limited());
+    }
+
+    #[test]
+    fn test_distinct_keys_get_independent_buckets() {
+        let registry = TokenBucketRegistry::new();
+        let alice = RateLimitKey::User("alice".to_string());
+        let bob = RateLimitKey::User("bob".to_string());
+
+        for _ in 0..5 {
+            assert!(registry.check(&alice, config(), 1.0).is_allowed());
+        }
+        assert!(registry.check(&alice, config(), 1.0).is_limited());
+        // bob's bucket is untouched by alice's exhaustion.
+        assert!(registry.check(&bob, config(), 1.0).is_allowed());
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_sweep_removes_full_buckets_but_keeps_partial() {
+        let registry = TokenBucketRegistry::new();
+        let idle = RateLimitKey::User("idle".to_string());
+        let active = RateLimitKey::User("active".to_string());
+
+        // idle's bucket is created but never consumed from.
+        registry.check(&idle, config(), 0.0);
+        registry.check(&active, config(), 1.0);
+
+        assert_eq!(registry.len(), 2);
+        registry.sweep();
+        assert_eq!(registry.len(), 1);
+        assert!(!registry.is_empty());
+    }
+
+    #[test]
+    fn test_metrics_sink_receives_allowed_and_rejected() {
+        struct CountingSink {
+            allowed: AtomicU32,
+            rejected: AtomicU32,
+        }
+        impl RateLimitMetricsSink for CountingSink {
+            fn record_allowed(&self) {
+                self.allowed.fetch_add(1, Ordering::Relaxed);
+            }
+            fn record_rejected(&self, _dimension: &str) {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let sink = Arc::new(CountingSink {
+            allowed: AtomicU32::new(0),
+            rejected: AtomicU32::new(0),
+        });
+        let registry = TokenBucketRegistry::new().with_metrics_sink(sink.clone());
+        let key = RateLimitKey::IP("203.0.113.1".to_string());
+
+        let small_capacity = RateLimitConfig {
+            capacity: 1,
+            refill_rate: 1.0,
+            ..Default::default()
+        };
+        assert!(registry.check(&key, small_capacity, 1.0).is_allowed());
+        assert!(registry.check(&key, small_capacity, 1.0).is_limited());
+
+        assert_eq!(sink.allowed.load(Ordering::Relaxed), 1);
+        assert_eq!(sink.rejected.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_periodic_sweep_reclaims_idle_buckets_in_background() {
+        let registry = Arc::new(TokenBucketRegistry::new());
+        let idle = RateLimitKey::User("idle".to_string());
+        registry.check(&idle, config(), 0.0);
+        assert_eq!(registry.len(), 1);
+
+        TokenBucketRegistry::spawn_periodic_sweep(&registry, StdDuration::from_millis(20));
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+
+        assert_eq!(registry.len(), 0);
+    }
+}
@@ -0,0 +1,366 @@
+//! Runtime-resolvable, hot-reloadable rate-limit tiers
+//!
+//! [`RateLimitTier::config`](crate::ratelimit::RateLimitTier::config) bakes
+//! each tier's capacity/refill rate in at compile time, and
+//! [`MultiDimensionalRateLimiter`](crate::ratelimit::MultiDimensionalRateLimiter)
+//! used to be constructed with one fixed [`RateLimitConfig`] per dimension --
+//! so changing a customer's plan, or just tuning a limit, meant a redeploy.
+//! [`TierResolver`] decouples "which config does this key get right now"
+//! from both of those: [`TierConfigTable`] is an atomically-swappable
+//! tier-to-config map that can be updated live without dropping in-flight
+//! buckets, and [`RedisTierResolver`] additionally looks up which tier a key
+//! is *in* from Redis, so an account upgrade is visible fleet-wide the
+//! moment it's written.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use dashmap::DashMap;
+use redis::AsyncCommands;
+
+use crate::ratelimit::types::{RateLimitConfig, RateLimitError, RateLimitKey, RateLimitTier};
+use crate::ratelimit::window::parse_rate_window;
+use crate::redis_client::RedisClient;
+
+/// Resolves the [`RateLimitConfig`] a given key should be checked against,
+/// consulted on every request so a config change (or plan upgrade) takes
+/// effect immediately rather than at the next process restart
+#[async_trait]
+pub trait TierResolver: Send + Sync {
+    /// Resolve the config `key` should currently be limited by
+    async fn resolve(&self, key: &RateLimitKey) -> RateLimitConfig;
+}
+
+/// Atomically-swappable tier-to-config mapping
+///
+/// Holds one [`RateLimitConfig`] per [`RateLimitTier`], seeded from each
+/// tier's compiled-in default. Updating a tier's config (e.g. an operator
+/// tuning the Pro tier's burst capacity) swaps in a new map without
+/// disturbing any bucket currently in flight, since the bucket state lives
+/// in the rate limit backend, not here.
+pub struct TierConfigTable {
+    configs: ArcSwap<HashMap<RateLimitTier, RateLimitConfig>>,
+}
+
+impl TierConfigTable {
+    /// Create a table seeded with each tier's compiled-in default config
+    pub fn new() -> Self {
+        let defaults = [
+            RateLimitTier::Free,
+            RateLimitTier::Basic,
+            RateLimitTier::Pro,
+            RateLimitTier::Enterprise,
+            RateLimitTier::Unlimited,
+        ]
+        .into_iter()
+        .map(|tier| (tier, tier.config()))
+        .collect();
+
+        Self {
+            configs: ArcSwap::new(Arc::new(defaults)),
+        }
+    }
+
+    /// Current config for `tier`, falling back to its compiled-in default
+    /// if the table has no override for it
+    pub fn get(&self, tier: RateLimitTier) -> RateLimitConfig {
+        self.configs
+            .load()
+            .get(&tier)
+            .copied()
+            .unwrap_or_else(|| tier.config())
+    }
+
+    /// Atomically update `tier`'s config, leaving every other tier's config
+    /// and every in-flight bucket untouched
+    ///
+    /// Rejects a `config` that fails [`RateLimitConfig::is_valid`] (e.g.
+    /// zero capacity or a non-positive refill rate) rather than applying
+    /// it: a reload endpoint that let such a config through would silently
+    /// lock every key in that tier out until the next restart.
+    pub fn update(&self, tier: RateLimitTier, config: RateLimitConfig) -> Result<(), RateLimitError> {
+        if !config.is_valid() {
+            return Err(RateLimitError::Config(format!(
+                "refusing to apply invalid rate limit config for tier {:?}: \
+                 capacity and refill_rate must be positive (got capacity={}, refill_rate={})",
+                tier, config.capacity, config.refill_rate
+            )));
+        }
+
+        let mut updated = (**self.configs.load()).clone();
+        updated.insert(tier, config);
+        self.configs.store(Arc::new(updated));
+        Ok(())
+    }
+
+    /// Parse a human-readable window (e.g. `"100/hour"`, see
+    /// [`parse_rate_window`]) and atomically update `tier` to it
+    pub fn update_from_window(&self, tier: RateLimitTier, window: &str) -> Result<(), RateLimitError> {
+        self.update(tier, parse_rate_window(window)?)
+    }
+}
+
+impl Default for TierConfigTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolves every key to the same, fixed tier's current config
+///
+/// Used where a dimension's tier doesn't vary per key (e.g. the IP,
+/// endpoint, and global dimensions of [`MultiDimensionalRateLimiter`]),
+/// while still allowing that tier's config to be tuned live via the shared
+/// [`TierConfigTable`].
+pub struct StaticTierResolver {
+    tier: RateLimitTier,
+    table: Arc<TierConfigTable>,
+}
+
+impl StaticTierResolver {
+    /// Resolve every key to `tier`'s current config in `table`
+    pub fn new(tier: RateLimitTier, table: Arc<TierConfigTable>) -> Self {
+        Self { tier, table }
+    }
+}
+
+#[async_trait]
+impl TierResolver for StaticTierResolver {
+    async fn resolve(&self, _key: &RateLimitKey) -> RateLimitConfig {
+        self.table.get(self.tier)
+    }
+}
+
+/// How long a [`RedisTierResolver`] trusts a locally-cached tier lookup
+/// before re-checking Redis
+const DEFAULT_TIER_CACHE_TTL: Duration = Duration::from_secs(30);
+
+struct CachedTier {
+    tier: RateLimitTier,
+    resolved_at: Instant,
+}
+
+/// Looks up which tier a key is currently in from Redis, so a plan change
+/// is consistent across a horizontally-scaled fleet, with a short local
+/// cache so that lookup isn't on the hot path of every request
+pub struct RedisTierResolver {
+    redis: Arc<RedisClient>,
+    table: Arc<TierConfigTable>,
+    default_tier: RateLimitTier,
+    cache_ttl: Duration,
+    cache: DashMap<String, CachedTier>,
+}
+
+impl RedisTierResolver {
+    /// Create a resolver backed by `redis`, falling back to `default_tier`
+    /// for keys with no tier assignment stored, using the default cache TTL
+    pub fn new(redis: Arc<RedisClient>, table: Arc<TierConfigTable>, default_tier: RateLimitTier) -> Self {
+        Self::with_cache_ttl(redis, table, default_tier, DEFAULT_TIER_CACHE_TTL)
+    }
+
+    /// Create a resolver with a custom local-cache TTL
+    pub fn with_cache_ttl(
+        redis: Arc<RedisClient>,
+        table: Arc<TierConfigTable>,
+        default_tier: RateLimitTier,
+        cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            redis,
+            table,
+            default_tier,
+            cache_ttl,
+            cache: DashMap::new(),
+        }
+    }
+
+    /// Redis key holding the tier assignment for a rate-limit key
+    fn tier_key(key: &RateLimitKey) -> String {
+        format!("{}:tier", key.to_redis_key())
+    }
+
+    /// Record (or overwrite) the tier a key is assigned to
+    pub async fn set_tier(&self, key: &RateLimitKey, tier: RateLimitTier) -> Result<(), RateLimitError> {
+        let mut conn = self.redis.get_connection(&Self::tier_key(key)).await?;
+        let tier_name = tier_to_str(tier);
+        conn.set::<_, _, ()>(Self::tier_key(key), tier_name)
+            .await
+            .map_err(RateLimitError::Redis)?;
+        self.cache.remove(&key.to_redis_key());
+        Ok(())
+    }
+
+    async fn lookup_tier(&self, key: &RateLimitKey) -> RateLimitTier {
+        let mut conn = match self.redis.get_connection(&Self::tier_key(key)).await {
+            Ok(conn) => conn,
+            Err(_) => return self.default_tier,
+        };
+
+        let tier_name: Option<String> = conn.get(Self::tier_key(key)).await.unwrap_or(None);
+        tier_name
+            .and_then(|name| tier_from_str(&name))
+            .unwrap_or(self.default_tier)
+    }
+}
+
+#[async_trait]
+impl TierResolver for RedisTierResolver {
+    async fn resolve(&self, key: &RateLimitKey) -> RateLimitConfig {
+        let cache_key = key.to_redis_key();
+
+        if let Some(cached) = self.cache.get(&cache_key) {
+            if cached.resolved_at.elapsed() < self.cache_ttl {
+                return self.table.get(cached.tier);
+            }
+        }
+
+        let tier = self.lookup_tier(key).await;
+        self.cache.insert(
+            cache_key,
+            CachedTier {
+                tier,
+                resolved_at: Instant::now(),
+            },
+        );
+        self.table.get(tier)
+    }
+}
+
+fn tier_to_str(tier: RateLimitTier) -> &'static str {
+    match tier {
+        RateLimitTier::Free => "free",
+        RateLimitTier::Basic => "basic",
+        RateLimitTier::Pro => "pro",
+        RateLimitTier::Enterprise => "enterprise",
+        RateLimitTier::Unlimited => "unlimited",
+    }
+}
+
+fn tier_from_str(name: &str) -> Option<RateLimitTier> {
+    match name {
+        "free" => Some(RateLimitTier::Free),
+        "basic" => Some(RateLimitTier::Basic),
+        "pro" => Some(RateLimitTier::Pro),
+        "enterprise" => Some(RateLimitTier::Enterprise),
+        "unlimited" => Some(RateLimitTier::Unlimited),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_table_defaults_match_compiled_in_tier_configs() {
+        let table = TierConfigTable::new();
+        assert_eq!(table.get(RateLimitTier::Free), RateLimitTier::Free.config());
+        assert_eq!(table.get(RateLimitTier::Pro), RateLimitTier::Pro.config());
+    }
+
+    #[tokio::test]
+    async fn test_table_update_overrides_one_tier_only() {
+        let table = TierConfigTable::new();
+        let new_pro_config = RateLimitConfig {
+            capacity: 999,
+            refill_rate: 42.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        table.update(RateLimitTier::Pro, new_pro_config).unwrap();
+
+        assert_eq!(table.get(RateLimitTier::Pro), new_pro_config);
+        assert_eq!(table.get(RateLimitTier::Free), RateLimitTier::Free.config());
+    }
+
+    #[tokio::test]
+    async fn test_static_resolver_reflects_live_table_updates() {
+        let table = Arc::new(TierConfigTable::new());
+        let resolver = StaticTierResolver::new(RateLimitTier::Basic, table.clone());
+        let key = RateLimitKey::User("someone".to_string());
+
+        assert_eq!(resolver.resolve(&key).await, RateLimitTier::Basic.config());
+
+        let tenth_of_pro = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+        table.update(RateLimitTier::Basic, tenth_of_pro).unwrap();
+
+        assert_eq!(resolver.resolve(&key).await, tenth_of_pro);
+    }
+
+    #[test]
+    fn test_update_rejects_zero_capacity_config_and_leaves_table_unchanged() {
+        let table = TierConfigTable::new();
+        let zero_capacity = RateLimitConfig {
+            capacity: 0,
+            refill_rate: 42.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        assert!(table.update(RateLimitTier::Pro, zero_capacity).is_err());
+        assert_eq!(table.get(RateLimitTier::Pro), RateLimitTier::Pro.config());
+    }
+
+    #[test]
+    fn test_update_rejects_non_positive_refill_rate_and_leaves_table_unchanged() {
+        let table = TierConfigTable::new();
+        let frozen_refill = RateLimitConfig {
+            capacity: 500,
+            refill_rate: 0.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+
+        assert!(table.update(RateLimitTier::Pro, frozen_refill).is_err());
+        assert_eq!(table.get(RateLimitTier::Pro), RateLimitTier::Pro.config());
+    }
+
+    #[test]
+    fn test_update_from_window_parses_and_applies() {
+        let table = TierConfigTable::new();
+        table
+            .update_from_window(RateLimitTier::Pro, "5000/hour")
+            .unwrap();
+
+        let config = table.get(RateLimitTier::Pro);
+        assert!((config.requests_per_hour() - 5000.0).abs() < 0.001);
+        assert_eq!(config.capacity, 500);
+    }
+
+    #[test]
+    fn test_update_from_window_rejects_malformed_window() {
+        let table = TierConfigTable::new();
+        assert!(table
+            .update_from_window(RateLimitTier::Pro, "nonsense")
+            .is_err());
+        // Untouched on error.
+        assert_eq!(table.get(RateLimitTier::Pro), RateLimitTier::Pro.config());
+    }
+
+    #[test]
+    fn test_tier_str_round_trip() {
+        for tier in [
+            RateLimitTier::Free,
+            RateLimitTier::Basic,
+            RateLimitTier::Pro,
+            RateLimitTier::Enterprise,
+            RateLimitTier::Unlimited,
+        ] {
+            assert_eq!(tier_from_str(tier_to_str(tier)), Some(tier));
+        }
+    }
+
+    #[test]
+    fn test_tier_from_str_rejects_unknown_names() {
+        assert_eq!(tier_from_str("gold"), None);
+    }
+}
@@ -3,27 +3,112 @@
 //! Provides a thread-safe, local token bucket for rate limiting without Redis.
 //! Useful for testing and single-instance deployments.
 
-use crate::ratelimit::types::{RateLimitConfig, RateLimitResult};
+use crate::ratelimit::clock::{Clock, SystemClock};
+use crate::ratelimit::types::{RateLimitConfig, RateLimitReason, RateLimitResult, TokenType};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
 /// Local token bucket for rate limiting
 ///
 /// This is a thread-safe, in-memory implementation suitable for single-instance
 /// deployments or testing. For distributed systems, use RedisRateLimiter instead.
+///
+/// Tracks request frequency (the `TokenType::Ops` bucket) and, if
+/// configured via [`TokenBucket::with_bytes`], payload bandwidth (the
+/// `TokenType::Bytes` bucket) as two independent buckets so a single
+/// request can be throttled by count, size, or both.
 #[derive(Clone)]
 pub struct TokenBucket {
     state: Arc<Mutex<TokenBucketState>>,
     config: RateLimitConfig,
+    bytes: Option<SingleBucket>,
+    /// Wakes waiters parked in [`TokenBucket::consume_or_wait`] as soon as
+    /// any consumer observes fresh tokens, instead of making every waiter
+    /// sleep out its own full `retry_after_ms`
+    notify: Arc<Notify>,
+    /// Time source used for refill math; overridable via
+    /// [`TokenBucket::with_clock`] so tests can advance time deterministically
+    clock: Arc<dyn Clock>,
+}
+
+/// An additional, independently refilling bucket for a second `TokenType`
+#[derive(Clone)]
+struct SingleBucket {
+    state: Arc<Mutex<TokenBucketState>>,
+    config: RateLimitConfig,
 }
 
 struct TokenBucketState {
-    /// Current number of tokens (can be fractional)
+    /// Current number of tokens in the replenishable pool (can be fractional)
     tokens: f64,
+    /// Remaining one-time burst credit (from `RateLimitConfig::one_time_burst`);
+    /// spent before the replenishable pool and never refilled
+    burst_remaining: f64,
     /// Last time tokens were refilled
     last_refill: Instant,
 }
 
+impl TokenBucketState {
+    fn full(config: &RateLimitConfig, clock: &dyn Clock) -> Self {
+        Self {
+            tokens: config.capacity as f64,
+            burst_remaining: config.one_time_burst as f64,
+            last_refill: clock.now(),
+        }
+    }
+
+    /// Total tokens available right now: the replenishable pool plus
+    /// whatever burst credit hasn't been spent yet
+    fn available(&self) -> f64 {
+        self.tokens + self.burst_remaining
+    }
+
+    /// Deduct `amount` tokens, spending burst credit first since it's the
+    /// one part of the bucket that never comes back
+    fn consume(&mut self, amount: f64) {
+        let from_burst = amount.min(self.burst_remaining);
+        self.burst_remaining -= from_burst;
+        self.tokens -= amount - from_burst;
+    }
+}
+
+/// Refill `state`'s replenishable pool per `config`'s rate, capped at
+/// `config.capacity`; the one-time burst credit is never touched here
+fn refill(state: &mut TokenBucketState, config: &RateLimitConfig, clock: &dyn Clock) {
+    let now = clock.now();
+    let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+
+    let new_tokens = elapsed * config.refill_rate;
+    state.tokens = (state.tokens + new_tokens).min(config.capacity as f64);
+    state.last_refill = now;
+}
+
+/// Time until `config`'s bucket is fully refilled from `current_tokens`,
+/// folding in `rate_usage_factor`/`burst_pct`/`duration_overhead_ms` so the
+/// estimate matches the throttled target rather than the raw capacity/rate
+fn calculate_reset_time(config: &RateLimitConfig, current_tokens: f64) -> u64 {
+    let effective_capacity = config.effective_capacity();
+    if current_tokens >= effective_capacity {
+        return config.duration_overhead_ms;
+    }
+
+    let tokens_needed = effective_capacity - current_tokens;
+    let seconds_until_full = tokens_needed / config.effective_refill_rate();
+
+    (seconds_until_full * 1000.0) as u64 + config.duration_overhead_ms
+}
+
+/// Time to wait before `config`'s bucket has `tokens_needed` available,
+/// folding in `rate_usage_factor`/`duration_overhead_ms`
+fn calculate_retry_time(config: &RateLimitConfig, current_tokens: f64, tokens_needed: f64) -> u64 {
+    let tokens_deficit = tokens_needed - current_tokens;
+    let seconds_to_wait = tokens_deficit / config.effective_refill_rate();
+
+    // Add small buffer (100ms) plus any configured overhead
+    ((seconds_to_wait * 1000.0) + 100.0) as u64 + config.duration_overhead_ms
+}
+
 impl TokenBucket {
     /// Create a new token bucket
     ///
@@ -33,15 +118,57 @@ impl TokenBucket {
     /// # Returns
     /// * `Self` - New token bucket initialized with full capacity
     pub fn new(config: RateLimitConfig) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
         Self {
-            state: Arc::new(Mutex::new(TokenBucketState {
-                tokens: config.capacity as f64,
-                last_refill: Instant::now(),
-            })),
+            state: Arc::new(Mutex::new(TokenBucketState::full(&config, clock.as_ref()))),
             config,
+            bytes: None,
+            notify: Arc::new(Notify::new()),
+            clock,
         }
     }
 
+    /// Create a token bucket that tracks both `TokenType::Ops` and
+    /// `TokenType::Bytes` as independent buckets
+    ///
+    /// # Arguments
+    /// * `ops_config` - Request-frequency bucket configuration
+    /// * `bytes_config` - Payload-bandwidth bucket configuration
+    pub fn with_bytes(ops_config: RateLimitConfig, bytes_config: RateLimitConfig) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+        Self {
+            state: Arc::new(Mutex::new(TokenBucketState::full(&ops_config, clock.as_ref()))),
+            config: ops_config,
+            bytes: Some(SingleBucket {
+                state: Arc::new(Mutex::new(TokenBucketState::full(
+                    &bytes_config,
+                    clock.as_ref(),
+                ))),
+                config: bytes_config,
+            }),
+            notify: Arc::new(Notify::new()),
+            clock,
+        }
+    }
+
+    /// Use a custom time source instead of the real wall clock
+    ///
+    /// Resets both buckets to full capacity as observed by the new clock, so
+    /// tests can inject a [`crate::ratelimit::clock::FakeClock`] and step
+    /// time forward precisely instead of sleeping.
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            *state = TokenBucketState::full(&self.config, clock.as_ref());
+        }
+        if let Some(bytes) = &self.bytes {
+            let mut state = bytes.state.lock().unwrap();
+            *state = TokenBucketState::full(&bytes.config, clock.as_ref());
+        }
+
+        Self { clock, ..self }
+    }
+
     /// Try to consume tokens from the bucket
     ///
     /// # Arguments
@@ -51,77 +178,227 @@ impl TokenBucket {
     /// * `RateLimitResult::Allowed` - Request allowed, tokens consumed
     /// * `RateLimitResult::Limited` - Request denied, insufficient tokens
     pub fn try_consume(&self, tokens: f64) -> RateLimitResult {
-        let mut state = self.state.lock().unwrap();
+        Self::try_consume_one(
+            &self.state,
+            &self.config,
+            self.clock.as_ref(),
+            tokens,
+            RateLimitReason::Custom,
+        )
+    }
 
-        // Refill tokens based on elapsed time
-        self.refill(&mut state);
+    /// Asynchronously wait until `tokens` are available, then consume them
+    ///
+    /// Where [`TokenBucket::try_consume`] returns `Limited` immediately and
+    /// leaves polling to the caller, this sleeps for the reported
+    /// `retry_after_ms` and retries. A successful consumption wakes every
+    /// other waiter on this bucket so they recheck right away instead of
+    /// sleeping out a now-stale deadline, which keeps a burst of callers
+    /// under sustained overload from each polling independently once
+    /// tokens start flowing again.
+    pub async fn consume_or_wait(&self, tokens: f64) -> RateLimitResult {
+        loop {
+            let result = self.try_consume(tokens);
+            let retry_after_ms = match result {
+                RateLimitResult::Allowed { .. } => {
+                    self.notify.notify_waiters();
+                    return result;
+                }
+                RateLimitResult::Limited { retry_after_ms, .. } => retry_after_ms,
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_millis(retry_after_ms)) => {}
+                _ = self.notify.notified() => {}
+            }
+        }
+    }
 
-        if state.tokens >= tokens {
-            // Enough tokens available
-            state.tokens -= tokens;
+    /// Try to consume `amount` tokens from a single dimension
+    ///
+    /// This is the dual-dimension entry point: the per-dimension counterpart
+    /// to [`TokenBucket::try_consume_multi`] for callers that only care about
+    /// one of `TokenType::Ops` / `TokenType::Bytes` at a time.
+    ///
+    /// Consuming `TokenType::Bytes` on a bucket created via [`TokenBucket::new`]
+    /// (no bytes dimension configured) always succeeds, since no bandwidth
+    /// limit was requested for it.
+    ///
+    /// # Arguments
+    /// * `token_type` - Which bucket to consume from
+    /// * `amount` - Number of tokens to consume
+    pub fn consume(&self, token_type: TokenType, amount: f64) -> RateLimitResult {
+        match token_type {
+            TokenType::Ops => self.try_consume(amount),
+            TokenType::Bytes => match &self.bytes {
+                Some(bytes) => Self::try_consume_one(
+                    &bytes.state,
+                    &bytes.config,
+                    self.clock.as_ref(),
+                    amount,
+                    RateLimitReason::TokenQuota(TokenType::Bytes),
+                ),
+                None => RateLimitResult::Allowed {
+                    remaining: f64::MAX,
+                    reset_after_ms: 0,
+                },
+            },
+        }
+    }
 
-            RateLimitResult::Allowed {
-                remaining: state.tokens,
-                reset_after_ms: self.calculate_reset_time(state.tokens),
+    /// Atomically check and consume several token types at once
+    ///
+    /// Allowed only if every requested bucket has enough tokens; if any is
+    /// short, nothing is consumed and the result is `Limited`, tagged with
+    /// whichever token type was exhausted and a `retry_after_ms` that is
+    /// the max over the deficient buckets (so the caller waits long enough
+    /// to satisfy all of them).
+    pub fn try_consume_multi(&self, requests: &[(TokenType, f64)]) -> RateLimitResult {
+        // Lock in a fixed order (ops before bytes) so concurrent multi-consume
+        // calls can't deadlock against each other.
+        let mut ops_state = self.state.lock().unwrap();
+        refill(&mut ops_state, &self.config, self.clock.as_ref());
+        let mut bytes_state = self.bytes.as_ref().map(|b| b.state.lock().unwrap());
+        if let (Some(state), Some(bytes)) = (bytes_state.as_mut(), &self.bytes) {
+            refill(state, &bytes.config, self.clock.as_ref());
+        }
+
+        // Sum requested amounts per token type before checking for a
+        // deficit: two entries of the same type in one call draw from the
+        // same starting balance, so checking each against the full balance
+        // independently would let both pass even when their combined total
+        // doesn't fit.
+        let mut ops_requested = 0.0;
+        let mut bytes_requested = 0.0;
+        for &(token_type, amount) in requests {
+            match token_type {
+                TokenType::Ops => ops_requested += amount,
+                TokenType::Bytes => bytes_requested += amount,
             }
-        } else {
-            // Insufficient tokens
-            use crate::ratelimit::types::RateLimitReason;
+        }
 
-            let retry_after_ms = self.calculate_retry_time(state.tokens, tokens);
+        let mut deficits: Vec<(TokenType, u64)> = Vec::new();
+        if ops_requested > 0.0 {
+            let current = ops_state.available();
+            if current < ops_requested {
+                deficits.push((
+                    TokenType::Ops,
+                    calculate_retry_time(&self.config, current, ops_requested),
+                ));
+            }
+        }
+        if bytes_requested > 0.0 {
+            if let (Some(state), Some(bytes)) = (bytes_state.as_ref(), &self.bytes) {
+                let current = state.available();
+                if current < bytes_requested {
+                    deficits.push((
+                        TokenType::Bytes,
+                        calculate_retry_time(&bytes.config, current, bytes_requested),
+                    ));
+                }
+            }
+            // No bytes bucket configured: unbounded, never deficient.
+        }
 
-            RateLimitResult::Limited {
-                reason: RateLimitReason::Custom,
+        if let Some(&(token_type, retry_after_ms)) =
+            deficits.iter().max_by_key(|(_, retry_ms)| *retry_ms)
+        {
+            let current_tokens = match token_type {
+                TokenType::Ops => ops_state.available(),
+                TokenType::Bytes => bytes_state.as_ref().map(|s| s.available()).unwrap_or(0.0),
+            };
+            return RateLimitResult::Limited {
+                reason: RateLimitReason::TokenQuota(token_type),
                 retry_after_ms,
-                current_tokens: state.tokens,
-            }
+                current_tokens,
+            };
         }
-    }
 
-    /// Refill tokens based on elapsed time
-    fn refill(&self, state: &mut TokenBucketState) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        for &(token_type, amount) in requests {
+            match token_type {
+                TokenType::Ops => ops_state.consume(amount),
+                TokenType::Bytes => {
+                    if let Some(state) = bytes_state.as_mut() {
+                        state.consume(amount);
+                    }
+                }
+            }
+        }
 
-        // Calculate new tokens
-        let new_tokens = elapsed * self.config.refill_rate;
-        state.tokens = (state.tokens + new_tokens).min(self.config.capacity as f64);
-        state.last_refill = now;
+        RateLimitResult::Allowed {
+            remaining: ops_state.available(),
+            reset_after_ms: calculate_reset_time(&self.config, ops_state.tokens),
+        }
     }
 
-    /// Calculate time until bucket is fully reset
-    fn calculate_reset_time(&self, current_tokens: f64) -> u64 {
-        if current_tokens >= self.config.capacity as f64 {
-            return 0;
-        }
+    fn try_consume_one(
+        state: &Mutex<TokenBucketState>,
+        config: &RateLimitConfig,
+        clock: &dyn Clock,
+        tokens: f64,
+        limited_reason: RateLimitReason,
+    ) -> RateLimitResult {
+        let mut state = state.lock().unwrap();
 
-        let tokens_needed = self.config.capacity as f64 - current_tokens;
-        let seconds_until_full = tokens_needed / self.config.refill_rate;
+        refill(&mut state, config, clock);
 
-        (seconds_until_full * 1000.0) as u64
-    }
+        let available = state.available();
+        if available >= tokens {
+            state.consume(tokens);
 
-    /// Calculate time to wait before retrying
-    fn calculate_retry_time(&self, current_tokens: f64, tokens_needed: f64) -> u64 {
-        let tokens_deficit = tokens_needed - current_tokens;
-        let seconds_to_wait = tokens_deficit / self.config.refill_rate;
+            RateLimitResult::Allowed {
+                remaining: state.available(),
+                reset_after_ms: calculate_reset_time(config, state.tokens),
+            }
+        } else {
+            let retry_after_ms = calculate_retry_time(config, available, tokens);
 
-        // Add small buffer (100ms)
-        ((seconds_to_wait * 1000.0) + 100.0) as u64
+            RateLimitResult::Limited {
+                reason: limited_reason,
+                retry_after_ms,
+                current_tokens: available,
+            }
+        }
     }
 
-    /// Get current token count (without consuming)
+    /// Get current token count, including any unspent burst credit
+    /// (without consuming)
     pub fn current_tokens(&self) -> f64 {
         let mut state = self.state.lock().unwrap();
-        self.refill(&mut state);
-        state.tokens
+        refill(&mut state, &self.config, self.clock.as_ref());
+        state.available()
+    }
+
+    /// Get the current bytes-bucket token count (including any unspent
+    /// burst credit), or `None` if no bytes dimension was configured
+    pub fn current_bytes_tokens(&self) -> Option<f64> {
+        let bytes = self.bytes.as_ref()?;
+        let mut state = bytes.state.lock().unwrap();
+        refill(&mut state, &bytes.config, self.clock.as_ref());
+        Some(state.available())
     }
 
-    /// Reset the bucket to full capacity
+    /// Reset the bucket to full capacity, restoring the one-time burst
+    /// credit as well
     pub fn reset(&self) {
         let mut state = self.state.lock().unwrap();
         state.tokens = self.config.capacity as f64;
-        state.last_refill = Instant::now();
+        state.burst_remaining = self.config.one_time_burst as f64;
+        state.last_refill = self.clock.now();
+
+        if let Some(bytes) = &self.bytes {
+            let mut bytes_state = bytes.state.lock().unwrap();
+            bytes_state.tokens = bytes.config.capacity as f64;
+            bytes_state.burst_remaining = bytes.config.one_time_burst as f64;
+            bytes_state.last_refill = self.clock.now();
+        }
+    }
+
+    /// Overwrite the current token count, clamped to `[0, capacity]`
+    pub fn set_tokens(&self, tokens: f64) {
+        let mut state = self.state.lock().unwrap();
+        state.tokens = tokens.clamp(0.0, self.config.capacity as f64);
+        state.last_refill = self.clock.now();
     }
 
     /// Get the configuration for this bucket
@@ -133,6 +410,7 @@ impl TokenBucket {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ratelimit::clock::FakeClock;
     use std::thread::sleep;
     use std::time::Duration;
 
@@ -141,6 +419,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -152,6 +432,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -168,6 +450,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -188,6 +472,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 10.0, // 10 tokens per second
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -207,6 +493,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 100.0, // Very fast refill
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -226,6 +514,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -242,6 +532,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -261,6 +553,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -280,6 +574,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 100,
             refill_rate: 10.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = Arc::new(TokenBucket::new(config));
 
@@ -304,6 +600,8 @@ mod tests {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 2.0, // 2 tokens per second
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
@@ -326,15 +624,379 @@ mod tests {
         assert!(bucket.try_consume(1.0).is_limited());
     }
 
+    #[test]
+    fn test_token_bucket_set_tokens() {
+        let config = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        };
+        let bucket = TokenBucket::new(config);
+
+        bucket.set_tokens(3.0);
+        assert_eq!(bucket.current_tokens(), 3.0);
+
+        // Clamped to capacity
+        bucket.set_tokens(100.0);
+        assert_eq!(bucket.current_tokens(), 10.0);
+
+        // Clamped to zero
+        bucket.set_tokens(-5.0);
+        assert_eq!(bucket.current_tokens(), 0.0);
+    }
+
     #[test]
     fn test_token_bucket_config_access() {
         let config = RateLimitConfig {
             capacity: 10,
             refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
         };
         let bucket = TokenBucket::new(config);
 
         assert_eq!(bucket.config().capacity, 10);
         assert_eq!(bucket.config().refill_rate, 1.0);
     }
+
+    #[test]
+    fn test_consume_bytes_without_bytes_bucket_is_unbounded() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        });
+
+        // No bytes dimension was configured, so it's never limited.
+        assert!(bucket.consume(TokenType::Bytes, 1_000_000.0).is_allowed());
+        // The ops bucket is untouched.
+        assert_eq!(bucket.current_tokens(), 10.0);
+    }
+
+    #[test]
+    fn test_with_bytes_tracks_independent_buckets() {
+        let bucket = TokenBucket::with_bytes(
+            RateLimitConfig {
+                capacity: 10,
+                refill_rate: 1.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+        );
+
+        assert!(bucket.consume(TokenType::Ops, 1.0).is_allowed());
+        assert_eq!(bucket.current_tokens(), 9.0);
+        assert_eq!(bucket.current_bytes_tokens(), Some(1000.0));
+
+        assert!(bucket.consume(TokenType::Bytes, 400.0).is_allowed());
+        assert_eq!(bucket.current_bytes_tokens(), Some(600.0));
+        // Consuming bytes doesn't touch the ops bucket.
+        assert_eq!(bucket.current_tokens(), 9.0);
+    }
+
+    #[test]
+    fn test_try_consume_multi_allows_when_both_buckets_have_headroom() {
+        let bucket = TokenBucket::with_bytes(
+            RateLimitConfig {
+                capacity: 10,
+                refill_rate: 1.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+        );
+
+        let result =
+            bucket.try_consume_multi(&[(TokenType::Ops, 1.0), (TokenType::Bytes, 500.0)]);
+        assert!(result.is_allowed());
+        assert_eq!(bucket.current_tokens(), 9.0);
+        assert_eq!(bucket.current_bytes_tokens(), Some(500.0));
+    }
+
+    #[test]
+    fn test_try_consume_multi_denies_all_when_one_bucket_is_short() {
+        let bucket = TokenBucket::with_bytes(
+            RateLimitConfig {
+                capacity: 10,
+                refill_rate: 1.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+            RateLimitConfig {
+                capacity: 1000,
+                refill_rate: 100.0,
+                one_time_burst: 0,
+            ..Default::default()
+        },
+        );
+
+        // Bytes request exceeds the 1000-byte bucket; nothing should be consumed.
+        let result =
+            bucket.try_consume_multi(&[(TokenType::Ops, 1.0), (TokenType::Bytes, 5000.0)]);
+        assert!(result.is_limited());
+        if let RateLimitResult::Limited { reason, .. } = result {
+            assert_eq!(reason, RateLimitReason::TokenQuota(TokenType::Bytes));
+        }
+
+        // Ops tokens were not consumed either, since the request is all-or-nothing.
+        assert_eq!(bucket.current_tokens(), 10.0);
+        assert_eq!(bucket.current_bytes_tokens(), Some(1000.0));
+    }
+
+    #[test]
+    fn test_try_consume_multi_sums_duplicate_token_types_in_one_call() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 0,
+            ..Default::default()
+        });
+
+        // Two Ops entries summing to 12 against a 10-token bucket must be
+        // denied as a whole, not each pass independently against the same
+        // starting balance of 10.
+        let result =
+            bucket.try_consume_multi(&[(TokenType::Ops, 6.0), (TokenType::Ops, 6.0)]);
+        assert!(result.is_limited());
+        assert_eq!(bucket.current_tokens(), 10.0);
+
+        // A combined total that fits is still allowed and charged in full.
+        let result =
+            bucket.try_consume_multi(&[(TokenType::Ops, 4.0), (TokenType::Ops, 6.0)]);
+        assert!(result.is_allowed());
+        assert_eq!(bucket.current_tokens(), 0.0);
+    }
+
+    #[test]
+    fn test_one_time_burst_adds_to_initial_capacity() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 5,
+            ..Default::default()
+        });
+
+        assert_eq!(bucket.current_tokens(), 15.0);
+    }
+
+    #[test]
+    fn test_one_time_burst_is_consumed_first() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 5,
+            ..Default::default()
+        });
+
+        // Spend the 5-token burst without touching the steady-state pool.
+        assert!(bucket.try_consume(5.0).is_allowed());
+        assert_eq!(bucket.current_tokens(), 10.0);
+
+        // Further consumption now comes out of the steady-state pool.
+        assert!(bucket.try_consume(10.0).is_allowed());
+        assert_eq!(bucket.current_tokens(), 0.0);
+        assert!(bucket.try_consume(1.0).is_limited());
+    }
+
+    #[test]
+    fn test_one_time_burst_never_regenerates() {
+        let config = RateLimitConfig {
+            capacity: 10,
+            refill_rate: 100.0, // Very fast refill
+            one_time_burst: 5,
+            ..Default::default()
+        };
+        let bucket = TokenBucket::new(config);
+
+        // Spend the full bucket, including the burst credit.
+        assert!(bucket.try_consume(15.0).is_allowed());
+        assert_eq!(bucket.current_tokens(), 0.0);
+
+        // Plenty of time for the steady-state pool to refill completely...
+        sleep(Duration::from_millis(200));
+
+        // ...but the burst credit is gone for good.
+        let tokens = bucket.current_tokens();
+        assert!(tokens <= 10.0, "burst regenerated: {tokens}");
+    }
+
+    #[test]
+    fn test_reset_restores_one_time_burst() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            one_time_burst: 5,
+            ..Default::default()
+        });
+
+        bucket.try_consume(15.0);
+        assert_eq!(bucket.current_tokens(), 0.0);
+
+        bucket.reset();
+        assert_eq!(bucket.current_tokens(), 15.0);
+    }
+
+    #[test]
+    fn test_duration_overhead_is_added_to_retry_after_ms() {
+        let baseline = TokenBucket::new(RateLimitConfig {
+            capacity: 1,
+            refill_rate: 1.0,
+            ..Default::default()
+        });
+        baseline.try_consume(1.0);
+        let baseline_retry = match baseline.try_consume(1.0) {
+            RateLimitResult::Limited { retry_after_ms, .. } => retry_after_ms,
+            other => panic!("expected Limited, got {other:?}"),
+        };
+
+        let throttled = TokenBucket::new(RateLimitConfig {
+            capacity: 1,
+            refill_rate: 1.0,
+            duration_overhead_ms: 500,
+            ..Default::default()
+        });
+        throttled.try_consume(1.0);
+        let throttled_retry = match throttled.try_consume(1.0) {
+            RateLimitResult::Limited { retry_after_ms, .. } => retry_after_ms,
+            other => panic!("expected Limited, got {other:?}"),
+        };
+
+        assert_eq!(throttled_retry, baseline_retry + 500);
+    }
+
+    #[test]
+    fn test_burst_pct_shrinks_effective_capacity_for_reset_estimate() {
+        // burst_pct scales the *estimated* reset time, not the actual token
+        // count: with burst_pct 0.5, 9 of 10 real tokens already clears the
+        // 5-token effective capacity, so reset_after_ms reports "already
+        // reset" even though the real bucket isn't literally full.
+        let throttled = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            burst_pct: 0.5,
+            ..Default::default()
+        });
+        let result = throttled.try_consume(1.0);
+        assert!(matches!(
+            result,
+            RateLimitResult::Allowed { reset_after_ms: 0, .. }
+        ));
+
+        // At full burst_pct, the same consumption still has 1 token of
+        // headroom left to refill, so reset_after_ms is nonzero.
+        let full_burst = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 1.0,
+            ..Default::default()
+        });
+        let result = full_burst.try_consume(1.0);
+        assert!(matches!(
+            result,
+            RateLimitResult::Allowed { reset_after_ms, .. } if reset_after_ms > 0
+        ));
+    }
+
+    #[test]
+    fn test_fake_clock_refill_is_exact_with_zero_sleeping() {
+        let clock = FakeClock::new();
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 10.0, // 10 tokens per second
+            one_time_burst: 0,
+            ..Default::default()
+        })
+        .with_clock(Arc::new(clock.clone()));
+
+        assert!(bucket.try_consume(10.0).is_allowed());
+        assert_eq!(bucket.current_tokens(), 0.0);
+
+        // Advance exactly 300ms: 10 tokens/sec * 0.3s = 3 tokens, no slop.
+        clock.advance(Duration::from_millis(300));
+        assert_eq!(bucket.current_tokens(), 3.0);
+
+        // Advance well past full: refill caps at capacity.
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(bucket.current_tokens(), 10.0);
+    }
+
+    #[test]
+    fn test_fake_clock_reset_uses_injected_clock_for_last_refill() {
+        let clock = FakeClock::new();
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 10,
+            refill_rate: 10.0,
+            one_time_burst: 0,
+            ..Default::default()
+        })
+        .with_clock(Arc::new(clock.clone()));
+
+        bucket.try_consume(10.0);
+        bucket.reset();
+
+        // No time has passed on the fake clock since reset, so there's
+        // nothing to refill yet: exactly full capacity, not a hair over.
+        assert_eq!(bucket.current_tokens(), 10.0);
+
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(bucket.current_tokens(), 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_consume_or_wait_resolves_once_tokens_refill() {
+        let bucket = TokenBucket::new(RateLimitConfig {
+            capacity: 1,
+            refill_rate: 20.0, // 1 token every 50ms
+            one_time_burst: 0,
+            ..Default::default()
+        });
+
+        assert!(bucket.try_consume(1.0).is_allowed());
+
+        let result = tokio::time::timeout(Duration::from_millis(500), bucket.consume_or_wait(1.0))
+            .await
+            .expect("consume_or_wait should resolve once a token refills");
+        assert!(result.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_consume_or_wait_wakes_parked_waiters_early() {
+        let bucket = Arc::new(TokenBucket::new(RateLimitConfig {
+            capacity: 1,
+            refill_rate: 0.001, // effectively won't refill during this test
+            one_time_burst: 0,
+            ..Default::default()
+        }));
+        assert!(bucket.try_consume(1.0).is_allowed());
+
+        let waiter_bucket = Arc::clone(&bucket);
+        let waiter = tokio::spawn(async move { waiter_bucket.consume_or_wait(1.0).await });
+
+        // Give the waiter time to park on `notify.notified()`.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Free up capacity out-of-band (as a peer releasing tokens would)
+        // and nudge the waiter directly instead of waiting for its
+        // multi-hour retry deadline.
+        bucket.reset();
+        bucket.notify.notify_waiters();
+
+        let result = tokio::time::timeout(Duration::from_millis(200), waiter)
+            .await
+            .expect("a woken waiter should recheck promptly, not sleep out its stale deadline")
+            .unwrap();
+        assert!(result.is_allowed());
+    }
 }
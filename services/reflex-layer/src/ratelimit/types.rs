@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Rate limit tier definitions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum RateLimitTier {
     /// Free tier: 100 requests/hour, burst of 10
     #[default]
@@ -25,25 +25,23 @@ impl RateLimitTier {
     /// Get the rate limit configuration for this tier
     pub fn config(&self) -> RateLimitConfig {
         match self {
-            RateLimitTier::Free => RateLimitConfig {
-                capacity: 10,
-                refill_rate: 100.0 / 3600.0, // 100 per hour
-            },
-            RateLimitTier::Basic => RateLimitConfig {
-                capacity: 50,
-                refill_rate: 1000.0 / 3600.0, // 1000 per hour
-            },
-            RateLimitTier::Pro => RateLimitConfig {
-                capacity: 100,
-                refill_rate: 10000.0 / 3600.0, // 10,000 per hour
-            },
+            // Free and basic tiers favor smooth, steady throughput: spread
+            // requests evenly instead of letting an entire hour's quota
+            // drain in one burst
+            RateLimitTier::Free => RateLimitConfig::preconfig_throughput(10, 100.0),
+            RateLimitTier::Basic => RateLimitConfig::preconfig_throughput(50, 1000.0),
+            // Pro clients are trusted to spend most of their quota at once
+            // (e.g. a batch job) and wait out the rest
+            RateLimitTier::Pro => RateLimitConfig::preconfig_burst(100, 10000.0),
             RateLimitTier::Enterprise => RateLimitConfig {
                 capacity: 500,
                 refill_rate: 100000.0 / 3600.0, // 100,000 per hour
+                ..Default::default()
             },
             RateLimitTier::Unlimited => RateLimitConfig {
                 capacity: u64::MAX,
                 refill_rate: f64::MAX,
+                ..Default::default()
             },
         }
     }
@@ -60,6 +58,37 @@ impl RateLimitTier {
     }
 }
 
+impl std::fmt::Display for RateLimitTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            RateLimitTier::Free => "free",
+            RateLimitTier::Basic => "basic",
+            RateLimitTier::Pro => "pro",
+            RateLimitTier::Enterprise => "enterprise",
+            RateLimitTier::Unlimited => "unlimited",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for RateLimitTier {
+    type Err = RateLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "free" => Ok(RateLimitTier::Free),
+            "basic" => Ok(RateLimitTier::Basic),
+            "pro" => Ok(RateLimitTier::Pro),
+            "enterprise" => Ok(RateLimitTier::Enterprise),
+            "unlimited" => Ok(RateLimitTier::Unlimited),
+            other => Err(RateLimitError::Config(format!(
+                "unrecognized rate-limit tier '{}'",
+                other
+            ))),
+        }
+    }
+}
+
 /// Rate limit configuration
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct RateLimitConfig {
@@ -67,6 +96,48 @@ pub struct RateLimitConfig {
     pub capacity: u64,
     /// Token refill rate (tokens per second)
     pub refill_rate: f64,
+    /// One-time, non-replenishing credit on top of `capacity`, mirroring
+    /// Firecracker's rate limiter: absorbs a client's initial spike (e.g.
+    /// a batch of startup calls) without raising the sustained rate.
+    /// Spent once and never refilled (default: 0)
+    #[serde(default)]
+    pub one_time_burst: u64,
+    /// Fraction of `refill_rate` actually targeted, in `(0, 1]` (default: 1.0)
+    ///
+    /// Lets OctoLLM deliberately throttle itself below an upstream
+    /// provider's hard limit (e.g. target 90% of their quota) instead of
+    /// racing it and risking a 429 from the other side.
+    #[serde(default = "default_rate_usage_factor")]
+    pub rate_usage_factor: f64,
+    /// Fraction of `capacity`, in `(0, 1]`, that may be spent in a single
+    /// burst when estimating retry/reset timing (default: 1.0)
+    #[serde(default = "default_burst_pct")]
+    pub burst_pct: f64,
+    /// Flat milliseconds added to computed `retry_after_ms`/`reset_after_ms`
+    /// to absorb clock skew and network latency (default: 0)
+    #[serde(default)]
+    pub duration_overhead_ms: u64,
+}
+
+fn default_rate_usage_factor() -> f64 {
+    1.0
+}
+
+fn default_burst_pct() -> f64 {
+    1.0
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            refill_rate: 0.0,
+            one_time_burst: 0,
+            rate_usage_factor: default_rate_usage_factor(),
+            burst_pct: default_burst_pct(),
+            duration_overhead_ms: 0,
+        }
+    }
 }
 
 impl RateLimitConfig {
@@ -75,6 +146,7 @@ impl RateLimitConfig {
         Self {
             capacity,
             refill_rate: requests_per_hour / 3600.0,
+            ..Default::default()
         }
     }
 
@@ -83,6 +155,16 @@ impl RateLimitConfig {
         Self {
             capacity,
             refill_rate: requests_per_minute / 60.0,
+            ..Default::default()
+        }
+    }
+
+    /// Attach a one-time burst credit that is spent first and never
+    /// replenished, on top of this configuration's steady-state `capacity`
+    pub fn with_burst(self, one_time_burst: u64) -> Self {
+        Self {
+            one_time_burst,
+            ..self
         }
     }
 
@@ -95,6 +177,89 @@ impl RateLimitConfig {
     pub fn requests_per_minute(&self) -> f64 {
         self.refill_rate * 60.0
     }
+
+    /// Create a bandwidth configuration: `bytes_per_second` sustained
+    /// throughput with a `burst_capacity`-byte buffer, for use as the
+    /// `TokenType::Bytes` bucket alongside an ops-frequency config
+    pub fn bandwidth_per_second(burst_capacity: u64, bytes_per_second: f64) -> Self {
+        Self {
+            capacity: burst_capacity,
+            refill_rate: bytes_per_second,
+            ..Default::default()
+        }
+    }
+
+    /// Preset tuned for absorbing bursts: most of the window (`burst_pct`
+    /// ~0.99) may be spent at once, with a generous ~989ms overhead folded
+    /// into retry/reset timing to tolerate clock skew against the upstream
+    /// provider whose limit this is deliberately staying under
+    pub fn preconfig_burst(capacity: u64, requests_per_hour: f64) -> Self {
+        Self {
+            capacity,
+            refill_rate: requests_per_hour / 3600.0,
+            burst_pct: 0.99,
+            duration_overhead_ms: 989,
+            ..Default::default()
+        }
+    }
+
+    /// Preset tuned for smooth, steady throughput: only ~47% of the window
+    /// may be spent in a burst, with a tight ~10ms overhead, trading burst
+    /// headroom for lower latency per request
+    pub fn preconfig_throughput(capacity: u64, requests_per_hour: f64) -> Self {
+        Self {
+            capacity,
+            refill_rate: requests_per_hour / 3600.0,
+            burst_pct: 0.47,
+            duration_overhead_ms: 10,
+            ..Default::default()
+        }
+    }
+
+    /// Effective refill rate after applying `rate_usage_factor`, used when
+    /// estimating retry/reset timing so OctoLLM's own throttling stays
+    /// under the upstream target rather than racing it
+    pub fn effective_refill_rate(&self) -> f64 {
+        self.refill_rate * self.rate_usage_factor
+    }
+
+    /// Effective burst capacity after applying `burst_pct`, used when
+    /// estimating retry/reset timing
+    pub fn effective_capacity(&self) -> f64 {
+        self.capacity as f64 * self.burst_pct
+    }
+
+    /// Check that this configuration could actually rate-limit anything:
+    /// a zero `capacity` would reject every request outright, and a
+    /// zero/negative/non-finite `refill_rate` would mean tokens never
+    /// replenish (or replenish backwards), permanently locking every key
+    /// out once its initial capacity is spent
+    ///
+    /// Intended as a guard before accepting a config from an operator-facing
+    /// reload path (e.g. [`TierConfigTable::update`]), not as a check on
+    /// every request.
+    pub fn is_valid(&self) -> bool {
+        self.capacity > 0
+            && self.refill_rate.is_finite()
+            && self.refill_rate > 0.0
+            && self.rate_usage_factor.is_finite()
+            && self.rate_usage_factor > 0.0
+            && self.burst_pct.is_finite()
+            && self.burst_pct > 0.0
+    }
+}
+
+/// Which dimension of usage a token bucket limits
+///
+/// A single request can be cheap in one dimension and expensive in
+/// another (e.g. a tiny request with a huge file attached), so request
+/// frequency and payload bandwidth are tracked as independent buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenType {
+    /// Request frequency (one token per request, regardless of size)
+    Ops,
+    /// Payload bandwidth (one token per byte uploaded/processed)
+    Bytes,
 }
 
 /// Result of a rate limit check
@@ -116,6 +281,16 @@ pub enum RateLimitResult {
         /// Current token count (may be fractional)
         current_tokens: f64,
     },
+    /// Request denied because too many requests for this key are already
+    /// executing concurrently, distinct from a token-bucket rate being
+    /// exhausted -- the caller is within its request *rate* but over its
+    /// in-flight *concurrency* budget
+    ConcurrencyLimited {
+        /// Number of requests currently in flight for this key
+        in_flight: u64,
+        /// The configured maximum concurrent in-flight requests
+        max: u64,
+    },
 }
 
 impl RateLimitResult {
@@ -124,9 +299,13 @@ impl RateLimitResult {
         matches!(self, RateLimitResult::Allowed { .. })
     }
 
-    /// Check if the request is limited
+    /// Check if the request is limited (for any reason, including
+    /// concurrency saturation)
     pub fn is_limited(&self) -> bool {
-        matches!(self, RateLimitResult::Limited { .. })
+        matches!(
+            self,
+            RateLimitResult::Limited { .. } | RateLimitResult::ConcurrencyLimited { .. }
+        )
     }
 }
 
@@ -141,8 +320,13 @@ pub enum RateLimitReason {
     EndpointQuota,
     /// Global system quota exceeded
     GlobalQuota,
+    /// The joint (endpoint, IP) quota exceeded -- one client monopolizing a
+    /// single expensive endpoint even while under its global IP limit
+    EndpointIpQuota,
     /// Custom quota exceeded
     Custom,
+    /// A specific token-type bucket (ops or bytes) was exhausted
+    TokenQuota(TokenType),
 }
 
 impl RateLimitReason {
@@ -153,7 +337,10 @@ impl RateLimitReason {
             RateLimitReason::IPQuota => "IP address request quota exceeded",
             RateLimitReason::EndpointQuota => "Endpoint request quota exceeded",
             RateLimitReason::GlobalQuota => "System-wide quota exceeded",
+            RateLimitReason::EndpointIpQuota => "Per-IP endpoint quota exceeded",
             RateLimitReason::Custom => "Custom quota exceeded",
+            RateLimitReason::TokenQuota(TokenType::Ops) => "Request-frequency quota exceeded",
+            RateLimitReason::TokenQuota(TokenType::Bytes) => "Bandwidth quota exceeded",
         }
     }
 }
@@ -188,6 +375,15 @@ pub enum RateLimitError {
     /// Reflex error (for compatibility)
     #[error("Reflex error: {0}")]
     Reflex(String),
+
+    /// Too many requests for this key are already executing concurrently
+    #[error("Concurrency limit exceeded: {in_flight}/{max} in flight")]
+    ConcurrencyLimited {
+        /// Number of requests currently in flight for this key
+        in_flight: u64,
+        /// The configured maximum concurrent in-flight requests
+        max: u64,
+    },
 }
 
 // Convert ReflexError to RateLimitError for compatibility
@@ -209,25 +405,99 @@ pub enum RateLimitKey {
     IP(String),
     /// Rate limit by endpoint
     Endpoint(String),
+    /// Rate limit by the joint (endpoint, IP) pair, so one client can't
+    /// monopolize a single expensive endpoint while staying under its
+    /// overall per-IP limit
+    EndpointIp(String, String),
     /// Global rate limit
     Global,
     /// Custom key
     Custom(String),
 }
 
+/// Default IPv6 grouping prefix used by [`RateLimitKey::to_redis_key`]
+///
+/// A /64 is the smallest allocation most ISPs hand out, so grouping at
+/// that boundary catches same-customer address rotation without lumping
+/// distinct customers together.
+pub const DEFAULT_IPV6_GROUP_PREFIX: u8 = 64;
+
+/// A tighter grouping prefix for providers that allocate at /48, where a
+/// single customer can still rotate through many /64s
+pub const WIDE_IPV6_GROUP_PREFIX: u8 = 48;
+
 impl RateLimitKey {
-    /// Convert to Redis key string
+    /// The Prometheus `dimension` label this key belongs to, matching the
+    /// existing `ip`/`user`/`endpoint`/`global` label values
+    pub fn dimension(&self) -> &'static str {
+        match self {
+            RateLimitKey::User(_) => "user",
+            RateLimitKey::IP(_) => "ip",
+            RateLimitKey::Endpoint(_) => "endpoint",
+            RateLimitKey::EndpointIp(_, _) => "endpoint_ip",
+            RateLimitKey::Global => "global",
+            RateLimitKey::Custom(_) => "custom",
+        }
+    }
+
+    /// Convert to Redis key string, grouping IPv6 addresses by
+    /// [`DEFAULT_IPV6_GROUP_PREFIX`]
     pub fn to_redis_key(&self) -> String {
+        self.to_redis_key_with_ipv6_prefix(DEFAULT_IPV6_GROUP_PREFIX)
+    }
+
+    /// Convert to Redis key string, grouping IPv6 addresses by the given
+    /// prefix length (IPv4 addresses are always kept whole)
+    pub fn to_redis_key_with_ipv6_prefix(&self, ipv6_prefix_len: u8) -> String {
         match self {
             RateLimitKey::User(id) => format!("ratelimit:user:{}", id),
-            RateLimitKey::IP(ip) => format!("ratelimit:ip:{}", ip),
+            RateLimitKey::IP(ip) => {
+                format!("ratelimit:ip:{}", group_ip(ip, ipv6_prefix_len))
+            }
             RateLimitKey::Endpoint(endpoint) => format!("ratelimit:endpoint:{}", endpoint),
+            RateLimitKey::EndpointIp(endpoint, ip) => format!(
+                "ratelimit:endpoint_ip:{}:{}",
+                endpoint,
+                group_ip(ip, ipv6_prefix_len)
+            ),
             RateLimitKey::Global => "ratelimit:global".to_string(),
             RateLimitKey::Custom(key) => format!("ratelimit:custom:{}", key),
         }
     }
 }
 
+/// Normalize an IP address for grouped rate limiting
+///
+/// IPv4 addresses are kept as-is. IPv6 addresses are masked down to their
+/// `/prefix_len` prefix, so a client that rotates through its allocation
+/// (trivial with a /64 or /48) still lands on one shared bucket instead of
+/// defeating per-IP limits by spreading across 2^64+ distinct addresses.
+/// Strings that aren't parseable IP addresses (e.g. test fixtures) are
+/// passed through unchanged.
+fn group_ip(ip: &str, prefix_len: u8) -> String {
+    use std::net::{IpAddr, Ipv6Addr};
+
+    match ip.parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => v4.to_string(),
+        Ok(IpAddr::V6(v6)) => {
+            let prefix_len = prefix_len.min(128);
+            let full_segments = (prefix_len / 16) as usize;
+            let remaining_bits = prefix_len % 16;
+
+            let segments = v6.segments();
+            let mut masked = [0u16; 8];
+            masked[..full_segments].copy_from_slice(&segments[..full_segments]);
+            if remaining_bits > 0 && full_segments < 8 {
+                let mask = !0u16 << (16 - remaining_bits);
+                masked[full_segments] = segments[full_segments] & mask;
+            }
+
+            format!("{}/{}", Ipv6Addr::from(masked), prefix_len)
+        }
+        Err(_) => ip.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +518,24 @@ mod tests {
         assert_eq!(RateLimitTier::default(), RateLimitTier::Free);
     }
 
+    #[test]
+    fn test_free_and_basic_tiers_use_throughput_profile() {
+        let free = RateLimitTier::Free.config();
+        assert_eq!(free.burst_pct, 0.47);
+        assert_eq!(free.duration_overhead_ms, 10);
+
+        let basic = RateLimitTier::Basic.config();
+        assert_eq!(basic.burst_pct, 0.47);
+        assert_eq!(basic.duration_overhead_ms, 10);
+    }
+
+    #[test]
+    fn test_pro_tier_uses_burst_profile() {
+        let pro = RateLimitTier::Pro.config();
+        assert_eq!(pro.burst_pct, 0.99);
+        assert_eq!(pro.duration_overhead_ms, 989);
+    }
+
     #[test]
     fn test_rate_limit_tier_description() {
         assert_eq!(
@@ -333,4 +621,174 @@ mod tests {
         let key = RateLimitKey::Custom("special:limit".to_string());
         assert_eq!(key.to_redis_key(), "ratelimit:custom:special:limit");
     }
+
+    #[test]
+    fn test_rate_limit_key_endpoint_ip() {
+        let key = RateLimitKey::EndpointIp(
+            "/api/v1/completions".to_string(),
+            "192.168.1.1".to_string(),
+        );
+        assert_eq!(key.dimension(), "endpoint_ip");
+        assert_eq!(
+            key.to_redis_key(),
+            "ratelimit:endpoint_ip:/api/v1/completions:192.168.1.1"
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_key_endpoint_ip_groups_ipv6_like_plain_ip() {
+        let key = RateLimitKey::EndpointIp(
+            "/api/v1/completions".to_string(),
+            "2001:db8:1234:5678::1".to_string(),
+        );
+        assert!(key
+            .to_redis_key()
+            .starts_with("ratelimit:endpoint_ip:/api/v1/completions:2001:db8:1234:5678::/64"));
+    }
+
+    #[test]
+    fn test_rate_limit_config_bandwidth_per_second() {
+        let config = RateLimitConfig::bandwidth_per_second(1_000_000, 100_000.0);
+        assert_eq!(config.capacity, 1_000_000);
+        assert_eq!(config.refill_rate, 100_000.0);
+    }
+
+    #[test]
+    fn test_ipv4_key_is_kept_whole() {
+        let key = RateLimitKey::IP("203.0.113.7".to_string());
+        assert_eq!(key.to_redis_key(), "ratelimit:ip:203.0.113.7");
+    }
+
+    #[test]
+    fn test_ipv6_key_is_grouped_by_default_prefix() {
+        let a = RateLimitKey::IP("2001:db8:1234:5678::1".to_string());
+        let b = RateLimitKey::IP("2001:db8:1234:5678::2".to_string());
+
+        // Same /64: both addresses land on the same grouped bucket.
+        assert_eq!(a.to_redis_key(), b.to_redis_key());
+        assert!(a.to_redis_key().starts_with("ratelimit:ip:2001:db8:1234:5678::/64"));
+    }
+
+    #[test]
+    fn test_ipv6_key_grouped_at_different_prefixes_are_distinct() {
+        let a = RateLimitKey::IP("2001:db8:1234:5678::1".to_string());
+        let b = RateLimitKey::IP("2001:db8:1235:5678::1".to_string());
+
+        assert_ne!(
+            a.to_redis_key_with_ipv6_prefix(WIDE_IPV6_GROUP_PREFIX),
+            b.to_redis_key_with_ipv6_prefix(WIDE_IPV6_GROUP_PREFIX)
+        );
+    }
+
+    #[test]
+    fn test_ipv6_key_grouped_by_wide_prefix_merges_more_addresses() {
+        let a = RateLimitKey::IP("2001:db8:1234:5678::1".to_string());
+        let b = RateLimitKey::IP("2001:db8:1234:9999::1".to_string());
+
+        // Different /64s but the same /48: merge under the wider prefix.
+        assert_eq!(
+            a.to_redis_key_with_ipv6_prefix(WIDE_IPV6_GROUP_PREFIX),
+            b.to_redis_key_with_ipv6_prefix(WIDE_IPV6_GROUP_PREFIX)
+        );
+        // ...but not under the default /64.
+        assert_ne!(a.to_redis_key(), b.to_redis_key());
+    }
+
+    #[test]
+    fn test_unparseable_ip_string_passes_through() {
+        let key = RateLimitKey::IP("not-an-ip".to_string());
+        assert_eq!(key.to_redis_key(), "ratelimit:ip:not-an-ip");
+    }
+
+    #[test]
+    fn test_rate_usage_factor_and_burst_pct_default_to_one() {
+        let config = RateLimitConfig::custom(100, 3600.0);
+        assert_eq!(config.rate_usage_factor, 1.0);
+        assert_eq!(config.burst_pct, 1.0);
+        assert_eq!(config.duration_overhead_ms, 0);
+        assert_eq!(config.effective_refill_rate(), config.refill_rate);
+        assert_eq!(config.effective_capacity(), config.capacity as f64);
+    }
+
+    #[test]
+    fn test_preconfig_burst_favors_high_burst_pct() {
+        let config = RateLimitConfig::preconfig_burst(100, 3600.0);
+        assert_eq!(config.capacity, 100);
+        assert_eq!(config.burst_pct, 0.99);
+        assert_eq!(config.duration_overhead_ms, 989);
+    }
+
+    #[test]
+    fn test_preconfig_throughput_favors_low_overhead() {
+        let config = RateLimitConfig::preconfig_throughput(100, 3600.0);
+        assert_eq!(config.capacity, 100);
+        assert_eq!(config.burst_pct, 0.47);
+        assert_eq!(config.duration_overhead_ms, 10);
+    }
+
+    #[test]
+    fn test_effective_refill_rate_scales_by_usage_factor() {
+        let config = RateLimitConfig {
+            rate_usage_factor: 0.9,
+            ..RateLimitConfig::custom(100, 3600.0)
+        };
+        assert!((config.effective_refill_rate() - config.refill_rate * 0.9).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_rate_limit_key_dimension_labels() {
+        assert_eq!(RateLimitKey::User("u".to_string()).dimension(), "user");
+        assert_eq!(RateLimitKey::IP("1.2.3.4".to_string()).dimension(), "ip");
+        assert_eq!(
+            RateLimitKey::Endpoint("/x".to_string()).dimension(),
+            "endpoint"
+        );
+        assert_eq!(RateLimitKey::Global.dimension(), "global");
+        assert_eq!(RateLimitKey::Custom("c".to_string()).dimension(), "custom");
+    }
+
+    #[test]
+    fn test_rate_limit_tier_display() {
+        assert_eq!(RateLimitTier::Free.to_string(), "free");
+        assert_eq!(RateLimitTier::Enterprise.to_string(), "enterprise");
+    }
+
+    #[test]
+    fn test_rate_limit_tier_from_str_round_trips_with_display() {
+        use std::str::FromStr;
+
+        for tier in [
+            RateLimitTier::Free,
+            RateLimitTier::Basic,
+            RateLimitTier::Pro,
+            RateLimitTier::Enterprise,
+            RateLimitTier::Unlimited,
+        ] {
+            assert_eq!(RateLimitTier::from_str(&tier.to_string()).unwrap(), tier);
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_tier_from_str_is_case_insensitive() {
+        use std::str::FromStr;
+        assert_eq!(RateLimitTier::from_str("PRO").unwrap(), RateLimitTier::Pro);
+    }
+
+    #[test]
+    fn test_rate_limit_tier_from_str_rejects_unknown_name() {
+        use std::str::FromStr;
+        assert!(RateLimitTier::from_str("gold").is_err());
+    }
+
+    #[test]
+    fn test_token_quota_reason_description_is_per_token_type() {
+        assert_eq!(
+            RateLimitReason::TokenQuota(TokenType::Ops).description(),
+            "Request-frequency quota exceeded"
+        );
+        assert_eq!(
+            RateLimitReason::TokenQuota(TokenType::Bytes).description(),
+            "Bandwidth quota exceeded"
+        );
+    }
 }
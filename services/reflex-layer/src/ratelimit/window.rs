@@ -0,0 +1,173 @@
+//! Human-readable rate-limit window parsing
+//!
+//! Lets operators write a tier's limit the way they'd say it out loud --
+//! `"100/hour"`, `"1000/1h"`, `"twice-daily"` -- instead of precomputing a
+//! `refill_rate` by hand. [`parse_rate_window`] turns either form into the
+//! same [`RateLimitConfig`] the rest of the rate limiter already consumes.
+
+use std::time::Duration;
+
+use crate::ratelimit::types::{RateLimitConfig, RateLimitError};
+
+/// Parse a human-readable rate-limit window into a [`RateLimitConfig`]
+///
+/// Accepts two forms:
+/// - `"<count>/<window>"` (e.g. `"100/hour"`, `"1000/1h"`): `count` requests
+///   per `window`.
+/// - `"<frequency>-<window>"` (e.g. `"twice-daily"`, `"once-hourly"`):
+///   `frequency` is `once`, `twice`, `thrice`, or a bare integer.
+///
+/// In both forms, `window` is an optional integer multiplier followed by a
+/// unit name (`s`/`sec`/`second(s)`, `m`/`min`/`minute(s)`, `h`/`hr`/`hour(s)`,
+/// `d`/`day(s)`, `w`/`week(s)`), or one of the bare adjectives `hourly`,
+/// `daily`, `weekly`.
+///
+/// Burst capacity defaults to 10% of `count` (minimum 1); a caller that
+/// needs a different burst should build a `RateLimitConfig` directly
+/// instead of going through this helper.
+///
+/// # Examples
+///
+/// ```
+/// use reflex_layer::ratelimit::parse_rate_window;
+///
+/// let hundred_per_hour = parse_rate_window("100/hour").unwrap();
+/// assert_eq!(hundred_per_hour.capacity, 10);
+/// assert!((hundred_per_hour.requests_per_hour() - 100.0).abs() < 0.001);
+///
+/// let twice_daily = parse_rate_window("twice-daily").unwrap();
+/// assert!((twice_daily.requests_per_hour() - 2.0 / 24.0).abs() < 0.001);
+/// ```
+pub fn parse_rate_window(spec: &str) -> Result<RateLimitConfig, RateLimitError> {
+    let spec = spec.trim();
+
+    if let Some((count_part, window_part)) = spec.split_once('/') {
+        let count: u64 = count_part.trim().parse().map_err(|_| {
+            RateLimitError::Config(format!("invalid request count in rate window '{}'", spec))
+        })?;
+        let window = to_duration(window_part.trim())?;
+        return Ok(config_for_window(count, window));
+    }
+
+    if let Some((frequency_part, window_part)) = spec.split_once('-') {
+        let count = match frequency_part.trim().to_lowercase().as_str() {
+            "once" => 1,
+            "twice" => 2,
+            "thrice" => 3,
+            other => other.parse().map_err(|_| {
+                RateLimitError::Config(format!(
+                    "invalid frequency '{}' in rate window '{}'",
+                    other, spec
+                ))
+            })?,
+        };
+        let window = to_duration(window_part.trim())?;
+        return Ok(config_for_window(count, window));
+    }
+
+    Err(RateLimitError::Config(format!(
+        "unrecognized rate window '{}' (expected '<count>/<window>' or '<frequency>-<window>')",
+        spec
+    )))
+}
+
+/// Build the `RateLimitConfig` for `count` requests per `window`, applying
+/// the 10%-of-count burst default described on [`parse_rate_window`]
+fn config_for_window(count: u64, window: Duration) -> RateLimitConfig {
+    let requests_per_hour = count as f64 * 3600.0 / window.as_secs_f64().max(1.0);
+    let capacity = (count / 10).max(1);
+    RateLimitConfig::custom(capacity, requests_per_hour)
+}
+
+/// Parse a window string (e.g. `"hour"`, `"1h"`, `"daily"`, `"2d"`) into a
+/// `Duration`, as the half of a rate window following the `/` or `-`
+fn to_duration(window: &str) -> Result<Duration, RateLimitError> {
+    let window = window.to_lowercase();
+    let split_at = window
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(window.len());
+    let (count_str, unit_str) = window.split_at(split_at);
+
+    let count: u64 = if count_str.is_empty() {
+        1
+    } else {
+        count_str.parse().map_err(|_| {
+            RateLimitError::Config(format!("invalid window multiplier in '{}'", window))
+        })?
+    };
+
+    let unit_secs: u64 = match unit_str {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hrs" | "hour" | "hours" | "hourly" => 3600,
+        "d" | "day" | "days" | "daily" => 86_400,
+        "w" | "week" | "weeks" | "weekly" => 604_800,
+        other => {
+            return Err(RateLimitError::Config(format!(
+                "unrecognized window unit '{}'",
+                other
+            )))
+        }
+    };
+
+    Ok(Duration::from_secs(count * unit_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_count_per_unit_window() {
+        let config = parse_rate_window("100/hour").unwrap();
+        assert_eq!(config.capacity, 10);
+        assert!((config.requests_per_hour() - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_count_per_multiplied_window() {
+        let config = parse_rate_window("1000/1h").unwrap();
+        assert_eq!(config.capacity, 100);
+        assert!((config.requests_per_hour() - 1000.0).abs() < 0.001);
+
+        let config = parse_rate_window("1000/2h").unwrap();
+        assert!((config.requests_per_hour() - 500.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_named_frequency_window() {
+        let config = parse_rate_window("twice-daily").unwrap();
+        assert_eq!(config.capacity, 1); // (2 / 10).max(1)
+        assert!((config.requests_per_hour() - 2.0 / 24.0).abs() < 0.0001);
+
+        let config = parse_rate_window("once-hourly").unwrap();
+        assert!((config.requests_per_hour() - 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_parse_numeric_frequency_window() {
+        let config = parse_rate_window("5-daily").unwrap();
+        assert!((config.requests_per_hour() - 5.0 / 24.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_burst_capacity_is_at_least_one() {
+        let config = parse_rate_window("5/hour").unwrap();
+        assert_eq!(config.capacity, 1);
+    }
+
+    #[test]
+    fn test_rejects_unrecognized_unit() {
+        assert!(parse_rate_window("100/fortnight").is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_spec() {
+        assert!(parse_rate_window("not a window at all").is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_numeric_count() {
+        assert!(parse_rate_window("many/hour").is_err());
+    }
+}
@@ -17,9 +17,11 @@
 /// - `JAEGER_ENDPOINT`: Jaeger collector endpoint (default: http://jaeger-collector.octollm-monitoring.svc.cluster.local:4317)
 /// - `OTEL_SAMPLING_RATE`: Sampling rate 0.0-1.0 (default: 0.10 for prod, 1.0 for dev)
 /// - `ENVIRONMENT`: dev/staging/prod (default: dev)
+/// - `OTEL_METRICS_EXPORT_INTERVAL`: Metrics push interval in milliseconds (default: 15000)
 
 use opentelemetry::{
     global,
+    metrics::{MeterProvider as _, Unit},
     sdk::{
         export::trace::stdout,
         propagation::TraceContextPropagator,
@@ -30,8 +32,14 @@ use opentelemetry::{
 };
 use opentelemetry_otlp::WithExportConfig;
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, Registry};
 
+use reflex_layer::cache::CacheStats;
+use reflex_layer::injection::{InjectionType, Severity};
+use reflex_layer::pii::PIIType;
+
 /// Initialize OpenTelemetry tracing with Jaeger exporter
 ///
 /// # Arguments
@@ -80,17 +88,21 @@ pub async fn init_telemetry(service_name: &str, environment: &str) {
         .with_exporter(
             opentelemetry_otlp::new_exporter()
                 .tonic()
-                .with_endpoint(jaeger_endpoint),
+                .with_endpoint(&jaeger_endpoint),
         )
         .with_trace_config(
             trace::config()
                 .with_sampler(sampler)
                 .with_id_generator(RandomIdGenerator::default())
-                .with_resource(resource),
+                .with_resource(resource.clone()),
         )
         .install_batch(opentelemetry::runtime::Tokio)
         .expect("Failed to initialize tracer");
 
+    // Configure an OTLP metrics pipeline alongside the tracer, pushing on
+    // the same collector endpoint every `OTEL_METRICS_EXPORT_INTERVAL` ms
+    init_metrics(&jaeger_endpoint, resource);
+
     // Set global tracer provider
     global::set_text_map_propagator(TraceContextPropagator::new());
 
@@ -110,6 +122,107 @@ pub async fn init_telemetry(service_name: &str, environment: &str) {
     );
 }
 
+/// Build the OTLP metrics pipeline and register the global [`MeterProvider`],
+/// then create the counters `record_injection_detection`/`record_pii_redaction`
+/// report into. Runs on the same collector endpoint as the tracer, pushing
+/// every `OTEL_METRICS_EXPORT_INTERVAL` milliseconds (default: 15000).
+fn init_metrics(endpoint: &str, resource: Resource) {
+    let export_interval_ms: u64 = env::var("OTEL_METRICS_EXPORT_INTERVAL")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(15_000);
+
+    let provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry::runtime::Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_period(Duration::from_millis(export_interval_ms))
+        .with_resource(resource)
+        .build()
+        .expect("Failed to initialize meter provider");
+
+    global::set_meter_provider(provider);
+}
+
+/// Attach a live [`CacheStats`] (e.g. `RedisCache::stats_ref()`) to the
+/// global meter so its snapshot is scraped into `cache_hits_total`,
+/// `cache_misses_total`, `cache_errors_total`, and `cache_hit_ratio` on
+/// every OTLP export tick
+pub fn register_cache_metrics(stats: Arc<CacheStats>) {
+    let meter = global::meter("reflex-layer/cache");
+
+    let hits = stats.clone();
+    meter
+        .u64_observable_counter("cache_hits_total")
+        .with_description("Total number of cache hits")
+        .with_callback(move |observer| {
+            observer.observe(hits.snapshot().hits, &[]);
+        })
+        .init();
+
+    let misses = stats.clone();
+    meter
+        .u64_observable_counter("cache_misses_total")
+        .with_description("Total number of cache misses")
+        .with_callback(move |observer| {
+            observer.observe(misses.snapshot().misses, &[]);
+        })
+        .init();
+
+    let errors = stats.clone();
+    meter
+        .u64_observable_counter("cache_errors_total")
+        .with_description("Total number of cache operation errors")
+        .with_callback(move |observer| {
+            observer.observe(errors.snapshot().errors, &[]);
+        })
+        .init();
+
+    let hit_ratio = stats;
+    meter
+        .f64_observable_gauge("cache_hit_ratio")
+        .with_description("Cache hit rate as a fraction between 0.0 and 1.0")
+        .with_unit(Unit::new("ratio"))
+        .with_callback(move |observer| {
+            observer.observe(hit_ratio.snapshot().hit_rate, &[]);
+        })
+        .init();
+}
+
+/// Record one [`InjectionDetector`](reflex_layer::injection::InjectionDetector)
+/// match against the `injection_detections_total` counter, labeled by
+/// `injection_type`/`severity`
+pub fn record_injection_detection(injection_type: InjectionType, severity: Severity) {
+    let meter = global::meter("reflex-layer/injection");
+    let counter = meter
+        .u64_counter("injection_detections_total")
+        .with_description("Total number of prompt injection detections")
+        .init();
+
+    counter.add(
+        1,
+        &[
+            KeyValue::new("injection_type", format!("{:?}", injection_type)),
+            KeyValue::new("severity", format!("{:?}", severity)),
+        ],
+    );
+}
+
+/// Record one [`PIIDetector`](reflex_layer::pii::PIIDetector) redaction
+/// against the `pii_redactions_total` counter, labeled by `pii_type`
+pub fn record_pii_redaction(pii_type: PIIType) {
+    let meter = global::meter("reflex-layer/pii");
+    let counter = meter
+        .u64_counter("pii_redactions_total")
+        .with_description("Total number of PII redactions")
+        .init();
+
+    counter.add(1, &[KeyValue::new("pii_type", format!("{:?}", pii_type))]);
+}
+
 /// Create a custom span for tracing operations
 ///
 /// # Example
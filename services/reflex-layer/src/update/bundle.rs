@@ -0,0 +1,108 @@
+//! Signed bundle and metadata types for pattern-set updates
+
+use crate::update::UpdateError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Reference to a single pattern file within a bundle's metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternFileRef {
+    /// Path/name of the pattern file within the bundle
+    pub path: String,
+    /// Expected sha256 hash (lowercase hex) of the file's contents
+    pub sha256: String,
+}
+
+/// Signed metadata document describing a pattern-set update
+///
+/// This is the document that trusted keys sign over. It never contains the
+/// pattern file contents themselves, only their paths and hashes, so
+/// verification doesn't require trusting the bundle transport.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternSetMetadata {
+    /// Monotonically increasing version; used for anti-rollback protection
+    pub version: u64,
+    /// RFC 3339 timestamp after which this bundle must be rejected
+    pub expires: String,
+    /// Pattern files covered by this bundle, with their expected hashes
+    pub files: Vec<PatternFileRef>,
+}
+
+impl PatternSetMetadata {
+    /// Whether `expires` is in the past relative to now
+    pub fn is_expired(&self) -> bool {
+        match DateTime::parse_from_rfc3339(&self.expires) {
+            Ok(expires) => expires.with_timezone(&Utc) <= Utc::now(),
+            // An unparseable timestamp can't be proven fresh, so treat it as expired.
+            Err(_) => true,
+        }
+    }
+}
+
+/// A pattern file whose contents have passed hash verification
+#[derive(Debug, Clone)]
+pub struct VerifiedPatternFile {
+    /// Path/name of the pattern file
+    pub path: String,
+    /// Verified file contents (one regex source string)
+    pub content: String,
+}
+
+/// A signed pattern-set bundle as received from an update server
+///
+/// Carries the raw metadata bytes (exactly as signed), a set of detached
+/// signatures keyed by key ID, and the pattern file contents referenced by
+/// the metadata.
+#[derive(Debug, Clone)]
+pub struct SignedBundle {
+    metadata_bytes: Vec<u8>,
+    /// Detached signatures over `metadata_bytes`, keyed by key ID
+    signatures: Vec<(String, Vec<u8>)>,
+    files: HashMap<String, String>,
+}
+
+impl SignedBundle {
+    /// Construct a bundle from its raw metadata bytes, detached signatures,
+    /// and pattern file contents
+    pub fn new(
+        metadata_bytes: Vec<u8>,
+        signatures: Vec<(String, Vec<u8>)>,
+        files: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            metadata_bytes,
+            signatures,
+            files,
+        }
+    }
+
+    /// Raw metadata bytes exactly as signed
+    pub fn metadata_bytes(&self) -> &[u8] {
+        &self.metadata_bytes
+    }
+
+    /// Detached signatures over the metadata, keyed by key ID
+    pub fn signatures(&self) -> &[(String, Vec<u8>)] {
+        &self.signatures
+    }
+
+    /// Contents of a pattern file by path, if present in the bundle
+    pub fn file(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Parse the metadata document
+    pub fn metadata(&self) -> Result<PatternSetMetadata, UpdateError> {
+        serde_json::from_slice(&self.metadata_bytes)
+            .map_err(|e| UpdateError::InvalidMetadata(e.to_string()))
+    }
+
+    /// Replace a file's contents without updating its signed hash
+    ///
+    /// Only used in tests to simulate a bundle tampered with in transit.
+    #[cfg(test)]
+    pub(crate) fn tamper_file(&mut self, path: &str, new_content: &str) {
+        self.files.insert(path.to_string(), new_content.to_string());
+    }
+}
@@ -0,0 +1,240 @@
+//! Signed, versioned pattern-set updates
+//!
+//! Lets `InjectionDetector` and `PIIDetector` pick up new detection signatures
+//! from a remote bundle without a recompile, modeled on The Update Framework
+//! (TUF): a bundle carries a metadata document (pattern files + sha256
+//! hashes, a monotonically increasing `version`, and an `expires` timestamp)
+//! and a set of detached signatures over that document. A bundle is only
+//! accepted once at least `threshold` signatures validate against the
+//! trusted root keys, its `version` is strictly greater than the last
+//! accepted version (anti-rollback), and `expires` is still in the future
+//! (freshness). Each pattern file's sha256 is re-checked before it is
+//! compiled, so a tampered or malformed bundle never partially applies.
+
+pub mod bundle;
+pub mod trust;
+
+pub use bundle::{PatternFileRef, PatternSetMetadata, SignedBundle, VerifiedPatternFile};
+pub use trust::{TrustRoot, TrustedKey};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Errors that can occur while verifying or applying a signed pattern-set update
+#[derive(Error, Debug)]
+pub enum UpdateError {
+    /// Metadata document is not valid JSON or is missing required fields
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(String),
+
+    /// Fewer than the required threshold of signatures validated
+    #[error("Insufficient valid signatures: got {valid}, need {threshold}")]
+    InsufficientSignatures {
+        /// Number of signatures that validated against a trusted key
+        valid: usize,
+        /// Minimum number of valid signatures required
+        threshold: usize,
+    },
+
+    /// Bundle version is not strictly greater than the last accepted version
+    #[error("Rollback detected: bundle version {bundle} is not newer than accepted version {accepted}")]
+    RollbackDetected {
+        /// Version carried by the rejected bundle
+        bundle: u64,
+        /// Last version this client accepted
+        accepted: u64,
+    },
+
+    /// Bundle's `expires` timestamp is in the past
+    #[error("Bundle expired at {0}")]
+    Expired(String),
+
+    /// A pattern file's content does not match its declared sha256 hash
+    #[error("Hash mismatch for pattern file '{0}'")]
+    HashMismatch(String),
+
+    /// A pattern file listed in the metadata was not included in the bundle
+    #[error("Pattern file '{0}' missing from bundle")]
+    MissingFile(String),
+
+    /// A pattern file's contents failed to compile as a regex
+    #[error("Invalid regex in pattern file '{file}': {source}")]
+    InvalidPattern {
+        /// Name of the offending pattern file
+        file: String,
+        /// Underlying regex compilation error
+        source: String,
+    },
+}
+
+/// Compute the lowercase hex-encoded sha256 digest of `data`
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Verify a signed bundle against a trust root and return its verified,
+/// hash-checked contents.
+///
+/// Checks are applied in order: signature threshold, anti-rollback,
+/// freshness, then per-file hash verification. `last_accepted_version`
+/// should be the highest `version` this client has previously applied
+/// (`0` if none).
+pub fn verify_bundle(
+    bundle: &SignedBundle,
+    trust: &TrustRoot,
+    last_accepted_version: u64,
+) -> Result<Vec<VerifiedPatternFile>, UpdateError> {
+    let valid_signatures = trust.count_valid_signatures(bundle);
+    if valid_signatures < trust.threshold() {
+        return Err(UpdateError::InsufficientSignatures {
+            valid: valid_signatures,
+            threshold: trust.threshold(),
+        });
+    }
+
+    let metadata = bundle.metadata()?;
+
+    if metadata.version <= last_accepted_version {
+        return Err(UpdateError::RollbackDetected {
+            bundle: metadata.version,
+            accepted: last_accepted_version,
+        });
+    }
+
+    if metadata.is_expired() {
+        return Err(UpdateError::Expired(metadata.expires.clone()));
+    }
+
+    let mut verified = Vec::with_capacity(metadata.files.len());
+    for file_ref in &metadata.files {
+        let content = bundle
+            .file(&file_ref.path)
+            .ok_or_else(|| UpdateError::MissingFile(file_ref.path.clone()))?;
+
+        let actual_hash = sha256_hex(content.as_bytes());
+        if actual_hash != file_ref.sha256 {
+            return Err(UpdateError::HashMismatch(file_ref.path.clone()));
+        }
+
+        verified.push(VerifiedPatternFile {
+            path: file_ref.path.clone(),
+            content: content.to_string(),
+        });
+    }
+
+    Ok(verified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update::bundle::{PatternFileRef, PatternSetMetadata, SignedBundle};
+    use crate::update::trust::{TrustRoot, TrustedKey};
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::collections::HashMap;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn make_bundle(
+        version: u64,
+        expires: &str,
+        signers: &[&SigningKey],
+    ) -> (SignedBundle, HashMap<String, String>) {
+        let mut files = HashMap::new();
+        files.insert("ignore_instructions.txt".to_string(), r"(?i)ignore\s+all".to_string());
+
+        let metadata = PatternSetMetadata {
+            version,
+            expires: expires.to_string(),
+            files: vec![PatternFileRef {
+                path: "ignore_instructions.txt".to_string(),
+                sha256: sha256_hex(files["ignore_instructions.txt"].as_bytes()),
+            }],
+        };
+        let metadata_bytes = serde_json::to_vec(&metadata).unwrap();
+
+        let signatures = signers
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let sig = key.sign(&metadata_bytes);
+                (format!("key-{}", i), sig.to_bytes().to_vec())
+            })
+            .collect();
+
+        (
+            SignedBundle::new(metadata_bytes, signatures, files.clone()),
+            files,
+        )
+    }
+
+    fn trust_root(keys: &[(&str, &SigningKey)], threshold: usize) -> TrustRoot {
+        TrustRoot::new(
+            keys.iter()
+                .map(|(id, key)| TrustedKey::new(id.to_string(), key.verifying_key()))
+                .collect(),
+            threshold,
+        )
+    }
+
+    #[test]
+    fn test_verify_bundle_accepts_valid_update() {
+        let k1 = signing_key(1);
+        let k2 = signing_key(2);
+        let trust = trust_root(&[("key-0", &k1), ("key-1", &k2)], 2);
+        let (bundle, _) = make_bundle(2, "2999-01-01T00:00:00Z", &[&k1, &k2]);
+
+        let result = verify_bundle(&bundle, &trust, 1);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_verify_bundle_rejects_insufficient_signatures() {
+        let k1 = signing_key(1);
+        let k2 = signing_key(2);
+        let trust = trust_root(&[("key-0", &k1), ("key-1", &k2)], 2);
+        let (bundle, _) = make_bundle(2, "2999-01-01T00:00:00Z", &[&k1]);
+
+        let err = verify_bundle(&bundle, &trust, 1).unwrap_err();
+        assert!(matches!(err, UpdateError::InsufficientSignatures { valid: 1, threshold: 2 }));
+    }
+
+    #[test]
+    fn test_verify_bundle_rejects_rollback() {
+        let k1 = signing_key(1);
+        let k2 = signing_key(2);
+        let trust = trust_root(&[("key-0", &k1), ("key-1", &k2)], 2);
+        let (bundle, _) = make_bundle(1, "2999-01-01T00:00:00Z", &[&k1, &k2]);
+
+        let err = verify_bundle(&bundle, &trust, 5).unwrap_err();
+        assert!(matches!(err, UpdateError::RollbackDetected { .. }));
+    }
+
+    #[test]
+    fn test_verify_bundle_rejects_expired() {
+        let k1 = signing_key(1);
+        let k2 = signing_key(2);
+        let trust = trust_root(&[("key-0", &k1), ("key-1", &k2)], 2);
+        let (bundle, _) = make_bundle(2, "2000-01-01T00:00:00Z", &[&k1, &k2]);
+
+        let err = verify_bundle(&bundle, &trust, 1).unwrap_err();
+        assert!(matches!(err, UpdateError::Expired(_)));
+    }
+
+    #[test]
+    fn test_verify_bundle_rejects_tampered_file() {
+        let k1 = signing_key(1);
+        let k2 = signing_key(2);
+        let trust = trust_root(&[("key-0", &k1), ("key-1", &k2)], 2);
+        let (mut bundle, _) = make_bundle(2, "2999-01-01T00:00:00Z", &[&k1, &k2]);
+        bundle.tamper_file("ignore_instructions.txt", "(?i)totally different");
+
+        let err = verify_bundle(&bundle, &trust, 1).unwrap_err();
+        assert!(matches!(err, UpdateError::HashMismatch(_)));
+    }
+}
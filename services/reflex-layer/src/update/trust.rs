@@ -0,0 +1,78 @@
+//! Trusted root keys and threshold-signature verification
+
+use crate::update::bundle::SignedBundle;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+/// A single trusted root key, identified by the ID used in bundle signatures
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    /// Key ID as referenced in `SignedBundle::signatures`
+    pub key_id: String,
+    /// Ed25519 public key
+    pub public_key: VerifyingKey,
+}
+
+impl TrustedKey {
+    /// Create a new trusted key entry
+    pub fn new(key_id: String, public_key: VerifyingKey) -> Self {
+        Self { key_id, public_key }
+    }
+}
+
+/// Set of trusted root keys and the signature threshold required to accept a bundle
+#[derive(Debug, Clone)]
+pub struct TrustRoot {
+    keys: Vec<TrustedKey>,
+    threshold: usize,
+}
+
+impl TrustRoot {
+    /// Create a new trust root
+    ///
+    /// # Arguments
+    /// * `keys` - Trusted root public keys
+    /// * `threshold` - Minimum number of distinct trusted keys that must
+    ///   validly sign a bundle's metadata for it to be accepted (e.g. 2-of-3)
+    pub fn new(keys: Vec<TrustedKey>, threshold: usize) -> Self {
+        Self { keys, threshold }
+    }
+
+    /// Minimum number of valid signatures required to accept a bundle
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Count how many of the bundle's signatures validate against a distinct
+    /// trusted key
+    ///
+    /// Each trusted key is counted at most once, so duplicate signatures
+    /// from the same key cannot be used to inflate the threshold.
+    pub fn count_valid_signatures(&self, bundle: &SignedBundle) -> usize {
+        let mut validated = std::collections::HashSet::new();
+
+        for (key_id, sig_bytes) in bundle.signatures() {
+            if validated.contains(key_id) {
+                continue;
+            }
+
+            let Some(trusted) = self.keys.iter().find(|k| &k.key_id == key_id) else {
+                continue;
+            };
+
+            let Ok(sig_array): Result<[u8; 64], _> = sig_bytes.as_slice().try_into() else {
+                continue;
+            };
+            let signature = Signature::from_bytes(&sig_array);
+
+            if trusted
+                .public_key
+                .verify(bundle.metadata_bytes(), &signature)
+                .is_ok()
+            {
+                validated.insert(key_id.clone());
+            }
+        }
+
+        validated.len()
+    }
+}